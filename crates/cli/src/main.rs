@@ -1,17 +1,23 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 
 use bumpalo::Bump;
-use common::diagnostic::{self, ANSII_CLEAR, ANSII_COLOR_RED, ANSII_UNDERLINED};
-use ide::{IdeCtx, IdeDiagnostics};
-use toml::TomlCtx;
+use common::diagnostic::{
+    self, FileDiagnostic, RelatedLocation, ANSII_CLEAR, ANSII_COLOR_RED, ANSII_UNDERLINED,
+};
+use common::source::{FileId, Location, SourceMap};
+use common::Diagnostics;
+use ide::{IdeCtx, IdeDiagnostics, State};
+use toml::{DocumentBuilder, MapTable, TomlCtx};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum Mode {
     /// Validate arbitrary toml files.
     Validate,
-    /// Check a `Cargo.toml` manifest.
+    /// Check a `Cargo.toml` manifest, or the manifest embedded in a cargo script.
     Check,
+    /// Write a starter `Cargo.toml` manifest.
+    Init,
 }
 
 macro_rules! error {
@@ -44,6 +50,7 @@ fn main() -> ExitCode {
     let mode = match mode_str.as_str() {
         "validate" => Mode::Validate,
         "check" => Mode::Check,
+        "init" => Mode::Init,
         _ => input_error!("invalid mode `{mode_str}`"),
     };
 
@@ -51,51 +58,89 @@ fn main() -> ExitCode {
         input_error!("missing argument <file>");
     };
     let path: &Path = path.as_ref();
+    let is_script = path.extension().is_some_and(|e| e == "rs");
     if let Some(filename) = path.file_name() {
-        if mode == Mode::Check && filename != "Cargo.toml" {
-            input_error!("file isn't named `Cargo.toml`, use mode `validate` for arbitrary toml files");
+        if mode == Mode::Check && filename != "Cargo.toml" && !is_script {
+            input_error!("file isn't named `Cargo.toml` or a `.rs` script, use mode `validate` for arbitrary toml files");
         }
     } else {
         input_error!("<file> path is empty");
     }
 
+    if mode == Mode::Init {
+        return init(path);
+    }
+
     let text = match std::fs::read_to_string(path) {
         Ok(text) => text,
         Err(e) => error!("error reading from file: {e}"),
     };
-    let lines = diagnostic::lines(&text);
+    let mut sources = SourceMap::new();
+    let file = sources.add(path, text);
+    let text = &sources.get(file).text;
 
     let start = std::time::SystemTime::now();
     let mut ctx = IdeDiagnostics::default();
     let bump = Bump::new();
-    let tokens = ctx.lex(&bump, &text);
+    let tokens = if is_script {
+        let range = match ide::script::find_frontmatter(&mut ctx, text) {
+            Some(frontmatter) => frontmatter.manifest,
+            None => 0..0,
+        };
+        ctx.lex_embedded(&bump, text, range)
+    } else {
+        ctx.lex(&bump, text)
+    };
     let lexing = std::time::SystemTime::now();
     let asts = ctx.parse(&bump, &tokens);
     let parsing = std::time::SystemTime::now();
     let map = ctx.map(&asts);
     let mapping = std::time::SystemTime::now();
+    let root_bump = Bump::new();
+    let mut root = None;
     if mode == Mode::Check {
-        let _state = ctx.check(&map);
+        let state = if is_script {
+            ctx.check_script(&map)
+        } else {
+            ctx.check(&map)
+        };
+        if !is_script {
+            let implicit_lib = path.with_file_name("src").join("lib.rs").is_file();
+            ide::check::lint_version_reqs(&mut ctx, &state, implicit_lib);
+            root = check_workspace(&mut ctx, &root_bump, path, &map, &state);
+        }
     }
     let checking = std::time::SystemTime::now();
     let simple = toml::util::map_simple(map);
     let end = std::time::SystemTime::now();
 
     println!("{:#?}", simple);
+    let root_file = root.map(|root| match root {
+        WorkspaceRoot::This => file,
+        WorkspaceRoot::Other { path, text } => sources.add(path, text),
+    });
+    let mut ctx = ctx.in_file(file);
+    if let Some(root_file) = root_file {
+        add_root_locations(&mut ctx, root_file);
+    }
     ctx.sort_diagnostics();
+    let format = match std::env::var_os("NO_COLOR") {
+        Some(v) if !v.is_empty() => diagnostic::Format::Plain,
+        _ => diagnostic::Format::Ansii,
+    };
     let mut msg = String::new();
     for error in ctx.errors.iter() {
-        diagnostic::display(&mut msg, error, &lines).unwrap();
+        diagnostic::display_files_as(&mut msg, format, error, &sources, file).unwrap();
         println!("{msg}");
         msg.clear()
     }
     for warning in ctx.warnings.iter() {
-        diagnostic::display(&mut msg, warning, &lines).unwrap();
+        diagnostic::display_files_as(&mut msg, format, warning, &sources, file).unwrap();
         println!("{msg}");
         msg.clear()
     }
     for info in ctx.infos.iter() {
-        diagnostic::display(&mut msg, info, &lines).unwrap();
+        diagnostic::display_files_as(&mut msg, format, info, &sources, file).unwrap();
         println!("{msg}");
         msg.clear()
     }
@@ -121,10 +166,126 @@ fn main() -> ExitCode {
     ExitCode::SUCCESS
 }
 
+/// The root manifest of the workspace the checked manifest belongs to.
+enum WorkspaceRoot {
+    /// The checked manifest is the root manifest.
+    This,
+    Other {
+        path: PathBuf,
+        text: String,
+    },
+}
+
+/// Resolve the fields and dependencies inherited from the workspace, and validate the members if
+/// this is the root manifest. Diagnostics of the root manifest are only shown when it is checked
+/// directly.
+fn check_workspace<'a>(
+    ctx: &mut IdeDiagnostics,
+    bump: &Bump,
+    path: &Path,
+    map: &MapTable<'a>,
+    state: &State<'a>,
+) -> Option<WorkspaceRoot> {
+    if let Some(workspace) = state.workspace() {
+        ide::check::resolve_inherited(ctx, map, Some(workspace));
+        ide::check::discover_members(ctx, path, state);
+        return Some(WorkspaceRoot::This);
+    }
+
+    let root = ide::check::find_root_manifest(path)
+        .and_then(|root| std::fs::read_to_string(&root).ok().map(|text| (root, text)));
+    let Some((root_path, root_text)) = root else {
+        ide::check::resolve_inherited(ctx, map, None);
+        return None;
+    };
+
+    let text = bump.alloc_str(&root_text);
+    let mut root_ctx = IdeDiagnostics::default();
+    let tokens = root_ctx.lex(bump, text);
+    let asts = root_ctx.parse(bump, &tokens);
+    let root_map = root_ctx.map(&asts);
+    let root_state = root_ctx.check(&root_map);
+    ide::check::resolve_inherited(ctx, map, root_state.workspace());
+    Some(WorkspaceRoot::Other {
+        path: root_path,
+        text: root_text,
+    })
+}
+
+/// Point diagnostics about inherited dependencies to the related entries in the root manifest.
+fn add_root_locations(
+    ctx: &mut Diagnostics<
+        FileDiagnostic<ide::Error>,
+        FileDiagnostic<ide::Warning>,
+        FileDiagnostic<ide::Info>,
+    >,
+    root_file: FileId,
+) {
+    for error in ctx.errors.iter_mut() {
+        if let Some((span, annotation)) = error.diagnostic.root_location() {
+            let location = Location::new(root_file, span);
+            error
+                .related
+                .push(RelatedLocation::new(location, annotation));
+        }
+    }
+    for warning in ctx.warnings.iter_mut() {
+        if let Some((span, annotation)) = warning.diagnostic.root_location() {
+            let location = Location::new(root_file, span);
+            warning
+                .related
+                .push(RelatedLocation::new(location, annotation));
+        }
+    }
+}
+
+fn init(path: &Path) -> ExitCode {
+    if path.exists() {
+        let path = path.display();
+        error!("`{path}` already exists");
+    }
+
+    let dir = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p.to_path_buf(),
+        _ => match std::env::current_dir() {
+            Ok(dir) => dir,
+            Err(e) => error!("error reading current directory: {e}"),
+        },
+    };
+    let name = dir
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("package");
+
+    let manifest = DocumentBuilder::new()
+        .table("package")
+        .key("name", name)
+        .key("version", "0.1.0")
+        .key("edition", "2021")
+        .blank_line()
+        .comment("See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html")
+        .blank_line()
+        .table("dependencies")
+        .render();
+
+    if let Err(e) = std::fs::write(path, manifest) {
+        error!("error writing to file: {e}");
+    }
+
+    ExitCode::SUCCESS
+}
+
 fn help_message() {
     eprintln!("ctoml <mode> <file>");
     eprintln!();
     eprintln!("modes:");
     eprintln!("  {ANSII_UNDERLINED}validate{ANSII_CLEAR}  to validate arbitrary toml files");
     eprintln!("  {ANSII_UNDERLINED}check{ANSII_CLEAR}     to check a `Cargo.toml` manifest");
+    eprintln!(
+        "  {ANSII_UNDERLINED}init{ANSII_CLEAR}      to write a starter `Cargo.toml` manifest"
+    );
+    eprintln!();
+    eprintln!(
+        "For `.rs` files the manifest embedded in the frontmatter of a cargo script is used."
+    );
 }
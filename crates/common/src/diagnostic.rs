@@ -1,6 +1,13 @@
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+
 use unicode_width::UnicodeWidthStr;
 
-use crate::Span;
+use crate::source::{FileId, Location, SourceMap};
+use crate::{FmtStr, Pos, Span};
+
+#[cfg(test)]
+mod test;
 
 pub fn lines(input: &str) -> Vec<&str> {
     let mut lines = input.split('\n').collect::<Vec<_>>();
@@ -15,7 +22,9 @@ pub fn lines(input: &str) -> Vec<&str> {
 }
 
 pub fn cmp<D: Diagnostic>(a: &D, b: &D) -> std::cmp::Ordering {
-    span_cmp(a.span(), b.span())
+    a.file()
+        .cmp(&b.file())
+        .then_with(|| span_cmp(a.span(), b.span()))
 }
 
 pub fn span_cmp(a: Span, b: Span) -> std::cmp::Ordering {
@@ -50,6 +59,166 @@ pub trait Diagnostic {
     fn context_lines(&self) -> Option<&[u32]> {
         None
     }
+
+    /// The file [`Self::span`] refers to. If this is `None`, the diagnostic belongs to the file
+    /// that is currently being checked.
+    fn file(&self) -> Option<FileId> {
+        None
+    }
+
+    /// Related locations which may be in other files, for example the workspace manifest a
+    /// member manifest inherits from.
+    fn related(&self) -> &[RelatedLocation] {
+        &[]
+    }
+
+    /// Secondary labels highlighting other spans in the same file, for example all conflicting
+    /// keys. By default this only contains [`Self::hint`].
+    fn labels(&self) -> Vec<Label> {
+        self.hint().iter().map(Label::from_hint).collect()
+    }
+
+    /// `note:` and `help:` lines shown below the snippets.
+    fn footers(&self) -> Vec<Footer> {
+        Vec::new()
+    }
+}
+
+/// A secondary span with an annotation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Label {
+    pub span: Span,
+    pub annotation: FmtStr,
+}
+
+impl Label {
+    pub fn new(span: Span, annotation: impl Into<FmtStr>) -> Self {
+        Self {
+            span,
+            annotation: annotation.into(),
+        }
+    }
+
+    pub fn from_hint(hint: &impl DiagnosticHint) -> Self {
+        let mut annotation = String::new();
+        _ = hint.annotation(&mut annotation);
+        Self {
+            span: hint.span(),
+            annotation: FmtStr::from_string(annotation),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Footer {
+    Note(FmtStr),
+    Help(FmtStr),
+}
+
+impl Footer {
+    pub fn note(msg: impl Into<FmtStr>) -> Self {
+        Self::Note(msg.into())
+    }
+
+    pub fn help(msg: impl Into<FmtStr>) -> Self {
+        Self::Help(msg.into())
+    }
+
+    pub fn msg(&self) -> &str {
+        match self {
+            Footer::Note(m) | Footer::Help(m) => m,
+        }
+    }
+}
+
+impl std::fmt::Display for Footer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Footer::Note(m) => write!(f, "note: {}", &**m),
+            Footer::Help(m) => write!(f, "help: {}", &**m),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RelatedLocation {
+    pub location: Location,
+    /// A short description shown inline next to the spanned text.
+    pub annotation: FmtStr,
+}
+
+impl RelatedLocation {
+    pub fn new(location: Location, annotation: impl Into<FmtStr>) -> Self {
+        Self {
+            location,
+            annotation: annotation.into(),
+        }
+    }
+}
+
+/// A diagnostic that is associated with a file and optionally related locations in other files.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileDiagnostic<D> {
+    pub file: FileId,
+    pub diagnostic: D,
+    pub related: Vec<RelatedLocation>,
+}
+
+impl<D> FileDiagnostic<D> {
+    pub fn new(file: FileId, diagnostic: D) -> Self {
+        Self {
+            file,
+            diagnostic,
+            related: Vec::new(),
+        }
+    }
+
+    pub fn with_related(mut self, related: RelatedLocation) -> Self {
+        self.related.push(related);
+        self
+    }
+}
+
+impl<D: Diagnostic> Diagnostic for FileDiagnostic<D> {
+    type Hint = D::Hint;
+
+    const SEVERITY: Severity = D::SEVERITY;
+
+    fn span(&self) -> Span {
+        self.diagnostic.span()
+    }
+
+    fn description(&self, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        self.diagnostic.description(f)
+    }
+
+    fn annotation(&self, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        self.diagnostic.annotation(f)
+    }
+
+    fn hint(&self) -> Option<Self::Hint> {
+        self.diagnostic.hint()
+    }
+
+    fn context_lines(&self) -> Option<&[u32]> {
+        self.diagnostic.context_lines()
+    }
+
+    fn file(&self) -> Option<FileId> {
+        Some(self.file)
+    }
+
+    fn related(&self) -> &[RelatedLocation] {
+        &self.related
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        self.diagnostic.labels()
+    }
+
+    fn footers(&self) -> Vec<Footer> {
+        self.diagnostic.footers()
+    }
 }
 
 pub trait DiagnosticHint {
@@ -81,60 +250,154 @@ impl std::fmt::Display for Severity {
     }
 }
 
+/// The output format used when displaying diagnostics.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Plain text without any styling.
+    Plain,
+    /// Text styled using ANSI escape sequences.
+    #[default]
+    Ansii,
+    /// Text styled using `<span>` elements with one of the classes `error`, `warning`, `info`,
+    /// `hint` or `gutter`. Special characters are escaped, so the output can be embedded inside
+    /// a `<pre>` element.
+    Html,
+}
+
+/// Maximum number of lines that are displayed for a span crossing lines, if it is longer only
+/// the first and last few lines are displayed.
+const MAX_MULTILINE_LINES: u32 = 6;
+
 pub fn display(
     f: &mut impl std::fmt::Write,
     diagnostic: &impl Diagnostic,
     lines: &[&str],
 ) -> std::fmt::Result {
-    fn display_context_lines(
-        f: &mut impl std::fmt::Write,
-        lines: &[&str],
-        context_lines: &[u32],
-        range: std::ops::Range<u32>,
-    ) -> std::fmt::Result {
-        for &l in context_lines.iter() {
-            if l < range.start {
-                continue;
+    display_as(f, Format::Ansii, diagnostic, lines)
+}
+
+pub fn display_as<D: Diagnostic>(
+    f: &mut impl std::fmt::Write,
+    format: Format,
+    diagnostic: &D,
+    lines: &[&str],
+) -> std::fmt::Result {
+    let out = &mut Out { f, format };
+    display_header(out, diagnostic, None, lines)?;
+    display_snippets(out, diagnostic, lines)?;
+    display_footers(out, &diagnostic.footers())
+}
+
+/// Display a diagnostic, that may refer to multiple files. Snippets are taken from the files
+/// registered in `sources`. If the diagnostic isn't associated with a file, `file` is used.
+pub fn display_files(
+    f: &mut impl std::fmt::Write,
+    diagnostic: &impl Diagnostic,
+    sources: &SourceMap,
+    file: FileId,
+) -> std::fmt::Result {
+    display_files_as(f, Format::Ansii, diagnostic, sources, file)
+}
+
+pub fn display_files_as<D: Diagnostic>(
+    f: &mut impl std::fmt::Write,
+    format: Format,
+    diagnostic: &D,
+    sources: &SourceMap,
+    file: FileId,
+) -> std::fmt::Result {
+    let out = &mut Out { f, format };
+    let source = sources.get(diagnostic.file().unwrap_or(file));
+    let lines = source.lines();
+    display_header(out, diagnostic, Some(&source.path), &lines)?;
+    display_snippets(out, diagnostic, &lines)?;
+
+    for related in diagnostic.related() {
+        let Location { file, span } = related.location;
+        let source = sources.get(file);
+        let lines = source.lines();
+        display_location(out, ":::", Some(&source.path), span.start, &lines)?;
+        display_empty_gutter(out)?;
+        let label = RenderLabel {
+            span,
+            severity: Severity::Hint,
+            annotation: related.annotation.to_string(),
+        };
+        display_labels(out, vec![label], &[], &lines)?;
+    }
+
+    display_footers(out, &diagnostic.footers())
+}
+
+/// Writes to the underlying writer while escaping special characters if necessary.
+struct Out<'a, W> {
+    f: &'a mut W,
+    format: Format,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Style {
+    Severity(Severity),
+    Gutter,
+}
+
+impl<W: std::fmt::Write> Out<'_, W> {
+    fn start(&mut self, style: Style) -> std::fmt::Result {
+        match self.format {
+            Format::Plain => Ok(()),
+            Format::Ansii => {
+                let color = match style {
+                    Style::Severity(s) => ansii_esc_color(s),
+                    Style::Gutter => ANSII_COLOR_BLUE,
+                };
+                self.f.write_str(color)
             }
-            if l < range.end {
-                display_line(f, l as usize, lines[l as usize])?;
-            } else {
-                break;
+            Format::Html => {
+                let class = match style {
+                    Style::Severity(s) => html_class(s),
+                    Style::Gutter => "gutter",
+                };
+                write!(self.f, "<span class=\"{class}\">")
             }
         }
-        Ok(())
     }
 
-    writeln!(f, "{}", diagnostic.header(lines))?;
-    writeln!(f, "     {ANSII_COLOR_BLUE}|{ANSII_CLEAR}")?;
-
-    let context_lines = diagnostic.context_lines().unwrap_or(&[]);
-    let main_span = diagnostic.span();
-    let hint = diagnostic.hint();
-    let mut current_line = 0;
-
-    if let Some(hint) = &hint {
-        let hint_span = hint.span();
-        if hint_span.start < main_span.start {
-            display_context_lines(f, lines, context_lines, current_line..hint_span.start.line)?;
-            write!(f, "{}", hint.body(lines))?;
-            current_line = hint_span.end.line + 1;
+    fn end(&mut self) -> std::fmt::Result {
+        match self.format {
+            Format::Plain => Ok(()),
+            Format::Ansii => self.f.write_str(ANSII_CLEAR),
+            Format::Html => self.f.write_str("</span>"),
         }
     }
 
-    display_context_lines(f, lines, context_lines, current_line..main_span.start.line)?;
-    write!(f, "{}", diagnostic.body(lines))?;
-    current_line = main_span.end.line + 1;
+    fn styled(&mut self, style: Style, str: &str) -> std::fmt::Result {
+        self.start(style)?;
+        self.write_str(str)?;
+        self.end()
+    }
+}
 
-    if let Some(hint) = &hint {
-        let hint_span = hint.span();
-        if hint_span.start >= main_span.start {
-            display_context_lines(f, lines, context_lines, current_line..hint_span.start.line)?;
-            write!(f, "{}", hint.body(lines))?;
+impl<W: std::fmt::Write> std::fmt::Write for Out<'_, W> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        if self.format != Format::Html {
+            return self.f.write_str(s);
         }
-    }
 
-    Ok(())
+        let mut start = 0;
+        for (i, c) in s.char_indices() {
+            let escaped = match c {
+                '<' => "&lt;",
+                '>' => "&gt;",
+                '&' => "&amp;",
+                '"' => "&quot;",
+                _ => continue,
+            };
+            self.f.write_str(&s[start..i])?;
+            self.f.write_str(escaped)?;
+            start = i + 1;
+        }
+        self.f.write_str(&s[start..])
+    }
 }
 
 pub trait DisplayDiagnosticHeader: Diagnostic + Sized {
@@ -157,26 +420,296 @@ pub struct DiagnosticHeader<'a, D: Diagnostic, T> {
 
 impl<'a, D: Diagnostic, T: AsRef<str>> std::fmt::Display for DiagnosticHeader<'a, D, T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        display_header(f, self.diagnostic, self.text)
+        let out = &mut Out {
+            f,
+            format: Format::Ansii,
+        };
+        display_header_line(out, self.diagnostic, None, self.text)
     }
 }
 
 fn display_header<D: Diagnostic>(
-    f: &mut impl std::fmt::Write,
+    out: &mut Out<impl std::fmt::Write>,
+    diagnostic: &D,
+    path: Option<&std::path::Path>,
+    text: &[impl AsRef<str>],
+) -> std::fmt::Result {
+    display_header_line(out, diagnostic, path, text)?;
+    display_empty_gutter(out)
+}
+
+fn display_header_line<D: Diagnostic>(
+    out: &mut Out<impl std::fmt::Write>,
     diagnostic: &D,
+    path: Option<&std::path::Path>,
     text: &[impl AsRef<str>],
 ) -> std::fmt::Result {
     let severity = D::SEVERITY;
-    let color = ansii_esc_color(severity);
-    write!(f, "{color}{severity}{ANSII_CLEAR}: ")?;
-    diagnostic.description(f)?;
-    f.write_char('\n')?;
-    let pos = diagnostic.span().start;
+    out.styled(Style::Severity(severity), &severity.to_string())?;
+    out.write_str(": ")?;
+    diagnostic.description(out)?;
+    out.write_char('\n')?;
+    display_location(out, "-->", path, diagnostic.span().start, text)
+}
+
+fn display_location(
+    out: &mut Out<impl std::fmt::Write>,
+    arrow: &str,
+    path: Option<&std::path::Path>,
+    pos: Pos,
+    text: &[impl AsRef<str>],
+) -> std::fmt::Result {
     let line_nr = pos.line + 1;
-    let char = text[pos.line as usize].as_ref()[0..pos.char as usize]
-        .chars()
-        .count();
-    write!(f, "    {ANSII_COLOR_BLUE}-->{ANSII_CLEAR} {line_nr}:{char}")
+    let line = text.get(pos.line as usize).map_or("", |l| l.as_ref());
+    let char = line[0..(pos.char as usize).min(line.len())].chars().count();
+    out.write_str("    ")?;
+    out.styled(Style::Gutter, arrow)?;
+    out.write_char(' ')?;
+    if let Some(path) = path {
+        write!(out, "{}:", path.display())?;
+    }
+    write!(out, "{line_nr}:{char}")
+}
+
+fn display_empty_gutter(out: &mut Out<impl std::fmt::Write>) -> std::fmt::Result {
+    out.write_char('\n')?;
+    out.write_str("     ")?;
+    out.styled(Style::Gutter, "|")?;
+    out.write_char('\n')
+}
+
+fn display_footers(out: &mut Out<impl std::fmt::Write>, footers: &[Footer]) -> std::fmt::Result {
+    for footer in footers {
+        let kind = match footer {
+            Footer::Note(_) => "note",
+            Footer::Help(_) => "help",
+        };
+        out.write_str("     ")?;
+        out.styled(Style::Gutter, "=")?;
+        write!(out, " {kind}: ")?;
+        for (i, line) in footer.msg().lines().enumerate() {
+            if i > 0 {
+                // align with the first line
+                out.write_str("             ")?;
+            }
+            out.write_str(line)?;
+            out.write_char('\n')?;
+        }
+    }
+    Ok(())
+}
+
+/// Display the main span, all secondary labels and context lines of a diagnostic.
+fn display_snippets<D: Diagnostic>(
+    out: &mut Out<impl std::fmt::Write>,
+    diagnostic: &D,
+    lines: &[impl AsRef<str>],
+) -> std::fmt::Result {
+    let mut annotation = String::new();
+    diagnostic.annotation(&mut annotation)?;
+    let mut labels = vec![RenderLabel {
+        span: diagnostic.span(),
+        severity: D::SEVERITY,
+        annotation,
+    }];
+    for label in diagnostic.labels() {
+        labels.push(RenderLabel {
+            span: label.span,
+            severity: Severity::Hint,
+            annotation: label.annotation.to_string(),
+        });
+    }
+
+    let context_lines = diagnostic.context_lines().unwrap_or(&[]);
+    display_labels(out, labels, context_lines, lines)
+}
+
+struct RenderLabel {
+    span: Span,
+    severity: Severity,
+    annotation: String,
+}
+
+impl RenderLabel {
+    fn is_multiline(&self) -> bool {
+        self.span.end.line > self.span.start.line
+    }
+}
+
+fn display_labels(
+    out: &mut Out<impl std::fmt::Write>,
+    mut labels: Vec<RenderLabel>,
+    context_lines: &[u32],
+    text: &[impl AsRef<str>],
+) -> std::fmt::Result {
+    let line_str = |l: u32| text.get(l as usize).map_or("", |l| l.as_ref());
+
+    // A span ending at the start of a line, ends after the previous line.
+    for l in labels.iter_mut() {
+        let span = &mut l.span;
+        if span.end.line > span.start.line && span.end.char == 0 {
+            let line = span.end.line - 1;
+            span.end = Pos::new(line, line_str(line).len() as u32);
+        }
+    }
+
+    // Spans crossing lines are drawn in gutter columns, in the order they start.
+    let mut multiline = (0..labels.len())
+        .filter(|i| labels[*i].is_multiline())
+        .collect::<Vec<_>>();
+    multiline.sort_by_key(|i| labels[*i].span.start);
+    let num_cols = multiline.len();
+    // Whether the span starts at the first non-whitespace character of its line, in which case
+    // it's drawn as `/` in front of the line.
+    let slash = (multiline.iter())
+        .map(|i| {
+            let start = labels[*i].span.start;
+            let line = line_str(start.line);
+            line[..(start.char as usize).min(line.len())]
+                .trim()
+                .is_empty()
+        })
+        .collect::<Vec<_>>();
+
+    let mut displayed = BTreeSet::new();
+    for l in labels.iter() {
+        let Span { start, end } = l.span;
+        if end.line - start.line < MAX_MULTILINE_LINES {
+            displayed.extend(start.line..=end.line);
+        } else {
+            displayed.extend([start.line, start.line + 1, end.line - 1, end.line]);
+        }
+    }
+    let last_start = labels.iter().map(|l| l.span.start.line).max().unwrap_or(0);
+    displayed.extend(context_lines.iter().filter(|l| **l < last_start));
+
+    let mut active = vec![false; num_cols];
+    let mut prev_line = None;
+    for line_nr in displayed {
+        let line = line_str(line_nr);
+
+        if let Some(prev) = prev_line {
+            if line_nr > prev + 1 && active.iter().any(|a| *a) {
+                out.styled(Style::Gutter, "...")?;
+                out.write_char('\n')?;
+            }
+        }
+        prev_line = Some(line_nr);
+
+        // the line itself
+        for (c, i) in multiline.iter().enumerate() {
+            let span = labels[*i].span;
+            active[c] = span.start.line < line_nr && line_nr <= span.end.line
+                || slash[c] && span.start.line == line_nr;
+        }
+        let line_nr_str = format!("{:4} |", line_nr + 1);
+        out.styled(Style::Gutter, &line_nr_str)?;
+        out.write_char(' ')?;
+        for (c, i) in multiline.iter().enumerate() {
+            let l = &labels[*i];
+            let char = if slash[c] && l.span.start.line == line_nr {
+                "/"
+            } else if active[c] {
+                "|"
+            } else {
+                " "
+            };
+            out.styled(Style::Severity(l.severity), char)?;
+            out.write_char(' ')?;
+        }
+        display_line_text(out, line)?;
+        out.write_char('\n')?;
+
+        // spans crossing lines that end on this line
+        for (c, i) in multiline.iter().enumerate().rev() {
+            let l = &labels[*i];
+            if l.span.end.line != line_nr {
+                continue;
+            }
+            let end = (l.span.end.char as usize).min(line.len());
+            let last_char_start = line[..end].char_indices().next_back().map_or(0, |(i, _)| i);
+            display_underline_gutter(out, &labels, &multiline, &active, c)?;
+            out.start(Style::Severity(l.severity))?;
+            out.write_char('|')?;
+            let num_underscores = 1 + 2 * (num_cols - c - 1) + calc_width(&line[..last_char_start]);
+            write_repeated(out, '_', num_underscores)?;
+            out.write_char(underline_char(l.severity))?;
+            out.write_char(' ')?;
+            out.write_str(&l.annotation)?;
+            out.end()?;
+            out.write_char('\n')?;
+            active[c] = false;
+        }
+
+        // spans on this line
+        let mut single = (labels.iter())
+            .filter(|l| !l.is_multiline() && l.span.start.line == line_nr)
+            .collect::<Vec<_>>();
+        single.sort_by_key(|l| l.span.start.char);
+        for l in single {
+            let start = (l.span.start.char as usize).min(line.len());
+            let end = (l.span.end.char as usize).clamp(start, line.len());
+            display_underline_gutter(out, &labels, &multiline, &active, num_cols)?;
+            let num_spaces = calc_width(&line[0..start]);
+            write_repeated(out, ' ', num_spaces)?;
+            out.start(Style::Severity(l.severity))?;
+            let num_carets = line[start..end].width().max(1);
+            write_repeated(out, underline_char(l.severity), num_carets)?;
+            out.write_char(' ')?;
+            out.write_str(&l.annotation)?;
+            out.end()?;
+            out.write_char('\n')?;
+        }
+
+        // spans crossing lines that start on this line
+        for (c, i) in multiline.iter().enumerate() {
+            let l = &labels[*i];
+            if l.span.start.line != line_nr || slash[c] {
+                continue;
+            }
+            let start = (l.span.start.char as usize).min(line.len());
+            display_underline_gutter(out, &labels, &multiline, &active, c)?;
+            out.start(Style::Severity(l.severity))?;
+            out.write_char(' ')?;
+            let num_underscores = 1 + 2 * (num_cols - c - 1) + calc_width(&line[..start]);
+            write_repeated(out, '_', num_underscores)?;
+            out.write_char(underline_char(l.severity))?;
+            out.end()?;
+            out.write_char('\n')?;
+            active[c] = true;
+        }
+    }
+
+    Ok(())
+}
+
+/// Display the gutter of an underline row up to the column `until`.
+fn display_underline_gutter(
+    out: &mut Out<impl std::fmt::Write>,
+    labels: &[RenderLabel],
+    multiline: &[usize],
+    active: &[bool],
+    until: usize,
+) -> std::fmt::Result {
+    out.write_str("     ")?;
+    out.styled(Style::Gutter, "|")?;
+    out.write_char(' ')?;
+    for (c, i) in multiline.iter().enumerate().take(until) {
+        if active[c] {
+            out.styled(Style::Severity(labels[*i].severity), "|")?;
+        } else {
+            out.write_char(' ')?;
+        }
+        out.write_char(' ')?;
+    }
+    Ok(())
+}
+
+fn write_repeated(out: &mut impl std::fmt::Write, c: char, n: usize) -> std::fmt::Result {
+    for _ in 0..n {
+        out.write_char(c)?;
+    }
+    Ok(())
 }
 
 pub trait DisplayDiagnosticBody: Diagnostic + Sized {
@@ -217,76 +750,47 @@ pub struct DiagnosticHintBody<'a, D: DiagnosticHint, T> {
 
 impl<'a, D: Diagnostic, T: AsRef<str> + 'a> std::fmt::Display for DiagnosticBody<'a, D, T> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        display_body(
+        let mut annotation = String::new();
+        self.diagnostic.annotation(&mut annotation)?;
+        let label = RenderLabel {
+            span: self.diagnostic.span(),
+            severity: D::SEVERITY,
+            annotation,
+        };
+        let out = &mut Out {
             f,
-            |f| self.diagnostic.annotation(f),
-            D::SEVERITY,
-            self.diagnostic.span(),
-            self.text,
-        )
+            format: Format::Ansii,
+        };
+        display_labels(out, vec![label], &[], self.text)
     }
 }
 
 impl<'a, D: DiagnosticHint, T: AsRef<str> + 'a> std::fmt::Display for DiagnosticHintBody<'a, D, T> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        display_body(
+        let label = Label::from_hint(self.diagnostic);
+        let label = RenderLabel {
+            span: label.span,
+            severity: Severity::Hint,
+            annotation: label.annotation.to_string(),
+        };
+        let out = &mut Out {
             f,
-            |f| self.diagnostic.annotation(f),
-            Severity::Hint,
-            self.diagnostic.span(),
-            self.text,
-        )
-    }
-}
-
-fn display_body<F: std::fmt::Write>(
-    f: &mut F,
-    annotation: impl Fn(&mut F) -> std::fmt::Result,
-    severity: Severity,
-    span: Span,
-    text: &[impl AsRef<str>],
-) -> std::fmt::Result {
-    let start_line = span.start.line as usize;
-    let end_line = span.end.line as usize + 1;
-    let num_lines = end_line - start_line;
-    let color = ansii_esc_color(severity);
-    let underline_char = underline_char(severity);
-
-    for (i, line) in text[start_line..end_line].iter().enumerate() {
-        let line_nr = start_line + i;
-        let line = line.as_ref();
-        display_line(f, line_nr, line)?;
-
-        let col_start = if i == 0 { span.start.char as usize } else { 0 };
-        let col_end = if i == num_lines - 1 {
-            span.end.char as usize
-        } else {
-            line.len()
+            format: Format::Ansii,
         };
-        let num_spaces = calc_width(&line[0..col_start]);
-        write!(f, "     {ANSII_COLOR_BLUE}|{ANSII_CLEAR} ")?;
-        write!(f, "{:num_spaces$}{color}", "")?;
-
-        let spanned_text = &line[col_start..col_end];
-        let num_carets = spanned_text.width().max(1);
-        for _ in 0..num_carets {
-            f.write_char(underline_char)?;
-        }
-        if i == num_lines - 1 {
-            f.write_char(' ')?;
-            annotation(f)?;
-        }
-        writeln!(f, "{ANSII_CLEAR}")?;
+        display_labels(out, vec![label], &[], self.text)
     }
-
-    Ok(())
 }
 
 /// `line_nr` is 0-based
 pub fn display_line(f: &mut impl std::fmt::Write, line_nr: usize, line: &str) -> std::fmt::Result {
     let line_nr = line_nr + 1;
     write!(f, "{ANSII_COLOR_BLUE}{line_nr:4} |{ANSII_CLEAR} ")?;
+    display_line_text(f, line)?;
+    f.write_char('\n')?;
+    Ok(())
+}
 
+fn display_line_text(f: &mut impl std::fmt::Write, line: &str) -> std::fmt::Result {
     let mut next_start = 0;
     for (j, c) in line.char_indices() {
         match c {
@@ -300,9 +804,7 @@ pub fn display_line(f: &mut impl std::fmt::Write, line_nr: usize, line: &str) ->
             _ => (),
         }
     }
-    f.write_str(&line[next_start..])?;
-    f.write_char('\n')?;
-    Ok(())
+    f.write_str(&line[next_start..])
 }
 
 pub fn calc_width(line: &str) -> usize {
@@ -339,6 +841,15 @@ fn ansii_esc_color(severity: Severity) -> &'static str {
     }
 }
 
+fn html_class(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "info",
+        Severity::Hint => "hint",
+    }
+}
+
 fn underline_char(severity: Severity) -> char {
     match severity {
         Severity::Error => '^',
@@ -0,0 +1,284 @@
+use pretty_assertions::assert_eq;
+
+use super::*;
+use crate::{Diagnostics, Pos};
+
+#[derive(Debug, PartialEq)]
+struct TestError(Span);
+
+impl Diagnostic for TestError {
+    type Hint = TestHint;
+
+    const SEVERITY: Severity = Severity::Error;
+
+    fn span(&self) -> Span {
+        self.0
+    }
+
+    fn description(&self, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        f.write_str("missing inherited dependency")
+    }
+
+    fn annotation(&self, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        f.write_str("not found")
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct TestHint;
+
+impl DiagnosticHint for TestHint {
+    fn span(&self) -> Span {
+        unreachable!()
+    }
+
+    fn annotation(&self, _f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        unreachable!()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct LabeledError {
+    span: Span,
+    labels: Vec<Label>,
+    footers: Vec<Footer>,
+}
+
+impl Diagnostic for LabeledError {
+    type Hint = TestHint;
+
+    const SEVERITY: Severity = Severity::Error;
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn description(&self, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        f.write_str("conflicting <sources>")
+    }
+
+    fn annotation(&self, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        f.write_str("conflict")
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        self.labels.clone()
+    }
+
+    fn footers(&self) -> Vec<Footer> {
+        self.footers.clone()
+    }
+}
+
+fn span(start: (u32, u32), end: (u32, u32)) -> Span {
+    Span::new(Pos::new(start.0, start.1), Pos::new(end.0, end.1))
+}
+
+fn display_plain(diagnostic: &impl Diagnostic, input: &str) -> String {
+    let mut msg = String::new();
+    display_as(&mut msg, Format::Plain, diagnostic, &lines(input)).unwrap();
+    msg
+}
+
+fn strip_ansii(str: &str) -> String {
+    let mut out = String::new();
+    let mut chars = str.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|c| *c == 'm');
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[test]
+fn display_related_location_in_other_file() {
+    let mut sources = SourceMap::new();
+    let workspace = sources.add("Cargo.toml", "[workspace.dependencies]\nfoo = \"1\"\n");
+    let member = sources.add(
+        "member/Cargo.toml",
+        "[dependencies]\nbar.workspace = true\n",
+    );
+
+    let span = Span::new(Pos::new(1, 0), Pos::new(1, 3));
+    let related = Location::new(workspace, Span::new(Pos::new(0, 1), Pos::new(0, 23)));
+    let error = FileDiagnostic::new(member, TestError(span))
+        .with_related(RelatedLocation::new(related, "defined here"));
+
+    let mut msg = String::new();
+    display_files(&mut msg, &error, &sources, workspace).unwrap();
+    assert_eq!(
+        "\
+error: missing inherited dependency
+    --> member/Cargo.toml:2:0
+     |
+   2 | bar.workspace = true
+     | ^^^ not found
+    ::: Cargo.toml:1:1
+     |
+   1 | [workspace.dependencies]
+     |  ---------------------- defined here
+",
+        strip_ansii(&msg)
+    );
+}
+
+#[test]
+fn sort_by_file() {
+    let mut a = Diagnostics::<TestError, TestError, TestError>::default();
+    a.errors.push(TestError(Span::pos(Pos::new(3, 0))));
+    let mut b = Diagnostics::<TestError, TestError, TestError>::default();
+    b.errors.push(TestError(Span::pos(Pos::new(1, 0))));
+
+    let mut combined = b.in_file(FileId(1));
+    combined.append(&mut a.in_file(FileId(0)));
+    combined.sort_diagnostics();
+
+    let files: Vec<_> = combined.errors.iter().map(|e| e.file).collect();
+    assert_eq!(vec![FileId(0), FileId(1)], files);
+}
+
+#[test]
+fn display_multiple_labels_on_one_line() {
+    let input = "foo = { git = \"url\", path = \"..\", registry = \"reg\" }\n";
+    let error = LabeledError {
+        span: span((0, 8), (0, 11)),
+        labels: vec![
+            Label::new(span((0, 33), (0, 41)), "also specified here"),
+            Label::new(span((0, 21), (0, 25)), "and here"),
+        ],
+        footers: vec![
+            Footer::note("only one source may be specified"),
+            Footer::help("remove all but one of the keys"),
+        ],
+    };
+
+    assert_eq!(
+        "\
+error: conflicting <sources>
+    --> 1:8
+     |
+   1 | foo = { git = \"url\", path = \"..\", registry = \"reg\" }
+     |         ^^^ conflict
+     |                      ---- and here
+     |                                  -------- also specified here
+     = note: only one source may be specified
+     = help: remove all but one of the keys
+",
+        display_plain(&error, input)
+    );
+}
+
+#[test]
+fn display_multiline_span_starting_at_line_start() {
+    let input = "[dependencies]\nfoo = { version = \"1\",\n  features = [] }\n";
+    let error = LabeledError {
+        span: span((1, 0), (2, 16)),
+        labels: vec![Label::new(span((0, 1), (0, 13)), "in this table")],
+        footers: Vec::new(),
+    };
+
+    assert_eq!(
+        "\
+error: conflicting <sources>
+    --> 2:0
+     |
+   1 |   [dependencies]
+     |    ------------ in this table
+   2 | / foo = { version = \"1\",
+   3 | |   features = [] }
+     | |________________^ conflict
+",
+        display_plain(&error, input)
+    );
+}
+
+#[test]
+fn display_multiline_span_starting_inside_line() {
+    let input = "foo = \"\"\"\nabc\ndef\"\"\"\n";
+    let error = LabeledError {
+        span: span((0, 6), (2, 6)),
+        labels: Vec::new(),
+        footers: Vec::new(),
+    };
+
+    assert_eq!(
+        "\
+error: conflicting <sources>
+    --> 1:6
+     |
+   1 |   foo = \"\"\"
+     |  _______^
+   2 | | abc
+   3 | | def\"\"\"
+     | |______^ conflict
+",
+        display_plain(&error, input)
+    );
+}
+
+#[test]
+fn display_long_multiline_span_is_truncated() {
+    let input = "a = [\n1,\n2,\n3,\n4,\n5,\n6,\n]\n";
+    let error = LabeledError {
+        span: span((0, 0), (7, 1)),
+        labels: Vec::new(),
+        footers: vec![Footer::note("first line\nsecond line")],
+    };
+
+    assert_eq!(
+        "\
+error: conflicting <sources>
+    --> 1:0
+     |
+   1 | / a = [
+   2 | | 1,
+...
+   7 | | 6,
+   8 | | ]
+     | |_^ conflict
+     = note: first line
+             second line
+",
+        display_plain(&error, input)
+    );
+}
+
+#[test]
+fn display_html_escapes_text() {
+    let input = "a = \"<b>\"\n";
+    let error = LabeledError {
+        span: span((0, 4), (0, 9)),
+        labels: Vec::new(),
+        footers: vec![Footer::help("use `&`")],
+    };
+
+    let mut msg = String::new();
+    display_as(&mut msg, Format::Html, &error, &lines(input)).unwrap();
+    assert_eq!(
+        "\
+<span class=\"error\">error</span>: conflicting &lt;sources&gt;
+    <span class=\"gutter\">--&gt;</span> 1:4
+     <span class=\"gutter\">|</span>
+<span class=\"gutter\">   1 |</span> a = &quot;&lt;b&gt;&quot;
+     <span class=\"gutter\">|</span>     <span class=\"error\">^^^^^ conflict</span>
+     <span class=\"gutter\">=</span> help: use `&amp;`
+",
+        msg
+    );
+}
+
+#[test]
+fn display_ansii_matches_default() {
+    let input = "a = 1\n";
+    let error = TestError(span((0, 4), (0, 5)));
+
+    let mut ansii = String::new();
+    display_as(&mut ansii, Format::Ansii, &error, &lines(input)).unwrap();
+    let mut default = String::new();
+    display(&mut default, &error, &lines(input)).unwrap();
+    assert_eq!(default, ansii);
+    assert_eq!(display_plain(&error, input), strip_ansii(&ansii));
+}
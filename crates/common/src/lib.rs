@@ -1,9 +1,11 @@
 use std::fmt::Write as _;
 use std::ops::Deref;
 
-use crate::diagnostic::Diagnostic;
+use crate::diagnostic::{Diagnostic, FileDiagnostic};
+use crate::source::FileId;
 
 pub mod diagnostic;
+pub mod source;
 
 pub trait Ctx: Sized {
     type Error;
@@ -87,6 +89,40 @@ where
     }
 }
 
+impl<E, W, I> Diagnostics<E, W, I> {
+    /// Associate all diagnostics with `file`, so they can be combined with diagnostics of other
+    /// files.
+    pub fn in_file(
+        self,
+        file: FileId,
+    ) -> Diagnostics<FileDiagnostic<E>, FileDiagnostic<W>, FileDiagnostic<I>> {
+        Diagnostics {
+            errors: self
+                .errors
+                .into_iter()
+                .map(|d| FileDiagnostic::new(file, d))
+                .collect(),
+            warnings: self
+                .warnings
+                .into_iter()
+                .map(|d| FileDiagnostic::new(file, d))
+                .collect(),
+            infos: self
+                .infos
+                .into_iter()
+                .map(|d| FileDiagnostic::new(file, d))
+                .collect(),
+        }
+    }
+
+    /// Move all diagnostics of `other` into `self`.
+    pub fn append(&mut self, other: &mut Self) {
+        self.errors.append(&mut other.errors);
+        self.warnings.append(&mut other.warnings);
+        self.infos.append(&mut other.infos);
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Span {
     pub start: Pos,
@@ -0,0 +1,85 @@
+use std::path::{Path, PathBuf};
+
+use crate::diagnostic;
+use crate::Span;
+
+/// Identifies a file registered in a [`SourceMap`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FileId(pub u32);
+
+/// A span inside a specific file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Location {
+    pub file: FileId,
+    pub span: Span,
+}
+
+impl Location {
+    #[inline(always)]
+    pub fn new(file: FileId, span: Span) -> Self {
+        Self { file, span }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SourceFile {
+    pub path: PathBuf,
+    pub text: String,
+}
+
+impl SourceFile {
+    pub fn lines(&self) -> Vec<&str> {
+        diagnostic::lines(&self.text)
+    }
+}
+
+/// A registry of all files that diagnostics may refer to.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SourceMap {
+    files: Vec<SourceFile>,
+}
+
+impl SourceMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a file, if a file with the same path was already registered its text is
+    /// replaced and the existing id is returned.
+    pub fn add(&mut self, path: impl Into<PathBuf>, text: impl Into<String>) -> FileId {
+        let path = path.into();
+        let text = text.into();
+        if let Some(id) = self.find(&path) {
+            self.files[id.0 as usize].text = text;
+            return id;
+        }
+
+        let id = FileId(self.files.len() as u32);
+        self.files.push(SourceFile { path, text });
+        id
+    }
+
+    pub fn find(&self, path: &Path) -> Option<FileId> {
+        let idx = self.files.iter().position(|f| f.path == path)?;
+        Some(FileId(idx as u32))
+    }
+
+    /// # Panics
+    ///
+    /// If the file wasn't registered in this source map.
+    pub fn get(&self, id: FileId) -> &SourceFile {
+        &self.files[id.0 as usize]
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (FileId, &SourceFile)> {
+        (self.files.iter().enumerate()).map(|(i, f)| (FileId(i as u32), f))
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
@@ -1,7 +1,9 @@
-use common::diagnostic::{Diagnostic, DiagnosticHint, Severity};
+use common::diagnostic::{Diagnostic, DiagnosticHint, Footer, Label, Severity};
 use common::{Ctx, Diagnostics, FmtStr, Span};
 use toml::util::Datatype;
 
+use crate::{cfg, spdx};
+
 pub trait CargoCtx:
     Ctx<Error = Self::CargoError, Warning = Self::CargoWarning, Info = Self::CargoInfo>
 {
@@ -38,6 +40,16 @@ impl Error {
             kind,
         }
     }
+
+    /// A span in the workspace root manifest that is related to this error, with an annotation.
+    pub fn root_location(&self) -> Option<(Span, &'static str)> {
+        match self.kind {
+            ErrorKind::InheritedDepNotFound { root_span, .. } => {
+                Some((root_span, "workspace dependencies are defined here"))
+            }
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -46,6 +58,10 @@ pub enum ErrorKind {
         expected: Datatype,
         found: Datatype,
     },
+    WrongDatatypeEither {
+        expected: [Datatype; 2],
+        found: Datatype,
+    },
     /// In the 2024 edition keys with underscores are unsupported.
     UnsupportedUnderscore {
         old: &'static str,
@@ -55,10 +71,103 @@ pub enum ErrorKind {
     DepWorkspaceIsFalse,
     AmbigousDepSpecGitPath,
     AmbigousDepSpecGitRegistry,
-    AmbigousGitSpec,
+    /// More than one of `branch`, `tag` and `rev`, with the spans of the others.
+    AmbigousGitSpec {
+        others: Box<[Span]>,
+    },
     /// Invalid in the 2024 edition.
     MissingDepSpec,
     DepIgnoredGitKey(&'static str),
+    /// The frontmatter of a cargo script is missing its closing fence.
+    ScriptUnclosedFrontmatter,
+    /// The frontmatter of a cargo script has an infostring other than `cargo`.
+    ScriptUnsupportedInfostring(FmtStr),
+    /// Keys that aren't allowed in the embedded manifest of a cargo script.
+    ScriptForbiddenKey,
+    /// `field = { workspace = false }`
+    InheritedWorkspaceIsFalse,
+    /// `field = { ... }` without `workspace = true`
+    InheritedWorkspaceMissing,
+    EmptyPackageName,
+    InvalidPackageNameStart(char),
+    InvalidPackageNameChar(char),
+    UnknownEdition(FmtStr),
+    License(spdx::ErrorKind),
+    EmptyFeatureName,
+    InvalidFeatureNameStart(char),
+    InvalidFeatureNameChar(char),
+    /// A feature member that is neither a feature nor an optional dependency.
+    UnknownFeature(FmtStr),
+    FeatureDepNotFound(FmtStr),
+    FeatureDepNotOptional(FmtStr),
+    /// Features can't enable dev-dependencies.
+    FeatureDevDependency(FmtStr),
+    /// `dep?/feature` where the dependency isn't optional.
+    FeatureWeakDepNotOptional(FmtStr),
+    /// An optional dependency is used as a feature, but it has no implicit feature because
+    /// `dep:<name>` is used somewhere.
+    FeatureImplicitDepHidden {
+        dep: FmtStr,
+        dep_span: Span,
+    },
+    FeatureCycle {
+        /// `a -> b -> a`
+        cycle: FmtStr,
+        /// The feature members that form the cycle.
+        edges: Box<[(Span, FmtStr)]>,
+    },
+    UnknownResolver(FmtStr),
+    /// `optional` has to be specified by the members that inherit the dependency.
+    WorkspaceDepOptional,
+    WorkspaceDepInherited,
+    /// A field or dependency is inherited, but no workspace root was found.
+    NoWorkspaceRoot,
+    InheritedDepNotFound {
+        name: FmtStr,
+        /// The `[workspace.dependencies]` table, or `[workspace]` if it doesn't exist, in the root
+        /// manifest.
+        root_span: Span,
+    },
+    InheritedFieldNotFound(&'static str),
+    /// A workspace member path that doesn't exist.
+    MemberNotFound(FmtStr),
+    MemberMissingManifest(FmtStr),
+    DefaultMemberNotMember(FmtStr),
+    UnknownLintLevel(FmtStr),
+    MissingLintLevel,
+    /// A lint name with a tool prefix, and the suggested path: `lints.<tool>.<name>`.
+    LintNameHasTool(FmtStr),
+    /// Lints can't be specified alongside `lints.workspace = true`.
+    LintsWorkspaceOverride,
+    InheritedLintsNotFound,
+    /// `inherits` in `dev`, `release`, `test` or `bench`.
+    BuiltinProfileInherits(FmtStr),
+    /// Custom profiles have to inherit from another profile.
+    MissingProfileInherits,
+    InvalidProfileNameChar(char),
+    ReservedProfileName(FmtStr),
+    /// A key that isn't allowed in `package.<spec>` or `build-override`.
+    ProfileOverrideKey(&'static str),
+    /// A profile setting with an invalid datatype or value, and a description of valid values.
+    InvalidProfileValue(&'static str),
+    UnknownInheritedProfile(FmtStr),
+    ProfileInheritsCycle(FmtStr),
+    /// `[[bin]]`, `[[example]]`, `[[test]]` and `[[bench]]` targets need a name.
+    MissingTargetName,
+    EmptyTargetName,
+    InvalidTargetNameChar(char),
+    /// A target name that is already used by another target of the same kind.
+    DuplicateTargetName {
+        kind: &'static str,
+        first_span: Span,
+    },
+    UnknownCrateType(FmtStr),
+    /// A proc-macro library can't have other crate types.
+    ProcMacroWithOtherCrateTypes,
+    /// A `required-features` entry that is neither a feature nor an optional dependency.
+    UnknownRequiredFeature(FmtStr),
+    /// An invalid `[target.<spec>]` key.
+    TargetSpec(cfg::ErrorKind),
 }
 
 impl Diagnostic for Error {
@@ -75,6 +184,7 @@ impl Diagnostic for Error {
         let Self { path, kind, .. } = self;
         match kind {
             WrongDatatype { expected, found } => write!(f, "expected `{path}` to be of type {expected}, found {found}"),
+            WrongDatatypeEither { expected: [a, b], found } => write!(f, "expected `{path}` to be of type {a} or {b}, found {found}"),
             UnsupportedUnderscore { old, new } => {
                 if !path.is_empty() {
                     write!(f, "`{path}`: ")?;
@@ -85,9 +195,59 @@ impl Diagnostic for Error {
             DepWorkspaceIsFalse => write!(f, "invalid dependency specification `{path}`; `workspace` cannot be false"),
             AmbigousDepSpecGitPath => write!(f, "dependency specification `{path}` is ambigous, only one of `git` or `path` is allowed"),
             AmbigousDepSpecGitRegistry => write!(f, "dependency specification `{path}` is ambigous, only one of `git` or `registry` is allowed"),
-            AmbigousGitSpec => write!(f, "dependency specification `{path}` is ambigous, only one of `branch`, `tag` or `rev` is allowed"),
+            AmbigousGitSpec { .. } => write!(f, "dependency specification `{path}` is ambigous, only one of `branch`, `tag` or `rev` is allowed"),
             MissingDepSpec => write!(f, "dependency `{path}` is missing one of `workspace`, `path`, `git` or `version`, this is unsupported in the 2024 edition"),
             DepIgnoredGitKey(key) => write!(f, "invalid dependency specification `{path}`; `{key}` without `git` is not allowed"),
+            ScriptUnclosedFrontmatter => write!(f, "unclosed frontmatter, expected a closing fence with the same number of dashes"),
+            ScriptUnsupportedInfostring(info) => write!(f, "unsupported frontmatter infostring `{info}`, only `cargo` is supported"),
+            ScriptForbiddenKey => write!(f, "`{path}` is not allowed in embedded manifests"),
+            InheritedWorkspaceIsFalse => write!(f, "invalid inherited field `{path}`; `workspace` cannot be false"),
+            InheritedWorkspaceMissing => write!(f, "expected `{path}` to be inherited from the workspace with `workspace = true`"),
+            EmptyPackageName => write!(f, "package name cannot be empty"),
+            InvalidPackageNameStart(c) => write!(f, "invalid character `{c}` in package name, the first character must be an ASCII letter or `_`"),
+            InvalidPackageNameChar(c) => write!(f, "invalid character `{c}` in package name, only ASCII alphanumerics, `-` and `_` are allowed"),
+            UnknownEdition(edition) => write!(f, "unknown edition `{edition}`, expected one of `2015`, `2018`, `2021` or `2024`"),
+            License(e) => e.description(f),
+            EmptyFeatureName => write!(f, "feature name cannot be empty"),
+            InvalidFeatureNameStart(c) => write!(f, "invalid character `{c}` in feature name, the first character must be an alphanumeric or `_`"),
+            InvalidFeatureNameChar(c) => write!(f, "invalid character `{c}` in feature name, only alphanumerics, `_`, `-`, `+` and `.` are allowed"),
+            UnknownFeature(name) => write!(f, "`{path}` includes `{name}`, which is neither a feature nor an optional dependency"),
+            FeatureDepNotFound(dep) => write!(f, "`{path}` refers to `{dep}`, which is not a dependency"),
+            FeatureDepNotOptional(dep) => write!(f, "`{path}` refers to `{dep}`, which is not an optional dependency"),
+            FeatureDevDependency(dep) => write!(f, "`{path}` refers to `{dep}`, which is a dev-dependency, features cannot enable dev-dependencies"),
+            FeatureWeakDepNotOptional(dep) => write!(f, "`{path}` includes `{dep}?/` with a `?`, but `{dep}` is not an optional dependency"),
+            FeatureImplicitDepHidden { dep, .. } => write!(f, "`{path}` includes `{dep}`, but the optional dependency `{dep}` has no implicit feature, because `dep:{dep}` is used"),
+            FeatureCycle { cycle, .. } => write!(f, "cyclic feature dependency: {cycle}"),
+            UnknownResolver(resolver) => write!(f, "unknown resolver `{resolver}`, expected one of `1`, `2` or `3`"),
+            WorkspaceDepOptional => write!(f, "workspace dependency `{path}` cannot be optional"),
+            WorkspaceDepInherited => write!(f, "workspace dependency `{path}` cannot be inherited from the workspace"),
+            NoWorkspaceRoot => write!(f, "`{path}` is inherited from the workspace, but no workspace root was found"),
+            InheritedDepNotFound { name, .. } => write!(f, "dependency `{path}` is inherited from the workspace, but `workspace.dependencies.{name}` doesn't exist"),
+            InheritedFieldNotFound(key) => write!(f, "`{path}` is inherited from the workspace, but `workspace.package.{key}` doesn't exist"),
+            MemberNotFound(dir) => write!(f, "workspace member `{dir}` doesn't exist"),
+            MemberMissingManifest(dir) => write!(f, "workspace member `{dir}` has no `Cargo.toml`"),
+            DefaultMemberNotMember(dir) => write!(f, "default member `{dir}` is not a workspace member"),
+            UnknownLintLevel(level) => write!(f, "unknown lint level `{level}`, expected one of `forbid`, `deny`, `warn` or `allow`"),
+            MissingLintLevel => write!(f, "`{path}` is missing `level`"),
+            LintNameHasTool(suggestion) => write!(f, "`{path}` is not a valid lint name, use `{suggestion}` instead"),
+            LintsWorkspaceOverride => write!(f, "`{path}` cannot be specified, because the lints are inherited with `lints.workspace = true`"),
+            InheritedLintsNotFound => write!(f, "`{path}` is inherited from the workspace, but `workspace.lints` doesn't exist"),
+            BuiltinProfileInherits(name) => write!(f, "`inherits` cannot be specified in the built-in profile `{name}`"),
+            MissingProfileInherits => write!(f, "custom profile `{path}` is missing `inherits`"),
+            InvalidProfileNameChar(c) => write!(f, "invalid character `{c}` in profile name, only ASCII alphanumerics, `-` and `_` are allowed"),
+            ReservedProfileName(name) => write!(f, "profile name `{name}` is reserved"),
+            ProfileOverrideKey(section) => write!(f, "`{path}` cannot be specified in a `{section}` section"),
+            InvalidProfileValue(expected) => write!(f, "invalid value for `{path}`, expected {expected}"),
+            UnknownInheritedProfile(name) => write!(f, "`{path}` refers to the profile `{name}`, which doesn't exist"),
+            ProfileInheritsCycle(cycle) => write!(f, "cyclic profile inheritance: {cycle}"),
+            MissingTargetName => write!(f, "target `{path}` is missing `name`"),
+            EmptyTargetName => write!(f, "target name cannot be empty"),
+            InvalidTargetNameChar(c) => write!(f, "invalid character `{c}` in target name, only alphanumerics, `-` and `_` are allowed"),
+            DuplicateTargetName { kind, .. } => write!(f, "duplicate `{kind}` target name, target names must be unique"),
+            UnknownCrateType(crate_type) => write!(f, "unknown crate type `{crate_type}`, expected one of `bin`, `lib`, `rlib`, `dylib`, `cdylib`, `staticlib` or `proc-macro`"),
+            ProcMacroWithOtherCrateTypes => write!(f, "a proc-macro library cannot have other crate types"),
+            UnknownRequiredFeature(name) => write!(f, "`{path}` requires `{name}`, which is neither a feature nor an optional dependency"),
+            TargetSpec(e) => e.description(f),
         }
     }
 
@@ -96,20 +256,145 @@ impl Diagnostic for Error {
         let Self { kind, .. } = self;
         match kind {
             WrongDatatype { expected, .. } => write!(f, "expected {expected}"),
+            WrongDatatypeEither {
+                expected: [a, b], ..
+            } => write!(f, "expected {a} or {b}"),
             UnsupportedUnderscore { new, .. } => write!(f, "unsupported; instead use `{new}`"),
             DepWrongDatatype(..) => write!(f, "expected string or table"),
             DepWorkspaceIsFalse => write!(f, "`workspace` cannot be false"),
             AmbigousDepSpecGitPath => write!(f, "only one of `git` or `path` is allowed"),
             AmbigousDepSpecGitRegistry => write!(f, "only one of `git` or `registry` is allowed"),
-            AmbigousGitSpec => write!(f, "only one of `branch`, `tag` or `rev` is allowed"),
+            AmbigousGitSpec { .. } => write!(f, "only one of `branch`, `tag` or `rev` is allowed"),
             MissingDepSpec => write!(f, "missing one of `workspace`, `path`, `git` or `version`"),
             DepIgnoredGitKey(_) => write!(f, "not allowed without `git`"),
+            ScriptUnclosedFrontmatter => write!(f, "unclosed frontmatter"),
+            ScriptUnsupportedInfostring(_) => write!(f, "unsupported infostring"),
+            ScriptForbiddenKey => write!(f, "not allowed in embedded manifests"),
+            InheritedWorkspaceIsFalse => write!(f, "`workspace` cannot be false"),
+            InheritedWorkspaceMissing => write!(f, "missing `workspace = true`"),
+            EmptyPackageName => write!(f, "empty package name"),
+            InvalidPackageNameStart(_) => write!(f, "invalid first character"),
+            InvalidPackageNameChar(_) => write!(f, "invalid character"),
+            UnknownEdition(_) => write!(f, "unknown edition"),
+            License(e) => e.annotation(f),
+            EmptyFeatureName => write!(f, "empty feature name"),
+            InvalidFeatureNameStart(_) => write!(f, "invalid first character"),
+            InvalidFeatureNameChar(_) => write!(f, "invalid character"),
+            UnknownFeature(_) => write!(f, "unknown feature"),
+            FeatureDepNotFound(_) => write!(f, "not a dependency"),
+            FeatureDepNotOptional(_) => write!(f, "not an optional dependency"),
+            FeatureDevDependency(_) => write!(f, "dev-dependency"),
+            FeatureWeakDepNotOptional(_) => write!(f, "not an optional dependency"),
+            FeatureImplicitDepHidden { .. } => write!(f, "no implicit feature"),
+            FeatureCycle { .. } => write!(f, "cyclic feature dependency"),
+            UnknownResolver(_) => write!(f, "unknown resolver"),
+            WorkspaceDepOptional => write!(f, "not allowed in workspace dependencies"),
+            WorkspaceDepInherited => write!(f, "not allowed in workspace dependencies"),
+            NoWorkspaceRoot => write!(f, "no workspace root"),
+            InheritedDepNotFound { .. } => write!(f, "not found in `[workspace.dependencies]`"),
+            InheritedFieldNotFound(_) => write!(f, "not found in `[workspace.package]`"),
+            MemberNotFound(_) => write!(f, "directory not found"),
+            MemberMissingManifest(_) => write!(f, "missing `Cargo.toml`"),
+            DefaultMemberNotMember(_) => write!(f, "not a workspace member"),
+            UnknownLintLevel(_) => write!(f, "unknown lint level"),
+            MissingLintLevel => write!(f, "missing `level`"),
+            LintNameHasTool(_) => write!(f, "tool prefix in lint name"),
+            LintsWorkspaceOverride => write!(f, "not allowed with `workspace = true`"),
+            InheritedLintsNotFound => write!(f, "not found in `[workspace.lints]`"),
+            BuiltinProfileInherits(_) => write!(f, "not allowed in built-in profiles"),
+            MissingProfileInherits => write!(f, "missing `inherits`"),
+            InvalidProfileNameChar(_) => write!(f, "invalid character"),
+            ReservedProfileName(_) => write!(f, "reserved name"),
+            ProfileOverrideKey(_) => write!(f, "not allowed here"),
+            InvalidProfileValue(_) => write!(f, "invalid value"),
+            UnknownInheritedProfile(_) => write!(f, "unknown profile"),
+            ProfileInheritsCycle(_) => write!(f, "cyclic profile inheritance"),
+            MissingTargetName => write!(f, "missing `name`"),
+            EmptyTargetName => write!(f, "empty target name"),
+            InvalidTargetNameChar(_) => write!(f, "invalid character"),
+            DuplicateTargetName { .. } => write!(f, "duplicate target name"),
+            UnknownCrateType(_) => write!(f, "unknown crate type"),
+            ProcMacroWithOtherCrateTypes => write!(f, "conflicting crate types"),
+            UnknownRequiredFeature(_) => write!(f, "unknown feature"),
+            TargetSpec(e) => e.annotation(f),
+        }
+    }
+
+    fn hint(&self) -> Option<Self::Hint> {
+        use ErrorKind::*;
+        let Self { kind, .. } = self;
+        match kind {
+            FeatureImplicitDepHidden { dep_span, .. } => Some(Hint::FeatureDepUsed(*dep_span)),
+            DuplicateTargetName { first_span, .. } => Some(Hint::FirstDefinedHere(*first_span)),
+            _ => None,
         }
     }
 
     fn context_lines(&self) -> Option<&[u32]> {
         Some(&self.lines)
     }
+
+    fn labels(&self) -> Vec<Label> {
+        use ErrorKind::*;
+        let Self { kind, .. } = self;
+        match kind {
+            FeatureCycle { edges, .. } => (edges.iter())
+                .map(|(span, annotation)| Label::new(*span, annotation.clone()))
+                .collect(),
+            AmbigousGitSpec { others } => (others.iter())
+                .map(|span| Label::new(*span, "also specified here"))
+                .collect(),
+            _ => self.hint().iter().map(Label::from_hint).collect(),
+        }
+    }
+
+    fn footers(&self) -> Vec<Footer> {
+        use ErrorKind::*;
+        let Self { kind, .. } = self;
+        match kind {
+            UnsupportedUnderscore { new, .. } => {
+                vec![Footer::help(FmtStr::from_string(format!(
+                    "rename the key to `{new}`"
+                )))]
+            }
+            MissingDepSpec => vec![Footer::help(
+                "specify a version requirement, for example `version = \"*\"`",
+            )],
+            ScriptForbiddenKey => vec![Footer::note(
+                "a script is a single package with a single target and no build script",
+            )],
+            InheritedWorkspaceMissing => vec![Footer::help(
+                "inherit the field from `[workspace.package]` with `{ workspace = true }`",
+            )],
+            License(e) => e.footers(),
+            TargetSpec(e) => e.footers(),
+            FeatureImplicitDepHidden { dep, .. } => vec![Footer::help(FmtStr::from_string(
+                format!("use `dep:{dep}` to enable the dependency"),
+            ))],
+            FeatureWeakDepNotOptional(dep) => vec![Footer::help(FmtStr::from_string(
+                format!("remove the `?` or make `{dep}` an optional dependency"),
+            ))],
+            WorkspaceDepOptional => vec![Footer::help(
+                "make the dependency optional in the members that inherit it",
+            )],
+            MissingProfileInherits => vec![Footer::help(
+                "inherit the settings of a built-in profile, for example `inherits = \"release\"`",
+            )],
+            ReservedProfileName(name) if &**name == "debug" => vec![Footer::help(
+                "to configure the default development profile, use the name `dev`",
+            )],
+            LintsWorkspaceOverride => vec![Footer::help(
+                "either remove the lint or specify all lints without inheriting them",
+            )],
+            InvalidTargetNameChar('-') => vec![Footer::help(
+                "library names are used as crate names, use `_` instead of `-`",
+            )],
+            NoWorkspaceRoot => vec![Footer::note(
+                "the workspace root is the closest `Cargo.toml` with a `[workspace]` table in the parent directories",
+            )],
+            _ => Vec::new(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -129,6 +414,16 @@ impl Warning {
             kind,
         }
     }
+
+    /// A span in the workspace root manifest that is related to this warning, with an annotation.
+    pub fn root_location(&self) -> Option<(Span, &'static str)> {
+        match self.kind {
+            WarningKind::InheritedDefaultFeaturesIgnored { root_span, .. } => {
+                Some((root_span, "default features are enabled here"))
+            }
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -149,6 +444,43 @@ pub enum WarningKind {
         workspace_span: Span,
     },
     IgnoredUnknownKey,
+    /// crates.io limits package names to 64 characters.
+    PackageNameTooLong,
+    PackageNameIsKeyword(FmtStr),
+    /// Names of the standard library crates.
+    ReservedPackageName(FmtStr),
+    /// crates.io allows at most 5 keywords.
+    TooManyKeywords,
+    /// crates.io limits keywords to 20 characters.
+    KeywordTooLong,
+    InvalidKeyword(FmtStr),
+    /// crates.io allows at most 5 categories.
+    TooManyCategories,
+    License(spdx::WarningKind),
+    /// Cargo ignores `license-file` if `license` is set.
+    LicenseAndLicenseFile {
+        license_file_span: Span,
+    },
+    /// `default-features = false` has no effect if the workspace dependency enables default
+    /// features.
+    InheritedDefaultFeaturesIgnored {
+        name: FmtStr,
+        /// The workspace dependency in the root manifest.
+        root_span: Span,
+    },
+    /// A `workspace.members` glob pattern that matches no directory.
+    MemberPatternNoMatch(FmtStr),
+    /// A package nested in the workspace directory that is neither a member nor excluded.
+    UnlistedPackage(FmtStr),
+    UnknownLintTool(FmtStr),
+    /// An unknown lint name, with a suggestion of a similar known lint.
+    UnknownLint(Option<&'static str>),
+    /// `test` and `bench` always use `panic = "unwind"`.
+    ProfilePanicIgnored(FmtStr),
+    /// Compiler plugins have been removed.
+    TargetPluginIgnored,
+    /// A questionable `[target.<spec>]` key.
+    TargetSpec(cfg::WarningKind),
 }
 
 impl Diagnostic for Warning {
@@ -179,6 +511,23 @@ impl Diagnostic for Warning {
             MissingDepSpec => write!(f, "missing one of `workspace`, `path`, `git` or `version`, this will be unsupported in the 2024 edition"),
             WorkspaceDepIgnoredKey { .. } => write!(f, "key `{path}` is ignored, because `workspace` is set"),
             IgnoredUnknownKey => write!(f, "unknown key `{path}` is ignored"),
+            PackageNameTooLong => write!(f, "package name is longer than 64 characters, crates.io will reject it"),
+            PackageNameIsKeyword(name) => write!(f, "package name `{name}` is a rust keyword, it cannot be used as a crate name"),
+            ReservedPackageName(name) => write!(f, "package name `{name}` conflicts with a standard library crate"),
+            TooManyKeywords => write!(f, "`{path}` has more than 5 keywords, crates.io will reject it"),
+            KeywordTooLong => write!(f, "`{path}` is longer than 20 characters, crates.io will reject it"),
+            InvalidKeyword(keyword) => write!(f, "invalid keyword `{keyword}`, keywords must start with an ASCII alphanumeric and only contain ASCII alphanumerics, `_`, `-` or `+`"),
+            TooManyCategories => write!(f, "`{path}` has more than 5 categories, crates.io will reject it"),
+            License(w) => w.description(f),
+            LicenseAndLicenseFile { .. } => write!(f, "only one of `license` or `license-file` is necessary"),
+            InheritedDefaultFeaturesIgnored { name, .. } => write!(f, "`{path}` is ignored, because `workspace.dependencies.{name}` enables default features"),
+            MemberPatternNoMatch(pattern) => write!(f, "`{pattern}` doesn't match any directory"),
+            UnlistedPackage(dir) => write!(f, "package `{dir}` is nested in the workspace, but isn't a member"),
+            UnknownLintTool(tool) => write!(f, "unknown lint tool `{tool}`, expected one of `rust`, `clippy`, `rustdoc` or `cargo`"),
+            UnknownLint(_) => write!(f, "unknown lint `{path}`"),
+            ProfilePanicIgnored(profile) => write!(f, "`{path}` is ignored, because the `{profile}` profile always unwinds"),
+            TargetPluginIgnored => write!(f, "`{path}` is ignored, compiler plugins have been removed"),
+            TargetSpec(w) => w.description(f),
         }
     }
 
@@ -191,6 +540,23 @@ impl Diagnostic for Warning {
             MissingDepSpec => write!(f, "missing one of `workspace`, `path`, `git` or `version`"),
             WorkspaceDepIgnoredKey { .. } => write!(f, "key is ignored"),
             IgnoredUnknownKey => write!(f, "unknown key is ignored"),
+            PackageNameTooLong => write!(f, "longer than 64 characters"),
+            PackageNameIsKeyword(_) => write!(f, "rust keyword"),
+            ReservedPackageName(_) => write!(f, "reserved name"),
+            TooManyKeywords => write!(f, "more than 5 keywords"),
+            KeywordTooLong => write!(f, "longer than 20 characters"),
+            InvalidKeyword(_) => write!(f, "invalid keyword"),
+            TooManyCategories => write!(f, "more than 5 categories"),
+            License(w) => w.annotation(f),
+            LicenseAndLicenseFile { .. } => write!(f, "`license-file` is ignored"),
+            InheritedDefaultFeaturesIgnored { .. } => write!(f, "ignored"),
+            MemberPatternNoMatch(_) => write!(f, "matches no directory"),
+            UnlistedPackage(dir) => write!(f, "`{dir}` isn't a member"),
+            UnknownLintTool(_) => write!(f, "unknown lint tool"),
+            UnknownLint(_) => write!(f, "unknown lint"),
+            ProfilePanicIgnored(_) => write!(f, "ignored"),
+            TargetPluginIgnored => write!(f, "ignored"),
+            TargetSpec(w) => w.annotation(f),
         }
     }
 
@@ -207,12 +573,53 @@ impl Diagnostic for Warning {
                 Some(Hint::WorkspaceDepIgnoredKey(*workspace_span))
             }
             IgnoredUnknownKey => None,
+            PackageNameTooLong => None,
+            PackageNameIsKeyword(_) => None,
+            ReservedPackageName(_) => None,
+            TooManyKeywords => None,
+            KeywordTooLong => None,
+            InvalidKeyword(_) => None,
+            TooManyCategories => None,
+            License(_) => None,
+            LicenseAndLicenseFile { license_file_span } => {
+                Some(Hint::LicenseAndLicenseFile(*license_file_span))
+            }
+            InheritedDefaultFeaturesIgnored { .. } => None,
+            MemberPatternNoMatch(_) => None,
+            UnlistedPackage(_) => None,
+            UnknownLintTool(_) => None,
+            UnknownLint(_) => None,
+            ProfilePanicIgnored(_) => None,
+            TargetPluginIgnored => None,
+            TargetSpec(_) => None,
         }
     }
 
     fn context_lines(&self) -> Option<&[u32]> {
         Some(&self.lines)
     }
+
+    fn footers(&self) -> Vec<Footer> {
+        use WarningKind::*;
+        let Self { kind, .. } = self;
+        match kind {
+            License(w) => w.footers(),
+            TargetSpec(w) => w.footers(),
+            LicenseAndLicenseFile { .. } => vec![Footer::note(
+                "use `license` for SPDX license expressions and `license-file` for non-standard licenses",
+            )],
+            InheritedDefaultFeaturesIgnored { .. } => vec![Footer::help(
+                "set `default-features = false` in `[workspace.dependencies]` and enable default features in the members that need them",
+            )],
+            UnlistedPackage(_) => vec![Footer::help(
+                "add the package to `workspace.members` or `workspace.exclude`",
+            )],
+            UnknownLint(Some(s)) => vec![Footer::help(FmtStr::from_string(format!(
+                "did you mean `{s}`"
+            )))],
+            _ => Vec::new(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -240,6 +647,9 @@ impl Diagnostic for Info {
 pub enum Hint {
     RedundantDeprecatedUnderscore(Span),
     WorkspaceDepIgnoredKey(Span),
+    LicenseAndLicenseFile(Span),
+    FeatureDepUsed(Span),
+    FirstDefinedHere(Span),
 }
 
 impl DiagnosticHint for Hint {
@@ -247,6 +657,9 @@ impl DiagnosticHint for Hint {
         match self {
             Hint::RedundantDeprecatedUnderscore(s) => *s,
             Hint::WorkspaceDepIgnoredKey(s) => *s,
+            Hint::LicenseAndLicenseFile(s) => *s,
+            Hint::FeatureDepUsed(s) => *s,
+            Hint::FirstDefinedHere(s) => *s,
         }
     }
 
@@ -254,6 +667,9 @@ impl DiagnosticHint for Hint {
         match self {
             Hint::RedundantDeprecatedUnderscore(_) => write!(f, "used instead"),
             Hint::WorkspaceDepIgnoredKey(_) => write!(f, "defined here"),
+            Hint::LicenseAndLicenseFile(_) => write!(f, "`license-file` is set here"),
+            Hint::FeatureDepUsed(_) => write!(f, "`dep:` is used here"),
+            Hint::FirstDefinedHere(_) => write!(f, "first defined here"),
         }
     }
 }
@@ -0,0 +1,675 @@
+//! Parser for platform specifications, as used by `[target.<spec>]`. A spec is either a target
+//! triple or a `cfg` expression.
+//!
+//! ```text
+//! spec = "cfg(" cfg ")" | triple
+//! cfg  = name | name "=" string | ("all" | "any") "(" list? ")" | "not" "(" cfg ")"
+//! list = cfg ("," cfg)* ","?
+//! ```
+
+use common::diagnostic::Footer;
+use common::{FmtStr, Pos, Span};
+
+pub use platform::Platform;
+
+mod platform;
+mod targets;
+#[cfg(test)]
+mod test;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TargetSpec<'a> {
+    /// `x86_64-unknown-linux-gnu`
+    Triple(Name<'a>),
+    /// `cfg(...)`
+    Cfg(Cfg<'a>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Cfg<'a> {
+    /// `unix`
+    Name(Name<'a>),
+    /// `target_os = "linux"`, the value is the text inside the quotes.
+    KeyPair(Name<'a>, Name<'a>),
+    All(Vec<Cfg<'a>>),
+    Any(Vec<Cfg<'a>>),
+    Not(Box<Cfg<'a>>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Name<'a> {
+    pub text: &'a str,
+    /// For values the span includes the quotes.
+    pub span: Span,
+}
+
+/// The result of parsing a spec. Warnings don't prevent the spec from being parsed, syntax
+/// errors do. The spans are byte offsets relative to [`Pos::ZERO`].
+#[derive(Debug, Default, PartialEq)]
+pub struct Parsed<'a> {
+    pub spec: Option<TargetSpec<'a>>,
+    pub errors: Vec<Error>,
+    pub warnings: Vec<Warning>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Error {
+    pub span: Span,
+    pub kind: ErrorKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    Empty,
+    InvalidChar(char),
+    InvalidTripleChar(char),
+    /// Expected a name, or one of the predicates `all`, `any` or `not`.
+    ExpectedCfg,
+    ExpectedValue,
+    ExpectedCommaOrParen,
+    UnterminatedString,
+    UnclosedParen,
+    UnexpectedInput,
+    UnknownPredicate(FmtStr),
+    /// `not` takes exactly one cfg.
+    NotArgCount,
+}
+
+impl ErrorKind {
+    pub fn description(&self, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        use ErrorKind::*;
+        match self {
+            Empty => write!(f, "empty target specification"),
+            InvalidChar(c) => write!(f, "invalid character `{c}` in cfg expression"),
+            InvalidTripleChar(c) => write!(f, "invalid character `{c}` in target triple, only alphanumerics, `-`, `_` and `.` are allowed"),
+            ExpectedCfg => write!(f, "expected a cfg name or one of `all`, `any` or `not`"),
+            ExpectedValue => write!(f, "expected a string value after `=`"),
+            ExpectedCommaOrParen => write!(f, "expected `,` or `)` in cfg expression"),
+            UnterminatedString => write!(f, "unterminated string in cfg expression"),
+            UnclosedParen => write!(f, "unclosed parenthesis in cfg expression"),
+            UnexpectedInput => write!(f, "unexpected input after the cfg expression"),
+            UnknownPredicate(name) => write!(f, "unknown cfg predicate `{name}`, expected one of `all`, `any` or `not`"),
+            NotArgCount => write!(f, "`not` expects exactly one cfg"),
+        }
+    }
+
+    pub fn annotation(&self, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        use ErrorKind::*;
+        match self {
+            Empty => write!(f, "empty"),
+            InvalidChar(_) | InvalidTripleChar(_) => write!(f, "invalid character"),
+            ExpectedCfg => write!(f, "expected cfg"),
+            ExpectedValue => write!(f, "expected string"),
+            ExpectedCommaOrParen => write!(f, "expected `,` or `)`"),
+            UnterminatedString => write!(f, "unterminated"),
+            UnclosedParen => write!(f, "unclosed"),
+            UnexpectedInput => write!(f, "unexpected input"),
+            UnknownPredicate(_) => write!(f, "unknown predicate"),
+            NotArgCount => write!(f, "expected one cfg"),
+        }
+    }
+
+    pub fn footers(&self) -> Vec<Footer> {
+        Vec::new()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Warning {
+    pub span: Span,
+    pub kind: WarningKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WarningKind {
+    /// An unknown key of a `key = "value"` pair, with a suggestion of a known key.
+    UnknownKey(FmtStr, Option<&'static str>),
+    /// A known key that is used as a name, without a value.
+    MissingValue(&'static str),
+    /// A name that is a value of a known key, for example `linux`, with the suggested pair.
+    NameIsValue(FmtStr),
+    UnknownValue {
+        key: &'static str,
+        value: FmtStr,
+        expected: &'static str,
+    },
+    /// Cfgs that are never set when cargo selects the dependencies of a target.
+    NoEffect(FmtStr),
+    /// `all(...)` with two predicates that can't both be true.
+    Impossible { first: FmtStr, second: FmtStr },
+}
+
+impl WarningKind {
+    pub fn description(&self, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        use WarningKind::*;
+        match self {
+            UnknownKey(key, _) => write!(f, "unknown cfg key `{key}`"),
+            MissingValue(key) => write!(
+                f,
+                "cfg `{key}` expects a value, for example `{key} = \"...\"`"
+            ),
+            NameIsValue(pair) => write!(f, "unknown cfg name, did you mean `{pair}`"),
+            UnknownValue { key, value, .. } => write!(f, "unknown value `{value}` for cfg `{key}`"),
+            NoEffect(cfg) => write!(
+                f,
+                "`{cfg}` is never set when selecting dependencies, this cfg has no effect"
+            ),
+            Impossible { first, second } => write!(
+                f,
+                "cfg expression can never be true, `{first}` and `{second}` are mutually exclusive"
+            ),
+        }
+    }
+
+    pub fn annotation(&self, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        use WarningKind::*;
+        match self {
+            UnknownKey(..) => write!(f, "unknown key"),
+            MissingValue(_) => write!(f, "missing value"),
+            NameIsValue(_) => write!(f, "unknown name"),
+            UnknownValue { .. } => write!(f, "unknown value"),
+            NoEffect(_) => write!(f, "no effect"),
+            Impossible { .. } => write!(f, "never true"),
+        }
+    }
+
+    pub fn footers(&self) -> Vec<Footer> {
+        use WarningKind::*;
+        match self {
+            UnknownKey(_, Some(s)) => {
+                vec![Footer::help(FmtStr::from_string(format!(
+                    "did you mean `{s}`"
+                )))]
+            }
+            UnknownValue { expected, .. } => vec![Footer::note(FmtStr::from_string(format!(
+                "expected one of {expected}"
+            )))],
+            NoEffect(cfg) if cfg.starts_with("feature") => vec![Footer::help(
+                "use the `[features]` table to enable dependencies with features",
+            )],
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Keys of `key = "value"` pairs that are set by rustc.
+const KEYS: [&str; 17] = [
+    "feature",
+    "fmt_debug",
+    "panic",
+    "relocation_model",
+    "sanitize",
+    "target_abi",
+    "target_arch",
+    "target_endian",
+    "target_env",
+    "target_family",
+    "target_feature",
+    "target_has_atomic",
+    "target_has_atomic_equal_alignment",
+    "target_has_atomic_load_store",
+    "target_os",
+    "target_pointer_width",
+    "target_vendor",
+];
+/// Keys that can only have a single value at a time, `target_family`, `target_feature` and
+/// `target_has_atomic` can have multiple values.
+const SINGLE_VALUE_KEYS: [&str; 8] = [
+    "panic",
+    "target_abi",
+    "target_arch",
+    "target_endian",
+    "target_env",
+    "target_os",
+    "target_pointer_width",
+    "target_vendor",
+];
+/// Keys with a small set of valid values.
+const KEY_VALUES: [(&str, &[&str], &str); 4] = [
+    ("panic", &["abort", "unwind"], "`abort` or `unwind`"),
+    ("target_endian", &["big", "little"], "`big` or `little`"),
+    (
+        "target_family",
+        &["unix", "wasm", "windows"],
+        "`unix`, `wasm` or `windows`",
+    ),
+    (
+        "target_pointer_width",
+        &["16", "32", "64"],
+        "`16`, `32` or `64`",
+    ),
+];
+/// Common values of `target_os` and `target_family` that are mistakenly used as names.
+const OS_NAMES: [(&str, &str); 12] = [
+    ("android", "target_os"),
+    ("dragonfly", "target_os"),
+    ("freebsd", "target_os"),
+    ("fuchsia", "target_os"),
+    ("illumos", "target_os"),
+    ("ios", "target_os"),
+    ("linux", "target_os"),
+    ("macos", "target_os"),
+    ("netbsd", "target_os"),
+    ("openbsd", "target_os"),
+    ("solaris", "target_os"),
+    ("wasm", "target_family"),
+];
+/// Cfgs that cargo never sets when selecting the dependencies of a target.
+const NO_EFFECT: [&str; 4] = ["debug_assertions", "feature", "proc_macro", "test"];
+
+pub fn parse_target_spec(input: &str) -> Parsed<'_> {
+    let end = Pos::new(0, input.len() as u32);
+    let mut parser = Parser {
+        input,
+        tokens: Vec::new(),
+        idx: 0,
+        end,
+        errors: Vec::new(),
+        warnings: Vec::new(),
+    };
+
+    let spec = match input.strip_prefix("cfg") {
+        Some(rest) if rest.trim_start().starts_with('(') => parser.parse_cfg_spec(),
+        _ => parser.parse_triple(),
+    };
+    Parsed {
+        spec,
+        errors: parser.errors,
+        warnings: parser.warnings,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TokenKind {
+    Ident,
+    String,
+    Eq,
+    Comma,
+    LParen,
+    RParen,
+    UnterminatedString,
+    Invalid(char),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Token<'a> {
+    kind: TokenKind,
+    text: &'a str,
+    span: Span,
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn lex(input: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let kind = match c {
+            c if c.is_whitespace() => continue,
+            '(' => TokenKind::LParen,
+            ')' => TokenKind::RParen,
+            ',' => TokenKind::Comma,
+            '=' => TokenKind::Eq,
+            '"' => loop {
+                match chars.next() {
+                    Some((_, '"')) => break TokenKind::String,
+                    Some(_) => (),
+                    None => break TokenKind::UnterminatedString,
+                }
+            },
+            c if is_ident_start(c) => {
+                while chars.next_if(|(_, c)| is_ident_char(*c)).is_some() {}
+                TokenKind::Ident
+            }
+            c => TokenKind::Invalid(c),
+        };
+
+        let end = chars.peek().map_or(input.len(), |(i, _)| *i);
+        let text = &input[start..end];
+        let span = Span::new(Pos::new(0, start as u32), Pos::new(0, end as u32));
+        tokens.push(Token { kind, text, span });
+    }
+    tokens
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    tokens: Vec<Token<'a>>,
+    idx: usize,
+    /// The end of the input.
+    end: Pos,
+    errors: Vec<Error>,
+    warnings: Vec<Warning>,
+}
+
+impl<'a> Parser<'a> {
+    fn parse_triple(&mut self) -> Option<TargetSpec<'a>> {
+        let span = Span::new(Pos::ZERO, self.end);
+        if self.input.is_empty() {
+            self.error(span, ErrorKind::Empty);
+            return None;
+        }
+
+        let invalid = (self.input.char_indices())
+            .find(|(_, c)| !(c.is_alphanumeric() || matches!(c, '-' | '_' | '.')));
+        if let Some((i, c)) = invalid {
+            let start = Pos::new(0, i as u32);
+            let end = Pos::new(0, (i + c.len_utf8()) as u32);
+            self.error(Span::new(start, end), ErrorKind::InvalidTripleChar(c));
+            return None;
+        }
+
+        let name = Name {
+            text: self.input,
+            span,
+        };
+        Some(TargetSpec::Triple(name))
+    }
+
+    fn parse_cfg_spec(&mut self) -> Option<TargetSpec<'a>> {
+        self.tokens = lex(self.input);
+        // Skip `cfg`, which is followed by `(`.
+        self.idx = 1;
+        let res = self.parse_list().and_then(|(mut cfgs, span)| {
+            if let Some(t) = self.next() {
+                return Err(Error {
+                    span: t.span,
+                    kind: ErrorKind::UnexpectedInput,
+                });
+            }
+            match cfgs.len() {
+                1 => Ok(cfgs.remove(0)),
+                0 => Err(Error {
+                    span,
+                    kind: ErrorKind::ExpectedCfg,
+                }),
+                _ => Err(Error {
+                    span,
+                    kind: ErrorKind::UnexpectedInput,
+                }),
+            }
+        });
+        match res {
+            Ok(cfg) => Some(TargetSpec::Cfg(cfg)),
+            Err(e) => {
+                self.errors.push(e);
+                None
+            }
+        }
+    }
+
+    fn parse_cfg(&mut self) -> Result<Cfg<'a>, Error> {
+        let Some(t) = self.next() else {
+            return Err(self.error_at_end(ErrorKind::ExpectedCfg));
+        };
+
+        match t.kind {
+            TokenKind::Ident if self.peek().is_some_and(|t| t.kind == TokenKind::LParen) => {
+                let (mut cfgs, span) = self.parse_list()?;
+                let span = Span::new(t.span.start, span.end);
+                match t.text {
+                    "all" => {
+                        self.check_all(&cfgs, span);
+                        Ok(Cfg::All(cfgs))
+                    }
+                    "any" => Ok(Cfg::Any(cfgs)),
+                    "not" if cfgs.len() == 1 => Ok(Cfg::Not(Box::new(cfgs.remove(0)))),
+                    "not" => Err(Error {
+                        span,
+                        kind: ErrorKind::NotArgCount,
+                    }),
+                    _ => Err(Error {
+                        span: t.span,
+                        kind: ErrorKind::UnknownPredicate(FmtStr::from_str(t.text)),
+                    }),
+                }
+            }
+            TokenKind::Ident if self.peek().is_some_and(|t| t.kind == TokenKind::Eq) => {
+                self.idx += 1;
+                let value = match self.next() {
+                    Some(v) if v.kind == TokenKind::String => v,
+                    Some(v) => return Err(self.unexpected(v, ErrorKind::ExpectedValue)),
+                    None => return Err(self.error_at_end(ErrorKind::ExpectedValue)),
+                };
+                let key = Name {
+                    text: t.text,
+                    span: t.span,
+                };
+                let value = Name {
+                    text: &value.text[1..value.text.len() - 1],
+                    span: value.span,
+                };
+                self.check_key_pair(&key, &value);
+                Ok(Cfg::KeyPair(key, value))
+            }
+            TokenKind::Ident => {
+                let name = Name {
+                    text: t.text,
+                    span: t.span,
+                };
+                self.check_name(&name);
+                Ok(Cfg::Name(name))
+            }
+            _ => Err(self.unexpected(t, ErrorKind::ExpectedCfg)),
+        }
+    }
+
+    /// Parse a parenthesized list of cfgs, returns the cfgs and the span including the
+    /// parentheses.
+    fn parse_list(&mut self) -> Result<(Vec<Cfg<'a>>, Span), Error> {
+        let l_par = match self.next() {
+            Some(t) if t.kind == TokenKind::LParen => t,
+            Some(t) => return Err(self.unexpected(t, ErrorKind::ExpectedCfg)),
+            None => return Err(self.error_at_end(ErrorKind::ExpectedCfg)),
+        };
+
+        let mut cfgs = Vec::new();
+        loop {
+            if let Some(t) = self.peek().filter(|t| t.kind == TokenKind::RParen) {
+                self.idx += 1;
+                return Ok((cfgs, Span::new(l_par.span.start, t.span.end)));
+            }
+            if self.peek().is_none() {
+                return Err(Error {
+                    span: l_par.span,
+                    kind: ErrorKind::UnclosedParen,
+                });
+            }
+
+            cfgs.push(self.parse_cfg()?);
+
+            match self.next() {
+                Some(t) if t.kind == TokenKind::Comma => (),
+                Some(t) if t.kind == TokenKind::RParen => {
+                    return Ok((cfgs, Span::new(l_par.span.start, t.span.end)));
+                }
+                Some(t) => return Err(self.unexpected(t, ErrorKind::ExpectedCommaOrParen)),
+                None => {
+                    return Err(Error {
+                        span: l_par.span,
+                        kind: ErrorKind::UnclosedParen,
+                    })
+                }
+            }
+        }
+    }
+
+    fn check_name(&mut self, name: &Name) {
+        if NO_EFFECT.contains(&name.text) {
+            self.warn(
+                name.span,
+                WarningKind::NoEffect(FmtStr::from_str(name.text)),
+            );
+        } else if let Some(key) = KEYS.iter().find(|k| **k == name.text) {
+            self.warn(name.span, WarningKind::MissingValue(key));
+        } else if let Some((value, key)) = OS_NAMES.iter().find(|(v, _)| *v == name.text) {
+            let pair = FmtStr::from_string(format!("{key} = \"{value}\""));
+            self.warn(name.span, WarningKind::NameIsValue(pair));
+        }
+    }
+
+    fn check_key_pair(&mut self, key: &Name, value: &Name) {
+        let span = Span::new(key.span.start, value.span.end);
+        if !KEYS.contains(&key.text) {
+            let prefixed = format!("target_{}", key.text);
+            let suggestion = KEYS.iter().find(|k| **k == prefixed).copied();
+            let kind = WarningKind::UnknownKey(FmtStr::from_str(key.text), suggestion);
+            self.warn(key.span, kind);
+        } else if NO_EFFECT.contains(&key.text) {
+            let cfg = FmtStr::from_str(self.text(span));
+            self.warn(span, WarningKind::NoEffect(cfg));
+        } else if let Some((key, values, expected)) =
+            KEY_VALUES.iter().find(|(k, _, _)| *k == key.text)
+        {
+            if !values.contains(&value.text) {
+                let value_text = FmtStr::from_str(value.text);
+                let kind = WarningKind::UnknownValue {
+                    key,
+                    value: value_text,
+                    expected,
+                };
+                self.warn(value.span, kind);
+            }
+        }
+    }
+
+    /// Check for predicates of `all(...)` that can't be true at the same time.
+    fn check_all(&mut self, cfgs: &[Cfg], span: Span) {
+        let mut facts = Vec::new();
+        collect_facts(cfgs, &mut facts);
+
+        for (i, a) in facts.iter().enumerate() {
+            for b in facts[i + 1..].iter() {
+                if !a.conflicts(b) {
+                    continue;
+                }
+                let kind = WarningKind::Impossible {
+                    first: self.fact_text(a),
+                    second: self.fact_text(b),
+                };
+                self.warn(span, kind);
+                return;
+            }
+        }
+    }
+
+    fn fact_text(&self, fact: &Fact) -> FmtStr {
+        let text = self.text(fact.span);
+        match fact.negated {
+            true => FmtStr::from_string(format!("not({text})")),
+            false => FmtStr::from_str(text),
+        }
+    }
+
+    fn text(&self, span: Span) -> &'a str {
+        &self.input[span.start.char as usize..span.end.char as usize]
+    }
+
+    fn unexpected(&self, t: Token, kind: ErrorKind) -> Error {
+        let kind = match t.kind {
+            TokenKind::Invalid(c) => ErrorKind::InvalidChar(c),
+            TokenKind::UnterminatedString => ErrorKind::UnterminatedString,
+            _ => kind,
+        };
+        Error { span: t.span, kind }
+    }
+
+    fn error(&mut self, span: Span, kind: ErrorKind) {
+        self.errors.push(Error { span, kind });
+    }
+
+    fn error_at_end(&self, kind: ErrorKind) -> Error {
+        Error {
+            span: Span::new(self.end, self.end),
+            kind,
+        }
+    }
+
+    fn warn(&mut self, span: Span, kind: WarningKind) {
+        self.warnings.push(Warning { span, kind });
+    }
+
+    fn peek(&self) -> Option<Token<'a>> {
+        self.tokens.get(self.idx).copied()
+    }
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        let t = self.peek();
+        self.idx += t.is_some() as usize;
+        t
+    }
+}
+
+/// A predicate that has to be true or false for an `all(...)` expression to be true.
+struct Fact<'a> {
+    key: &'a str,
+    /// `None` for names.
+    value: Option<&'a str>,
+    negated: bool,
+    span: Span,
+}
+
+impl Fact<'_> {
+    fn conflicts(&self, other: &Fact) -> bool {
+        let same = self.key == other.key && self.value == other.value;
+        if self.negated || other.negated {
+            return same && self.negated != other.negated;
+        }
+        match (self.value, other.value) {
+            (Some(a), Some(b)) if self.key == other.key && a != b => {
+                SINGLE_VALUE_KEYS.contains(&self.key)
+                    || (self.key == "target_family" && [a, b] == ["unix", "windows"])
+                    || (self.key == "target_family" && [a, b] == ["windows", "unix"])
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Collect the facts of the predicates of `all(...)`, including nested `all(...)`s.
+fn collect_facts<'a>(cfgs: &[Cfg<'a>], facts: &mut Vec<Fact<'a>>) {
+    for cfg in cfgs {
+        match cfg {
+            Cfg::All(cfgs) => collect_facts(cfgs, facts),
+            Cfg::Not(cfg) => {
+                if let Some(mut fact) = leaf_fact(cfg) {
+                    fact.negated = true;
+                    facts.push(fact);
+                }
+            }
+            cfg => facts.extend(leaf_fact(cfg)),
+        }
+    }
+}
+
+fn leaf_fact<'a>(cfg: &Cfg<'a>) -> Option<Fact<'a>> {
+    let fact = match cfg {
+        // `unix` and `windows` are shorthands for `target_family = "..."`.
+        Cfg::Name(name) if matches!(name.text, "unix" | "windows") => Fact {
+            key: "target_family",
+            value: Some(name.text),
+            negated: false,
+            span: name.span,
+        },
+        Cfg::Name(name) => Fact {
+            key: name.text,
+            value: None,
+            negated: false,
+            span: name.span,
+        },
+        Cfg::KeyPair(key, value) => Fact {
+            key: key.text,
+            value: Some(value.text),
+            negated: false,
+            span: Span::new(key.span.start, value.span.end),
+        },
+        _ => return None,
+    };
+    Some(fact)
+}
@@ -0,0 +1,119 @@
+//! Evaluation of target specs for a target platform.
+
+use super::{targets, Cfg, TargetSpec};
+
+/// A target platform, described by its triple and the cfgs that are set for it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Platform {
+    pub triple: String,
+    /// Names like `unix` and `key = "value"` pairs, sorted.
+    cfgs: Vec<(String, Option<String>)>,
+}
+
+const HOST_ENV: &str = if cfg!(target_env = "gnu") {
+    "gnu"
+} else if cfg!(target_env = "musl") {
+    "musl"
+} else if cfg!(target_env = "msvc") {
+    "msvc"
+} else {
+    ""
+};
+
+impl Platform {
+    /// Parse the output of `rustc --print cfg`, which has one name or `key="value"` pair per
+    /// line. Returns the index of the first invalid line.
+    pub fn from_rustc_cfg(triple: impl Into<String>, output: &str) -> Result<Self, usize> {
+        let mut cfgs = Vec::new();
+        for (i, line) in output.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let cfg = match line.split_once('=') {
+                Some((key, value)) => {
+                    let value = (value.trim().strip_prefix('"'))
+                        .and_then(|v| v.strip_suffix('"'))
+                        .filter(|v| !v.contains('"'))
+                        .ok_or(i)?;
+                    (key.trim(), Some(value.to_string()))
+                }
+                None => (line, None),
+            };
+            if !is_ident(cfg.0) {
+                return Err(i);
+            }
+            cfgs.push((cfg.0.to_string(), cfg.1));
+        }
+        cfgs.sort();
+        cfgs.dedup();
+        Ok(Self {
+            triple: triple.into(),
+            cfgs,
+        })
+    }
+
+    /// One of the built-in common targets.
+    pub fn builtin(triple: &str) -> Option<Self> {
+        let idx = (targets::TARGETS)
+            .binary_search_by(|(t, _)| t.cmp(&triple))
+            .ok()?;
+        let (triple, cfgs) = targets::TARGETS[idx];
+        let platform = Self::from_rustc_cfg(triple, &cfgs.join("\n"));
+        debug_assert!(platform.is_ok(), "invalid built-in cfgs of `{triple}`");
+        platform.ok()
+    }
+
+    /// The triples of the built-in targets.
+    pub fn builtin_triples() -> impl Iterator<Item = &'static str> {
+        targets::TARGETS.iter().map(|(t, _)| *t)
+    }
+
+    /// The platform this was compiled for. `None` if it isn't one of the built-in targets, or if
+    /// it's ambiguous because multiple built-in targets have the same arch, os and env.
+    pub fn host() -> Option<Self> {
+        let host_cfgs = [
+            format!("target_arch=\"{}\"", std::env::consts::ARCH),
+            format!("target_os=\"{}\"", std::env::consts::OS),
+            format!("target_env=\"{HOST_ENV}\""),
+        ];
+        let mut matching = (targets::TARGETS.iter())
+            .filter(|(_, cfgs)| host_cfgs.iter().all(|c| cfgs.contains(&c.as_str())));
+        let (triple, _) = matching.next()?;
+        if matching.next().is_some() {
+            return None;
+        }
+        Self::builtin(triple)
+    }
+
+    pub fn has_name(&self, name: &str) -> bool {
+        (self.cfgs.iter()).any(|(n, v)| n == name && v.is_none())
+    }
+
+    pub fn has_pair(&self, key: &str, value: &str) -> bool {
+        (self.cfgs.iter()).any(|(k, v)| k == key && v.as_deref() == Some(value))
+    }
+
+    /// Whether the spec of a `[target.<spec>]` table applies to this platform.
+    pub fn matches(&self, spec: &TargetSpec) -> bool {
+        match spec {
+            TargetSpec::Triple(triple) => triple.text == self.triple,
+            TargetSpec::Cfg(cfg) => self.eval(cfg),
+        }
+    }
+
+    pub fn eval(&self, cfg: &Cfg) -> bool {
+        match cfg {
+            Cfg::Name(name) => self.has_name(name.text),
+            Cfg::KeyPair(key, value) => self.has_pair(key.text, value.text),
+            Cfg::All(cfgs) => cfgs.iter().all(|c| self.eval(c)),
+            Cfg::Any(cfgs) => cfgs.iter().any(|c| self.eval(c)),
+            Cfg::Not(cfg) => !self.eval(cfg),
+        }
+    }
+}
+
+fn is_ident(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars.next().is_some_and(super::is_ident_start) && chars.all(super::is_ident_char)
+}
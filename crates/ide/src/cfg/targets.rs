@@ -0,0 +1,687 @@
+//! The cfgs of common targets, generated from the output of `rustc --print cfg --target <triple>`
+//! of rustc 1.95.0, without `debug_assertions`. The list is sorted by triple, so it can be
+//! binary searched.
+
+pub const TARGETS: [(&str, &[&str]); 28] = [
+    (
+        "aarch64-apple-darwin",
+        &[
+            "panic=\"unwind\"",
+            "target_abi=\"\"",
+            "target_arch=\"aarch64\"",
+            "target_endian=\"little\"",
+            "target_env=\"\"",
+            "target_family=\"unix\"",
+            "target_feature=\"aes\"",
+            "target_feature=\"crc\"",
+            "target_feature=\"dit\"",
+            "target_feature=\"dotprod\"",
+            "target_feature=\"dpb\"",
+            "target_feature=\"dpb2\"",
+            "target_feature=\"fcma\"",
+            "target_feature=\"fhm\"",
+            "target_feature=\"flagm\"",
+            "target_feature=\"fp16\"",
+            "target_feature=\"frintts\"",
+            "target_feature=\"jsconv\"",
+            "target_feature=\"lor\"",
+            "target_feature=\"lse\"",
+            "target_feature=\"neon\"",
+            "target_feature=\"paca\"",
+            "target_feature=\"pacg\"",
+            "target_feature=\"pan\"",
+            "target_feature=\"pmuv3\"",
+            "target_feature=\"ras\"",
+            "target_feature=\"rcpc\"",
+            "target_feature=\"rcpc2\"",
+            "target_feature=\"rdm\"",
+            "target_feature=\"sb\"",
+            "target_feature=\"sha2\"",
+            "target_feature=\"sha3\"",
+            "target_feature=\"ssbs\"",
+            "target_feature=\"vh\"",
+            "target_has_atomic=\"128\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"macos\"",
+            "target_pointer_width=\"64\"",
+            "target_vendor=\"apple\"",
+            "unix",
+        ],
+    ),
+    (
+        "aarch64-apple-ios",
+        &[
+            "panic=\"unwind\"",
+            "target_abi=\"\"",
+            "target_arch=\"aarch64\"",
+            "target_endian=\"little\"",
+            "target_env=\"\"",
+            "target_family=\"unix\"",
+            "target_feature=\"aes\"",
+            "target_feature=\"neon\"",
+            "target_feature=\"pmuv3\"",
+            "target_feature=\"sha2\"",
+            "target_has_atomic=\"128\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"ios\"",
+            "target_pointer_width=\"64\"",
+            "target_vendor=\"apple\"",
+            "unix",
+        ],
+    ),
+    (
+        "aarch64-linux-android",
+        &[
+            "panic=\"unwind\"",
+            "target_abi=\"\"",
+            "target_arch=\"aarch64\"",
+            "target_endian=\"little\"",
+            "target_env=\"\"",
+            "target_family=\"unix\"",
+            "target_feature=\"neon\"",
+            "target_has_atomic=\"128\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"android\"",
+            "target_pointer_width=\"64\"",
+            "target_vendor=\"unknown\"",
+            "unix",
+        ],
+    ),
+    (
+        "aarch64-pc-windows-msvc",
+        &[
+            "panic=\"unwind\"",
+            "target_abi=\"\"",
+            "target_arch=\"aarch64\"",
+            "target_endian=\"little\"",
+            "target_env=\"msvc\"",
+            "target_family=\"windows\"",
+            "target_feature=\"neon\"",
+            "target_has_atomic=\"128\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"windows\"",
+            "target_pointer_width=\"64\"",
+            "target_vendor=\"pc\"",
+            "windows",
+        ],
+    ),
+    (
+        "aarch64-unknown-linux-gnu",
+        &[
+            "panic=\"unwind\"",
+            "target_abi=\"\"",
+            "target_arch=\"aarch64\"",
+            "target_endian=\"little\"",
+            "target_env=\"gnu\"",
+            "target_family=\"unix\"",
+            "target_feature=\"neon\"",
+            "target_has_atomic=\"128\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"linux\"",
+            "target_pointer_width=\"64\"",
+            "target_vendor=\"unknown\"",
+            "unix",
+        ],
+    ),
+    (
+        "aarch64-unknown-linux-musl",
+        &[
+            "panic=\"unwind\"",
+            "target_abi=\"\"",
+            "target_arch=\"aarch64\"",
+            "target_endian=\"little\"",
+            "target_env=\"musl\"",
+            "target_family=\"unix\"",
+            "target_feature=\"crt-static\"",
+            "target_feature=\"neon\"",
+            "target_has_atomic=\"128\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"linux\"",
+            "target_pointer_width=\"64\"",
+            "target_vendor=\"unknown\"",
+            "unix",
+        ],
+    ),
+    (
+        "arm-unknown-linux-gnueabihf",
+        &[
+            "panic=\"unwind\"",
+            "target_abi=\"eabihf\"",
+            "target_arch=\"arm\"",
+            "target_endian=\"little\"",
+            "target_env=\"gnu\"",
+            "target_family=\"unix\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"linux\"",
+            "target_pointer_width=\"32\"",
+            "target_vendor=\"unknown\"",
+            "unix",
+        ],
+    ),
+    (
+        "armv7-linux-androideabi",
+        &[
+            "panic=\"unwind\"",
+            "target_abi=\"eabi\"",
+            "target_arch=\"arm\"",
+            "target_endian=\"little\"",
+            "target_env=\"\"",
+            "target_family=\"unix\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"android\"",
+            "target_pointer_width=\"32\"",
+            "target_vendor=\"unknown\"",
+            "unix",
+        ],
+    ),
+    (
+        "armv7-unknown-linux-gnueabihf",
+        &[
+            "panic=\"unwind\"",
+            "target_abi=\"eabihf\"",
+            "target_arch=\"arm\"",
+            "target_endian=\"little\"",
+            "target_env=\"gnu\"",
+            "target_family=\"unix\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"linux\"",
+            "target_pointer_width=\"32\"",
+            "target_vendor=\"unknown\"",
+            "unix",
+        ],
+    ),
+    (
+        "i686-pc-windows-msvc",
+        &[
+            "panic=\"unwind\"",
+            "target_abi=\"\"",
+            "target_arch=\"x86\"",
+            "target_endian=\"little\"",
+            "target_env=\"msvc\"",
+            "target_family=\"windows\"",
+            "target_feature=\"fxsr\"",
+            "target_feature=\"sse\"",
+            "target_feature=\"sse2\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"windows\"",
+            "target_pointer_width=\"32\"",
+            "target_vendor=\"pc\"",
+            "windows",
+        ],
+    ),
+    (
+        "i686-unknown-linux-gnu",
+        &[
+            "panic=\"unwind\"",
+            "target_abi=\"\"",
+            "target_arch=\"x86\"",
+            "target_endian=\"little\"",
+            "target_env=\"gnu\"",
+            "target_family=\"unix\"",
+            "target_feature=\"fxsr\"",
+            "target_feature=\"sse\"",
+            "target_feature=\"sse2\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"linux\"",
+            "target_pointer_width=\"32\"",
+            "target_vendor=\"unknown\"",
+            "unix",
+        ],
+    ),
+    (
+        "powerpc64le-unknown-linux-gnu",
+        &[
+            "panic=\"unwind\"",
+            "target_abi=\"elfv2\"",
+            "target_arch=\"powerpc64\"",
+            "target_endian=\"little\"",
+            "target_env=\"gnu\"",
+            "target_family=\"unix\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"linux\"",
+            "target_pointer_width=\"64\"",
+            "target_vendor=\"unknown\"",
+            "unix",
+        ],
+    ),
+    (
+        "riscv64gc-unknown-linux-gnu",
+        &[
+            "panic=\"unwind\"",
+            "target_abi=\"\"",
+            "target_arch=\"riscv64\"",
+            "target_endian=\"little\"",
+            "target_env=\"gnu\"",
+            "target_family=\"unix\"",
+            "target_feature=\"a\"",
+            "target_feature=\"c\"",
+            "target_feature=\"m\"",
+            "target_feature=\"zaamo\"",
+            "target_feature=\"zalrsc\"",
+            "target_feature=\"zca\"",
+            "target_feature=\"zicsr\"",
+            "target_feature=\"zifencei\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"linux\"",
+            "target_pointer_width=\"64\"",
+            "target_vendor=\"unknown\"",
+            "unix",
+        ],
+    ),
+    (
+        "s390x-unknown-linux-gnu",
+        &[
+            "panic=\"unwind\"",
+            "target_abi=\"\"",
+            "target_arch=\"s390x\"",
+            "target_endian=\"big\"",
+            "target_env=\"gnu\"",
+            "target_family=\"unix\"",
+            "target_has_atomic=\"128\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"linux\"",
+            "target_pointer_width=\"64\"",
+            "target_vendor=\"unknown\"",
+            "unix",
+        ],
+    ),
+    (
+        "thumbv7em-none-eabihf",
+        &[
+            "panic=\"abort\"",
+            "target_abi=\"eabihf\"",
+            "target_arch=\"arm\"",
+            "target_endian=\"little\"",
+            "target_env=\"\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"none\"",
+            "target_pointer_width=\"32\"",
+            "target_vendor=\"unknown\"",
+        ],
+    ),
+    (
+        "wasm32-unknown-emscripten",
+        &[
+            "panic=\"unwind\"",
+            "target_abi=\"\"",
+            "target_arch=\"wasm32\"",
+            "target_endian=\"little\"",
+            "target_env=\"\"",
+            "target_family=\"unix\"",
+            "target_family=\"wasm\"",
+            "target_feature=\"bulk-memory\"",
+            "target_feature=\"crt-static\"",
+            "target_feature=\"multivalue\"",
+            "target_feature=\"mutable-globals\"",
+            "target_feature=\"nontrapping-fptoint\"",
+            "target_feature=\"reference-types\"",
+            "target_feature=\"sign-ext\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"emscripten\"",
+            "target_pointer_width=\"32\"",
+            "target_vendor=\"unknown\"",
+            "unix",
+        ],
+    ),
+    (
+        "wasm32-unknown-unknown",
+        &[
+            "panic=\"abort\"",
+            "target_abi=\"\"",
+            "target_arch=\"wasm32\"",
+            "target_endian=\"little\"",
+            "target_env=\"\"",
+            "target_family=\"wasm\"",
+            "target_feature=\"bulk-memory\"",
+            "target_feature=\"multivalue\"",
+            "target_feature=\"mutable-globals\"",
+            "target_feature=\"nontrapping-fptoint\"",
+            "target_feature=\"reference-types\"",
+            "target_feature=\"sign-ext\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"unknown\"",
+            "target_pointer_width=\"32\"",
+            "target_vendor=\"unknown\"",
+        ],
+    ),
+    (
+        "wasm32-wasip1",
+        &[
+            "panic=\"abort\"",
+            "target_abi=\"\"",
+            "target_arch=\"wasm32\"",
+            "target_endian=\"little\"",
+            "target_env=\"p1\"",
+            "target_family=\"wasm\"",
+            "target_feature=\"bulk-memory\"",
+            "target_feature=\"crt-static\"",
+            "target_feature=\"multivalue\"",
+            "target_feature=\"mutable-globals\"",
+            "target_feature=\"nontrapping-fptoint\"",
+            "target_feature=\"reference-types\"",
+            "target_feature=\"sign-ext\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"wasi\"",
+            "target_pointer_width=\"32\"",
+            "target_vendor=\"unknown\"",
+        ],
+    ),
+    (
+        "x86_64-apple-darwin",
+        &[
+            "panic=\"unwind\"",
+            "target_abi=\"\"",
+            "target_arch=\"x86_64\"",
+            "target_endian=\"little\"",
+            "target_env=\"\"",
+            "target_family=\"unix\"",
+            "target_feature=\"cmpxchg16b\"",
+            "target_feature=\"fxsr\"",
+            "target_feature=\"sse\"",
+            "target_feature=\"sse2\"",
+            "target_feature=\"sse3\"",
+            "target_feature=\"sse4.1\"",
+            "target_feature=\"ssse3\"",
+            "target_has_atomic=\"128\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"macos\"",
+            "target_pointer_width=\"64\"",
+            "target_vendor=\"apple\"",
+            "unix",
+        ],
+    ),
+    (
+        "x86_64-linux-android",
+        &[
+            "panic=\"unwind\"",
+            "target_abi=\"\"",
+            "target_arch=\"x86_64\"",
+            "target_endian=\"little\"",
+            "target_env=\"\"",
+            "target_family=\"unix\"",
+            "target_feature=\"fxsr\"",
+            "target_feature=\"popcnt\"",
+            "target_feature=\"sse\"",
+            "target_feature=\"sse2\"",
+            "target_feature=\"sse3\"",
+            "target_feature=\"sse4.1\"",
+            "target_feature=\"sse4.2\"",
+            "target_feature=\"ssse3\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"android\"",
+            "target_pointer_width=\"64\"",
+            "target_vendor=\"unknown\"",
+            "unix",
+        ],
+    ),
+    (
+        "x86_64-pc-windows-gnu",
+        &[
+            "panic=\"unwind\"",
+            "target_abi=\"\"",
+            "target_arch=\"x86_64\"",
+            "target_endian=\"little\"",
+            "target_env=\"gnu\"",
+            "target_family=\"windows\"",
+            "target_feature=\"cmpxchg16b\"",
+            "target_feature=\"fxsr\"",
+            "target_feature=\"sse\"",
+            "target_feature=\"sse2\"",
+            "target_feature=\"sse3\"",
+            "target_has_atomic=\"128\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"windows\"",
+            "target_pointer_width=\"64\"",
+            "target_vendor=\"pc\"",
+            "windows",
+        ],
+    ),
+    (
+        "x86_64-pc-windows-msvc",
+        &[
+            "panic=\"unwind\"",
+            "target_abi=\"\"",
+            "target_arch=\"x86_64\"",
+            "target_endian=\"little\"",
+            "target_env=\"msvc\"",
+            "target_family=\"windows\"",
+            "target_feature=\"cmpxchg16b\"",
+            "target_feature=\"fxsr\"",
+            "target_feature=\"sse\"",
+            "target_feature=\"sse2\"",
+            "target_feature=\"sse3\"",
+            "target_has_atomic=\"128\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"windows\"",
+            "target_pointer_width=\"64\"",
+            "target_vendor=\"pc\"",
+            "windows",
+        ],
+    ),
+    (
+        "x86_64-unknown-freebsd",
+        &[
+            "panic=\"unwind\"",
+            "target_abi=\"\"",
+            "target_arch=\"x86_64\"",
+            "target_endian=\"little\"",
+            "target_env=\"\"",
+            "target_family=\"unix\"",
+            "target_feature=\"fxsr\"",
+            "target_feature=\"sse\"",
+            "target_feature=\"sse2\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"freebsd\"",
+            "target_pointer_width=\"64\"",
+            "target_vendor=\"unknown\"",
+            "unix",
+        ],
+    ),
+    (
+        "x86_64-unknown-illumos",
+        &[
+            "panic=\"unwind\"",
+            "target_abi=\"\"",
+            "target_arch=\"x86_64\"",
+            "target_endian=\"little\"",
+            "target_env=\"\"",
+            "target_family=\"unix\"",
+            "target_feature=\"fxsr\"",
+            "target_feature=\"sse\"",
+            "target_feature=\"sse2\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"illumos\"",
+            "target_pointer_width=\"64\"",
+            "target_vendor=\"unknown\"",
+            "unix",
+        ],
+    ),
+    (
+        "x86_64-unknown-linux-gnu",
+        &[
+            "panic=\"unwind\"",
+            "target_abi=\"\"",
+            "target_arch=\"x86_64\"",
+            "target_endian=\"little\"",
+            "target_env=\"gnu\"",
+            "target_family=\"unix\"",
+            "target_feature=\"fxsr\"",
+            "target_feature=\"sse\"",
+            "target_feature=\"sse2\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"linux\"",
+            "target_pointer_width=\"64\"",
+            "target_vendor=\"unknown\"",
+            "unix",
+        ],
+    ),
+    (
+        "x86_64-unknown-linux-musl",
+        &[
+            "panic=\"unwind\"",
+            "target_abi=\"\"",
+            "target_arch=\"x86_64\"",
+            "target_endian=\"little\"",
+            "target_env=\"musl\"",
+            "target_family=\"unix\"",
+            "target_feature=\"crt-static\"",
+            "target_feature=\"fxsr\"",
+            "target_feature=\"sse\"",
+            "target_feature=\"sse2\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"linux\"",
+            "target_pointer_width=\"64\"",
+            "target_vendor=\"unknown\"",
+            "unix",
+        ],
+    ),
+    (
+        "x86_64-unknown-netbsd",
+        &[
+            "panic=\"unwind\"",
+            "target_abi=\"\"",
+            "target_arch=\"x86_64\"",
+            "target_endian=\"little\"",
+            "target_env=\"\"",
+            "target_family=\"unix\"",
+            "target_feature=\"fxsr\"",
+            "target_feature=\"sse\"",
+            "target_feature=\"sse2\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"netbsd\"",
+            "target_pointer_width=\"64\"",
+            "target_vendor=\"unknown\"",
+            "unix",
+        ],
+    ),
+    (
+        "x86_64-unknown-openbsd",
+        &[
+            "panic=\"unwind\"",
+            "target_abi=\"\"",
+            "target_arch=\"x86_64\"",
+            "target_endian=\"little\"",
+            "target_env=\"\"",
+            "target_family=\"unix\"",
+            "target_feature=\"fxsr\"",
+            "target_feature=\"sse\"",
+            "target_feature=\"sse2\"",
+            "target_has_atomic=\"16\"",
+            "target_has_atomic=\"32\"",
+            "target_has_atomic=\"64\"",
+            "target_has_atomic=\"8\"",
+            "target_has_atomic=\"ptr\"",
+            "target_os=\"openbsd\"",
+            "target_pointer_width=\"64\"",
+            "target_vendor=\"unknown\"",
+            "unix",
+        ],
+    ),
+];
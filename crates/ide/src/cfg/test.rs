@@ -0,0 +1,278 @@
+use pretty_assertions::assert_eq;
+
+use super::*;
+
+fn span(char: u32, len: u32) -> Span {
+    Span::new(Pos::new(0, char), Pos::new(0, char + len))
+}
+
+fn name(text: &str, span: Span) -> Cfg<'_> {
+    Cfg::Name(Name { text, span })
+}
+
+fn pair<'a>(key: &'a str, key_span: Span, value: &'a str, value_span: Span) -> Cfg<'a> {
+    Cfg::KeyPair(
+        Name {
+            text: key,
+            span: key_span,
+        },
+        Name {
+            text: value,
+            span: value_span,
+        },
+    )
+}
+
+#[track_caller]
+fn check_errors(input: &str, expected: Vec<(Span, ErrorKind)>) {
+    let parsed = parse_target_spec(input);
+    let errors: Vec<_> = (parsed.errors.into_iter())
+        .map(|e| (e.span, e.kind))
+        .collect();
+    assert_eq!(expected, errors);
+}
+
+#[track_caller]
+fn check_warnings(input: &str, expected: Vec<(Span, WarningKind)>) {
+    let parsed = parse_target_spec(input);
+    assert_eq!(Vec::<Error>::new(), parsed.errors);
+    let warnings: Vec<_> = (parsed.warnings.into_iter())
+        .map(|w| (w.span, w.kind))
+        .collect();
+    assert_eq!(expected, warnings);
+}
+
+#[test]
+fn triple() {
+    let parsed = parse_target_spec("x86_64-unknown-linux-gnu");
+    assert_eq!(
+        Parsed {
+            spec: Some(TargetSpec::Triple(Name {
+                text: "x86_64-unknown-linux-gnu",
+                span: span(0, 24),
+            })),
+            errors: Vec::new(),
+            warnings: Vec::new(),
+        },
+        parsed
+    );
+}
+
+#[test]
+fn names_and_pairs() {
+    let parsed = parse_target_spec("cfg(unix)");
+    let expected = TargetSpec::Cfg(name("unix", span(4, 4)));
+    assert_eq!(Some(expected), parsed.spec);
+
+    let parsed = parse_target_spec(r#"cfg(target_os = "linux")"#);
+    let expected = TargetSpec::Cfg(pair("target_os", span(4, 9), "linux", span(16, 7)));
+    assert_eq!(Some(expected), parsed.spec);
+}
+
+#[test]
+fn predicates() {
+    let parsed = parse_target_spec(r#"cfg(all(unix, not(target_arch = "x86"), any(a, b,)))"#);
+    let expected = TargetSpec::Cfg(Cfg::All(vec![
+        name("unix", span(8, 4)),
+        Cfg::Not(Box::new(pair(
+            "target_arch",
+            span(18, 11),
+            "x86",
+            span(32, 5),
+        ))),
+        Cfg::Any(vec![name("a", span(44, 1)), name("b", span(47, 1))]),
+    ]));
+    assert_eq!(Some(expected), parsed.spec);
+    assert_eq!(Vec::<Error>::new(), parsed.errors);
+
+    let parsed = parse_target_spec("cfg(any())");
+    assert_eq!(Some(TargetSpec::Cfg(Cfg::Any(Vec::new()))), parsed.spec);
+}
+
+#[test]
+fn errors() {
+    check_errors("", vec![(span(0, 0), ErrorKind::Empty)]);
+    check_errors(
+        "x86_64 linux",
+        vec![(span(6, 1), ErrorKind::InvalidTripleChar(' '))],
+    );
+    check_errors("cfg()", vec![(span(3, 2), ErrorKind::ExpectedCfg)]);
+    check_errors("cfg(unix", vec![(span(3, 1), ErrorKind::UnclosedParen)]);
+    check_errors(
+        "cfg(all(unix windows))",
+        vec![(span(13, 7), ErrorKind::ExpectedCommaOrParen)],
+    );
+    check_errors(
+        "cfg(target_os = linux)",
+        vec![(span(16, 5), ErrorKind::ExpectedValue)],
+    );
+    check_errors(
+        r#"cfg(target_os = "linux)"#,
+        vec![(span(16, 7), ErrorKind::UnterminatedString)],
+    );
+    check_errors(
+        "cfg(unix$)",
+        vec![(span(8, 1), ErrorKind::InvalidChar('$'))],
+    );
+    check_errors(
+        "cfg(either(unix))",
+        vec![(
+            span(4, 6),
+            ErrorKind::UnknownPredicate(FmtStr::from_str("either")),
+        )],
+    );
+    check_errors(
+        "cfg(not(unix, windows))",
+        vec![(span(4, 18), ErrorKind::NotArgCount)],
+    );
+    check_errors(
+        "cfg(unix) x",
+        vec![(span(10, 1), ErrorKind::UnexpectedInput)],
+    );
+}
+
+#[test]
+fn unknown_keys_and_names() {
+    check_warnings(
+        r#"cfg(os = "linux")"#,
+        vec![(
+            span(4, 2),
+            WarningKind::UnknownKey(FmtStr::from_str("os"), Some("target_os")),
+        )],
+    );
+    check_warnings(
+        r#"cfg(platform = "linux")"#,
+        vec![(
+            span(4, 8),
+            WarningKind::UnknownKey(FmtStr::from_str("platform"), None),
+        )],
+    );
+    check_warnings(
+        "cfg(target_os)",
+        vec![(span(4, 9), WarningKind::MissingValue("target_os"))],
+    );
+    check_warnings(
+        "cfg(linux)",
+        vec![(
+            span(4, 5),
+            WarningKind::NameIsValue(FmtStr::from_str("target_os = \"linux\"")),
+        )],
+    );
+    check_warnings(
+        r#"cfg(target_pointer_width = "128")"#,
+        vec![(
+            span(27, 5),
+            WarningKind::UnknownValue {
+                key: "target_pointer_width",
+                value: FmtStr::from_str("128"),
+                expected: "`16`, `32` or `64`",
+            },
+        )],
+    );
+    check_warnings(
+        r#"cfg(any(test, feature = "std"))"#,
+        vec![
+            (span(8, 4), WarningKind::NoEffect(FmtStr::from_str("test"))),
+            (
+                span(14, 15),
+                WarningKind::NoEffect(FmtStr::from_str("feature = \"std\"")),
+            ),
+        ],
+    );
+    // Custom cfgs set with `--cfg` are allowed.
+    check_warnings("cfg(tokio_unstable)", vec![]);
+}
+
+#[test]
+fn impossible() {
+    check_warnings(
+        "cfg(all(unix, windows))",
+        vec![(
+            span(4, 18),
+            WarningKind::Impossible {
+                first: FmtStr::from_str("unix"),
+                second: FmtStr::from_str("windows"),
+            },
+        )],
+    );
+    check_warnings(
+        r#"cfg(all(target_os = "linux", all(unix, target_os = "macos")))"#,
+        vec![(
+            span(4, 56),
+            WarningKind::Impossible {
+                first: FmtStr::from_str("target_os = \"linux\""),
+                second: FmtStr::from_str("target_os = \"macos\""),
+            },
+        )],
+    );
+    check_warnings(
+        "cfg(all(unix, not(unix)))",
+        vec![(
+            span(4, 20),
+            WarningKind::Impossible {
+                first: FmtStr::from_str("unix"),
+                second: FmtStr::from_str("not(unix)"),
+            },
+        )],
+    );
+    // Targets can be part of multiple families, and have multiple features.
+    check_warnings(r#"cfg(all(unix, target_family = "wasm"))"#, vec![]);
+    check_warnings(
+        r#"cfg(all(target_feature = "sse", target_feature = "avx"))"#,
+        vec![],
+    );
+    check_warnings("cfg(any(unix, windows))", vec![]);
+}
+
+#[track_caller]
+fn check_matches(platform: &Platform, input: &str, expected: bool) {
+    let spec = parse_target_spec(input).spec.unwrap();
+    assert_eq!(expected, platform.matches(&spec), "{input}");
+}
+
+#[test]
+fn eval_builtin() {
+    let linux = Platform::builtin("x86_64-unknown-linux-gnu").unwrap();
+    check_matches(&linux, "x86_64-unknown-linux-gnu", true);
+    check_matches(&linux, "x86_64-pc-windows-msvc", false);
+    check_matches(&linux, "cfg(unix)", true);
+    check_matches(&linux, "cfg(windows)", false);
+    check_matches(&linux, r#"cfg(target_os = "linux")"#, true);
+    check_matches(&linux, r#"cfg(all(unix, target_arch = "x86_64"))"#, true);
+    check_matches(&linux, r#"cfg(all(unix, target_arch = "aarch64"))"#, false);
+    check_matches(&linux, r#"cfg(any(windows, target_env = "gnu"))"#, true);
+    check_matches(&linux, r#"cfg(not(target_feature = "crt-static"))"#, true);
+    check_matches(&linux, "cfg(all())", true);
+    check_matches(&linux, "cfg(any())", false);
+    check_matches(&linux, "cfg(debug_assertions)", false);
+
+    let wasm = Platform::builtin("wasm32-unknown-unknown").unwrap();
+    check_matches(&wasm, "cfg(unix)", false);
+    check_matches(&wasm, r#"cfg(target_family = "wasm")"#, true);
+    check_matches(&wasm, r#"cfg(panic = "abort")"#, true);
+
+    assert_eq!(None, Platform::builtin("x86_64-unknown-haiku"));
+    #[cfg(all(target_arch = "x86_64", target_os = "linux", target_env = "gnu"))]
+    assert_eq!(Some(linux.clone()), Platform::host());
+    for triple in Platform::builtin_triples() {
+        let platform = Platform::builtin(triple);
+        assert_eq!(Some(triple), platform.as_ref().map(|p| p.triple.as_str()));
+    }
+}
+
+#[test]
+fn eval_rustc_cfg() {
+    let output = "debug_assertions\npanic=\"unwind\"\ntarget_os=\"redox\"\nunix\n";
+    let redox = Platform::from_rustc_cfg("x86_64-unknown-redox", output).unwrap();
+    assert!(redox.has_name("unix"));
+    assert!(redox.has_name("debug_assertions"));
+    assert!(redox.has_pair("target_os", "redox"));
+    check_matches(&redox, r#"cfg(all(unix, target_os = "redox"))"#, true);
+    check_matches(&redox, "x86_64-unknown-redox", true);
+
+    assert_eq!(
+        Err(1),
+        Platform::from_rustc_cfg("x", "unix\ntarget_os=linux\n")
+    );
+    assert_eq!(Err(0), Platform::from_rustc_cfg("x", "not a cfg"));
+}
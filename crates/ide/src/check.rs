@@ -1,18 +1,116 @@
-use common::{FmtStr, Span};
-use semver::{SemverCtx, VersionReq};
+use common::{FmtStr, Pos, Span};
+use semver::{PartialVersion, SemverCtx, Version, VersionReq};
 use toml::map::{
     self, MapArray, MapArrayInlineEntry, MapNode, MapTable, MapTableEntry, MapTableEntryRepr,
     ParentId, Scalar,
 };
-use toml::parse::{BoolVal, Ident, StringVal};
+use toml::parse::{BoolVal, Ident, IntVal, StringVal};
 use toml::util::Datatype;
 
-use crate::cargo;
-use crate::IdeCtx;
+use crate::{cargo, cfg, spdx, IdeCtx};
+
+pub use features::{Feature, FeatureGraph, FeatureMember, FeatureMemberKind, ResolvedFeatures};
+pub use lints::{Lint, LintLevel};
+pub use profile::Profile;
+pub use targets::{Target, TargetKind};
+pub use workspace::{
+    discover_members, find_root_manifest, resolve_inherited, Inherited, InheritedDependency,
+    InheritedField, Members, Workspace,
+};
+
+mod features;
+mod lints;
+mod profile;
+mod targets;
+#[cfg(test)]
+mod test;
+mod workspace;
 
 #[derive(Debug, Default, PartialEq)]
 pub struct State<'a> {
     dependencies: Vec<Dependency<'a>>,
+    /// `package.name`
+    package_name: Option<StringAssignment<'a>>,
+    /// `package.version`
+    package_version: Option<PackageVersion<'a>>,
+    /// `package.edition`
+    edition: Option<Edition>,
+    /// `package.rust-version`
+    rust_version: Option<RustVersion<'a>>,
+    features: FeatureGraph<'a>,
+    /// `[lints]`, empty if the lints are inherited from the workspace.
+    lints: Vec<Lint<'a>>,
+    /// `[profile]`
+    profiles: Vec<Profile<'a>>,
+    /// `[lib]`, `[[bin]]`, `[[example]]`, `[[test]]` and `[[bench]]`
+    targets: Vec<Target<'a>>,
+    /// `[workspace]`, if this is the root manifest of a workspace.
+    workspace: Option<Workspace<'a>>,
+}
+
+impl<'a> State<'a> {
+    pub fn dependencies(&self) -> &[Dependency<'a>] {
+        &self.dependencies
+    }
+
+    pub fn features(&self) -> &FeatureGraph<'a> {
+        &self.features
+    }
+
+    pub fn lints(&self) -> &[Lint<'a>] {
+        &self.lints
+    }
+
+    pub fn profiles(&self) -> &[Profile<'a>] {
+        &self.profiles
+    }
+
+    pub fn targets(&self) -> &[Target<'a>] {
+        &self.targets
+    }
+
+    pub fn workspace(&self) -> Option<&Workspace<'a>> {
+        self.workspace.as_ref()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Edition {
+    E2015,
+    E2018,
+    E2021,
+    E2024,
+}
+
+impl Edition {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "2015" => Some(Self::E2015),
+            "2018" => Some(Self::E2018),
+            "2021" => Some(Self::E2021),
+            "2024" => Some(Self::E2024),
+            _ => None,
+        }
+    }
+}
+
+/// The dependency resolver version, `package.resolver` or `workspace.resolver`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Resolver {
+    V1,
+    V2,
+    V3,
+}
+
+impl Resolver {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "1" => Some(Self::V1),
+            "2" => Some(Self::V2),
+            "3" => Some(Self::V3),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -41,6 +139,8 @@ pub struct Dependency<'a> {
     pub package: Option<StringAssignment<'a>>,
     pub kind: DependencyKind,
     pub target: Option<&'a str>,
+    /// The parsed `target`, `None` if it's invalid.
+    pub target_spec: Option<cfg::TargetSpec<'a>>,
     pub spec: DependencySpec<'a>,
     pub features: DependencyFeatures<'a>,
     pub optional: Option<BoolAssignment<'a>>,
@@ -54,6 +154,12 @@ impl<'a> Dependency<'a> {
             .map(|p| p.val.text)
             .unwrap_or(self.name)
     }
+
+    /// Whether the dependency is used when building for `platform`. Dependencies without a
+    /// target are always active, and so are those with an invalid target spec.
+    pub fn is_active(&self, platform: &cfg::Platform) -> bool {
+        (self.target_spec.as_ref()).is_none_or(|spec| platform.matches(spec))
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -106,6 +212,18 @@ pub struct DependencyVersion<'a> {
     req: Option<VersionReq>,
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct PackageVersion<'a> {
+    str: StringAssignment<'a>,
+    version: Option<Version>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RustVersion<'a> {
+    str: StringAssignment<'a>,
+    version: Option<PartialVersion>,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum DependencyGitSpec<'a> {
     Branch(StringAssignment<'a>),
@@ -141,6 +259,22 @@ impl<'a> StringAssignment<'a> {
     }
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntAssignment<'a> {
+    pub repr: &'a MapTableEntryRepr<'a>,
+    pub val: &'a IntVal<'a>,
+}
+
+impl<'a> IntAssignment<'a> {
+    pub fn ident(&self) -> &'a Ident<'a> {
+        self.repr.key.repr_ident()
+    }
+
+    pub fn span(&self) -> Span {
+        self.repr.repr_span()
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct BoolAssignment<'a> {
     pub repr: &'a MapTableEntryRepr<'a>,
@@ -159,23 +293,43 @@ impl<'a> BoolAssignment<'a> {
 
 pub fn check<'a>(ctx: &mut impl IdeCtx, table: &'a MapTable<'a>) -> State<'a> {
     let mut state = State::default();
+    // Features reference dependencies, so they are parsed last.
+    let mut features = None;
+    // Targets reference features.
+    let mut targets = Vec::new();
     for (key, entry) in table.iter() {
         let path = map::Path::root(&entry.reprs);
         match *key {
+            "package" => {
+                if let Some(table) = expect_table_in_table(ctx, &path, entry) {
+                    parse_package(ctx, &mut state, &path, table);
+                }
+            }
+            "lib" => targets.push((TargetKind::Lib, entry)),
+            "bin" => targets.push((TargetKind::Bin, entry)),
+            "example" => targets.push((TargetKind::Example, entry)),
+            "test" => targets.push((TargetKind::Test, entry)),
+            "bench" => targets.push((TargetKind::Bench, entry)),
             // TODO
-            "package" => (),
-            "lib" => (),
-            "bin" => (),
-            "example" => (),
-            "test" => (),
-            "bench" => (),
             "badges" => (),
-            "features" => (),
-            "lints" => (),
+            "features" => features = Some(entry),
+            "lints" => {
+                if let Some(table) = expect_table_in_table(ctx, &path, entry) {
+                    state.lints = lints::parse_lints(ctx, &path, table, true);
+                }
+            }
             "patch" => (),
             "replace" => (),
-            "profile" => (),
-            "workspace" => (),
+            "profile" => {
+                if let Some(table) = expect_table_in_table(ctx, &path, entry) {
+                    state.profiles = profile::parse_profiles(ctx, &path, table);
+                }
+            }
+            "workspace" => {
+                if let Some(table) = expect_table_in_table(ctx, &path, entry) {
+                    state.workspace = Some(workspace::parse_workspace(ctx, &path, entry, table));
+                }
+            }
 
             "dependencies" => {
                 if let Some(table) = expect_table_in_table(ctx, &path, entry) {
@@ -227,9 +381,99 @@ pub fn check<'a>(ctx: &mut impl IdeCtx, table: &'a MapTable<'a>) -> State<'a> {
             _ => warn_unused(ctx, &path, entry),
         }
     }
+
+    if let Some(entry) = features {
+        let path = map::Path::root(&entry.reprs);
+        if let Some(table) = expect_table_in_table(ctx, &path, entry) {
+            state.features = features::parse_features(ctx, &state.dependencies, &path, table);
+        }
+    }
+
+    state.targets = targets::parse_targets(ctx, &state.features, targets);
+
     state
 }
 
+/// Lint the version requirements of all dependencies. A manifest is considered a library if it
+/// has a `[lib]` target, or if `implicit_lib` is set because the package has a `src/lib.rs`.
+/// Dev-dependencies are never linted as part of a library.
+pub fn lint_version_reqs(ctx: &mut impl IdeCtx, state: &State, implicit_lib: bool) {
+    let library = implicit_lib || state.targets.iter().any(|t| t.kind == TargetKind::Lib);
+    for dep in state.dependencies.iter() {
+        let version = match &dep.spec {
+            DependencySpec::Git { version, .. } => version.as_ref(),
+            DependencySpec::Path { version, .. } => version.as_ref(),
+            DependencySpec::Registry { version, .. } => Some(version),
+            _ => None,
+        };
+        let Some(DependencyVersion {
+            str,
+            req: Some(req),
+        }) = version
+        else {
+            continue;
+        };
+
+        let options = semver::LintOptions {
+            library: library && dep.kind != DependencyKind::Dev,
+        };
+        let req = VersionReq {
+            pos: Pos::ZERO,
+            ..req.clone()
+        };
+        let mut diagnostics =
+            common::Diagnostics::<semver::Error, semver::Warning, semver::Info>::default();
+        semver::lint_requirement(&mut diagnostics, &req, options);
+
+        let map_span = |s: Span| str.val.text_range_span(s.start.char..s.end.char);
+        for mut w in diagnostics.warnings {
+            w.map_spans(map_span);
+            ctx.warn(w);
+        }
+        for mut i in diagnostics.infos {
+            i.map_spans(map_span);
+            ctx.info(i);
+        }
+    }
+}
+
+/// Keys that aren't allowed in the embedded manifest of a cargo script.
+const SCRIPT_FORBIDDEN_KEYS: [&str; 6] = ["workspace", "lib", "bin", "example", "test", "bench"];
+const SCRIPT_FORBIDDEN_PACKAGE_KEYS: [&str; 3] = ["workspace", "build", "links"];
+
+/// Check the manifest embedded in a cargo script, see [`crate::script`].
+pub fn check_script<'a>(ctx: &mut impl IdeCtx, table: &'a MapTable<'a>) -> State<'a> {
+    for (key, entry) in table.iter() {
+        let path = map::Path::root(&entry.reprs);
+        if SCRIPT_FORBIDDEN_KEYS.contains(key) {
+            script_forbidden_key(ctx, &path, entry);
+        } else if *key == "package" {
+            let MapNode::Table(package) = &entry.node else {
+                continue;
+            };
+            for (key, entry) in package.iter() {
+                if SCRIPT_FORBIDDEN_PACKAGE_KEYS.contains(key) {
+                    let path = path.append_key(&entry.reprs);
+                    script_forbidden_key(ctx, &path, entry);
+                }
+            }
+        }
+    }
+
+    check(ctx, table)
+}
+
+fn script_forbidden_key(ctx: &mut impl IdeCtx, path: &map::Path, entry: &MapTableEntry) {
+    for repr in entry.reprs.iter() {
+        ctx.error(cargo::Error::new(
+            map::context_lines(path.prev, [repr.parent]),
+            path.fmt_path(),
+            repr.repr_span(),
+            cargo::ErrorKind::ScriptForbiddenKey,
+        ));
+    }
+}
+
 pub fn parse_target<'a>(
     ctx: &mut impl IdeCtx,
     state: &mut State<'a>,
@@ -238,11 +482,48 @@ pub fn parse_target<'a>(
 ) {
     for (key, entry) in table.iter() {
         let path = path.append_key(&entry.reprs);
-        // TODO: validate target spec
+        let spec = check_target_spec(ctx, &path, entry, key);
         if let Some(table) = expect_table_in_table(ctx, &path, entry) {
+            let start = state.dependencies.len();
             parse_target_dependencies(ctx, state, &path, table, key);
+            for dep in state.dependencies[start..].iter_mut() {
+                dep.target_spec = spec.clone();
+            }
+        }
+    }
+}
+
+fn check_target_spec<'a>(
+    ctx: &mut impl IdeCtx,
+    path: &map::Path,
+    entry: &MapTableEntry,
+    spec: &'a str,
+) -> Option<cfg::TargetSpec<'a>> {
+    let parsed = cfg::parse_target_spec(spec);
+    for repr in entry.reprs.iter() {
+        let lines = || map::context_lines(path.prev, [repr.parent]);
+        let ident = repr.key.repr_ident();
+        let map_span = |s: Span| ident.text_range_span(s.start.char..s.end.char);
+        for e in parsed.errors.iter() {
+            let kind = cargo::ErrorKind::TargetSpec(e.kind.clone());
+            ctx.error(cargo::Error::new(
+                lines(),
+                path.fmt_path(),
+                map_span(e.span),
+                kind,
+            ));
+        }
+        for w in parsed.warnings.iter() {
+            let kind = cargo::WarningKind::TargetSpec(w.kind.clone());
+            ctx.warn(cargo::Warning::new(
+                lines(),
+                path.fmt_path(),
+                map_span(w.span),
+                kind,
+            ));
         }
     }
+    parsed.spec
 }
 
 pub fn parse_target_dependencies<'a>(
@@ -437,14 +718,15 @@ impl<'a> DependencyBuilder<'a> {
                         + self.tag.is_some() as u8
                         + self.rev.is_some() as u8;
                     let spec = if num > 1 {
-                        for key in [&self.branch, &self.tag, &self.rev].into_iter().flatten() {
-                            ctx.error(cargo::Error::new(
-                                path.context_lines([key.repr.parent]),
-                                path.fmt_path(),
-                                key.span(),
-                                cargo::ErrorKind::AmbigousGitSpec,
-                            ));
-                        }
+                        let mut keys = [&self.branch, &self.tag, &self.rev].into_iter().flatten();
+                        let first = keys.next().expect("more than one key");
+                        let others = keys.map(|k| k.span()).collect();
+                        ctx.error(cargo::Error::new(
+                            path.context_lines([first.repr.parent]),
+                            path.fmt_path(),
+                            first.span(),
+                            cargo::ErrorKind::AmbigousGitSpec { others },
+                        ));
                         DependencyGitSpec::Conflicting
                     } else if let Some(branch) = self.branch {
                         DependencyGitSpec::Branch(branch)
@@ -497,6 +779,7 @@ impl<'a> DependencyBuilder<'a> {
             package: self.package,
             kind,
             target,
+            target_spec: None,
             spec,
             features: self.features,
             optional: self.optional,
@@ -505,6 +788,365 @@ impl<'a> DependencyBuilder<'a> {
     }
 }
 
+/// `[package]` keys that can be inherited from `[workspace.package]`.
+const INHERITABLE_PACKAGE_KEYS: [&str; 16] = [
+    "version",
+    "authors",
+    "description",
+    "documentation",
+    "readme",
+    "homepage",
+    "repository",
+    "license",
+    "license-file",
+    "keywords",
+    "categories",
+    "publish",
+    "edition",
+    "rust-version",
+    "exclude",
+    "include",
+];
+
+fn parse_package<'a>(
+    ctx: &mut impl IdeCtx,
+    state: &mut State<'a>,
+    path: &map::Path<'a, '_>,
+    table: &'a MapTable<'a>,
+) {
+    for (key, entry) in table.iter() {
+        let path = path.append_key(&entry.reprs);
+        if INHERITABLE_PACKAGE_KEYS.contains(key) && parse_inherited(ctx, &path, entry) {
+            continue;
+        }
+        parse_package_field(ctx, state, &path, key, entry);
+    }
+
+    check_license_and_license_file(ctx, path, table);
+}
+
+fn parse_package_field<'a>(
+    ctx: &mut impl IdeCtx,
+    state: &mut State<'a>,
+    path: &map::Path<'a, '_>,
+    key: &str,
+    entry: &'a MapTableEntry<'a>,
+) {
+    match key {
+        "name" => {
+            if let Some(str) = expect_string_in_table(ctx, path, entry) {
+                check_package_name(ctx, path, &str);
+                state.package_name = Some(str);
+            }
+        }
+        "version" => {
+            if let Some(str) = expect_string_in_table(ctx, path, entry) {
+                state.package_version = Some(parse_package_version(ctx, str));
+            }
+        }
+        "edition" => {
+            if let Some(str) = expect_string_in_table(ctx, path, entry) {
+                state.edition = parse_edition(ctx, path, &str);
+            }
+        }
+        "rust-version" => {
+            if let Some(str) = expect_string_in_table(ctx, path, entry) {
+                state.rust_version = Some(parse_rust_version(ctx, str));
+            }
+        }
+        "keywords" => {
+            if let Some(array) = expect_string_array_in_table(ctx, path, entry) {
+                check_keywords(ctx, path, array);
+            }
+        }
+        "categories" => {
+            if let Some(array) = expect_string_array_in_table(ctx, path, entry) {
+                check_categories(ctx, path, array);
+            }
+        }
+        "authors" | "include" | "exclude" => {
+            expect_string_array_in_table(ctx, path, entry);
+        }
+        "publish" => match &entry.node {
+            MapNode::Array(_) => {
+                expect_string_array_in_table(ctx, path, entry);
+            }
+            _ => expect_either_in_table(ctx, path, entry, [Datatype::Bool, Datatype::Array]),
+        },
+        "readme" | "build" => {
+            expect_either_in_table(ctx, path, entry, [Datatype::String, Datatype::Bool])
+        }
+        "license" => {
+            if let Some(str) = expect_string_in_table(ctx, path, entry) {
+                check_license(ctx, path, &str);
+            }
+        }
+        "resolver" => {
+            if let Some(str) = expect_string_in_table(ctx, path, entry) {
+                parse_resolver(ctx, path, &str);
+            }
+        }
+        "description" | "documentation" | "homepage" | "repository" | "license-file" | "links"
+        | "workspace" | "default-run" => {
+            expect_string_in_table(ctx, path, entry);
+        }
+        "autolib" | "autobins" | "autoexamples" | "autotests" | "autobenches" => {
+            expect_bool_in_table(ctx, path, entry);
+        }
+        // Arbitrary data for external tools.
+        "metadata" => (),
+        _ => warn_unused(ctx, path, entry),
+    }
+}
+
+fn check_license_and_license_file(ctx: &mut impl IdeCtx, path: &map::Path, table: &MapTable) {
+    if let (Some(license), Some(license_file)) = (table.get("license"), table.get("license-file")) {
+        let path = path.append_key(&license.reprs);
+        let license_file_span = license_file.reprs.first().kind.span();
+        for repr in license.reprs.iter() {
+            ctx.warn(cargo::Warning::new(
+                map::context_lines(path.prev, [repr.parent]),
+                path.fmt_path(),
+                repr.repr_span(),
+                cargo::WarningKind::LicenseAndLicenseFile { license_file_span },
+            ));
+        }
+    }
+}
+
+/// Parse a field that is inherited from the workspace: `field = { workspace = true }`. Returns
+/// whether the entry is a table and was handled.
+fn parse_inherited(ctx: &mut impl IdeCtx, path: &map::Path, entry: &MapTableEntry) -> bool {
+    let MapNode::Table(table) = &entry.node else {
+        return false;
+    };
+
+    let mut workspace = None;
+    for (key, entry) in table.iter() {
+        let path = path.append_key(&entry.reprs);
+        match *key {
+            "workspace" => workspace = expect_bool_in_table(ctx, &path, entry),
+            _ => warn_unused(ctx, &path, entry),
+        }
+    }
+
+    match workspace {
+        Some(workspace) =>
+        {
+            #[allow(clippy::bool_comparison)]
+            if workspace.val.val == false {
+                ctx.error(cargo::Error::new(
+                    path.context_lines([workspace.repr.parent]),
+                    path.fmt_path(),
+                    workspace.span(),
+                    cargo::ErrorKind::InheritedWorkspaceIsFalse,
+                ));
+            }
+        }
+        // The wrong datatype has already been reported
+        None if table.get("workspace").is_some() => (),
+        None => {
+            for repr in entry.reprs.iter() {
+                ctx.error(cargo::Error::new(
+                    map::context_lines(path.prev, [repr.parent]),
+                    path.fmt_path(),
+                    repr.repr_span(),
+                    cargo::ErrorKind::InheritedWorkspaceMissing,
+                ));
+            }
+        }
+    }
+
+    true
+}
+
+/// Rust keywords, which can't be used as crate names.
+const KEYWORDS: [&str; 51] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while",
+];
+const STD_CRATES: [&str; 5] = ["alloc", "core", "proc_macro", "std", "test"];
+const MAX_PACKAGE_NAME_LEN: usize = 64;
+
+fn check_package_name(ctx: &mut impl IdeCtx, path: &map::Path, str: &StringAssignment) {
+    let name = str.val.text;
+    let lines = || path.context_lines([str.repr.parent]);
+    let error = |kind| cargo::Error::new(lines(), path.fmt_path(), str.val.lit_span, kind);
+    let warning = |kind| cargo::Warning::new(lines(), path.fmt_path(), str.val.lit_span, kind);
+
+    let mut chars = name.char_indices();
+    let Some((_, first)) = chars.next() else {
+        ctx.error(error(cargo::ErrorKind::EmptyPackageName));
+        return;
+    };
+
+    let mut valid = true;
+    if !(first.is_ascii_alphabetic() || first == '_') {
+        let span = str.val.text_range_span(0..first.len_utf8() as u32);
+        ctx.error(cargo::Error::new(
+            lines(),
+            path.fmt_path(),
+            span,
+            cargo::ErrorKind::InvalidPackageNameStart(first),
+        ));
+        valid = false;
+    }
+    for (i, c) in chars {
+        if !(c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            let i = i as u32;
+            let span = str.val.text_range_span(i..i + c.len_utf8() as u32);
+            ctx.error(cargo::Error::new(
+                lines(),
+                path.fmt_path(),
+                span,
+                cargo::ErrorKind::InvalidPackageNameChar(c),
+            ));
+            valid = false;
+        }
+    }
+    if !valid {
+        return;
+    }
+
+    if name.len() > MAX_PACKAGE_NAME_LEN {
+        ctx.warn(warning(cargo::WarningKind::PackageNameTooLong));
+    }
+    let fmt_name = || FmtStr::from_str(name);
+    if KEYWORDS.contains(&name) {
+        ctx.warn(warning(
+            cargo::WarningKind::PackageNameIsKeyword(fmt_name()),
+        ));
+    } else if STD_CRATES.contains(&name.replace('-', "_").as_str()) {
+        ctx.warn(warning(cargo::WarningKind::ReservedPackageName(fmt_name())));
+    }
+}
+
+fn parse_package_version<'a>(
+    ctx: &mut impl SemverCtx,
+    str: StringAssignment<'a>,
+) -> PackageVersion<'a> {
+    let version = match semver::parse_version(str.val.text, Pos::ZERO) {
+        Ok(v) => Some(v),
+        Err(mut e) => {
+            e.map_spans(|s| str.val.text_range_span(s.start.char..s.end.char));
+            ctx.error(e);
+            None
+        }
+    };
+    PackageVersion { str, version }
+}
+
+fn parse_edition(
+    ctx: &mut impl IdeCtx,
+    path: &map::Path,
+    str: &StringAssignment,
+) -> Option<Edition> {
+    let edition = Edition::parse(str.val.text);
+    if edition.is_none() {
+        ctx.error(cargo::Error::new(
+            map::context_lines(path.prev, [str.repr.parent]),
+            path.fmt_path(),
+            str.val.lit_span,
+            cargo::ErrorKind::UnknownEdition(FmtStr::from_str(str.val.text)),
+        ));
+    }
+    edition
+}
+
+fn parse_resolver(
+    ctx: &mut impl IdeCtx,
+    path: &map::Path,
+    str: &StringAssignment,
+) -> Option<Resolver> {
+    let resolver = Resolver::parse(str.val.text);
+    if resolver.is_none() {
+        ctx.error(cargo::Error::new(
+            path.context_lines([str.repr.parent]),
+            path.fmt_path(),
+            str.val.lit_span,
+            cargo::ErrorKind::UnknownResolver(FmtStr::from_str(str.val.text)),
+        ));
+    }
+    resolver
+}
+
+fn check_license(ctx: &mut impl IdeCtx, path: &map::Path, str: &StringAssignment) {
+    let parsed = spdx::parse_expression(str.val.text);
+    let lines = || path.context_lines([str.repr.parent]);
+    let map_span = |s: Span| str.val.text_range_span(s.start.char..s.end.char);
+    for e in parsed.errors {
+        let kind = cargo::ErrorKind::License(e.kind);
+        ctx.error(cargo::Error::new(
+            lines(),
+            path.fmt_path(),
+            map_span(e.span),
+            kind,
+        ));
+    }
+    for w in parsed.warnings {
+        let kind = cargo::WarningKind::License(w.kind);
+        ctx.warn(cargo::Warning::new(
+            lines(),
+            path.fmt_path(),
+            map_span(w.span),
+            kind,
+        ));
+    }
+}
+
+const MAX_KEYWORDS: usize = 5;
+const MAX_KEYWORD_LEN: usize = 20;
+const MAX_CATEGORIES: usize = 5;
+
+fn check_keywords(ctx: &mut impl IdeCtx, path: &map::Path, array: StringArray) {
+    for (n, &(i, keyword)) in array.items.iter().enumerate() {
+        let path = path.append_index(i);
+        let lines = || map::context_lines(path.prev, [array.parent]);
+        let warning = |kind| cargo::Warning::new(lines(), path.fmt_path(), keyword.lit_span, kind);
+
+        if n == MAX_KEYWORDS {
+            ctx.warn(warning(cargo::WarningKind::TooManyKeywords));
+        }
+
+        let text = keyword.text;
+        let valid_start = text.starts_with(|c: char| c.is_ascii_alphanumeric());
+        let valid_chars = (text.chars()).all(|c| c.is_ascii_alphanumeric() || "_-+".contains(c));
+        if !valid_start || !valid_chars {
+            let keyword = FmtStr::from_str(text);
+            ctx.warn(warning(cargo::WarningKind::InvalidKeyword(keyword)));
+        } else if text.len() > MAX_KEYWORD_LEN {
+            ctx.warn(warning(cargo::WarningKind::KeywordTooLong));
+        }
+    }
+}
+
+fn check_categories(ctx: &mut impl IdeCtx, path: &map::Path, array: StringArray) {
+    if let Some(&(i, category)) = array.items.get(MAX_CATEGORIES) {
+        let path = path.append_index(i);
+        ctx.warn(cargo::Warning::new(
+            map::context_lines(path.prev, [array.parent]),
+            path.fmt_path(),
+            category.lit_span,
+            cargo::WarningKind::TooManyCategories,
+        ));
+    }
+}
+
+fn parse_rust_version<'a>(ctx: &mut impl SemverCtx, str: StringAssignment<'a>) -> RustVersion<'a> {
+    let version = match semver::parse_partial_version(str.val.text, Pos::ZERO) {
+        Ok(v) => Some(v),
+        Err(mut e) => {
+            e.map_spans(|s| str.val.text_range_span(s.start.char..s.end.char));
+            ctx.error(e);
+            None
+        }
+    };
+    RustVersion { str, version }
+}
+
 fn parse_dependencies<'a>(
     ctx: &mut impl IdeCtx,
     state: &mut State<'a>,
@@ -531,6 +1173,7 @@ fn parse_dependencies<'a>(
                     package: None,
                     kind,
                     target,
+                    target_spec: None,
                     spec,
                     features,
                     optional: None,
@@ -610,11 +1253,19 @@ fn parse_version_req<'a>(
     ctx: &mut impl SemverCtx,
     str: StringAssignment<'a>,
 ) -> DependencyVersion<'a> {
-    let pos = str.val.text_span().start;
-    // TODO: spans from the semver parser may be incorrect when escape sequences are used for ascii characters
-    let req = match semver::parse_requirement(str.val.text, pos) {
-        Ok(v) => Some(v),
-        Err(e) => {
+    // Parse relative to the start of the text and map spans back to the source afterwards,
+    // since the text may contain escape sequences.
+    let req = match semver::parse_requirement(str.val.text, Pos::ZERO) {
+        Ok(mut v) => {
+            for mut w in semver::check_requirement(&v) {
+                w.map_spans(|s| str.val.text_range_span(s.start.char..s.end.char));
+                ctx.warn(w);
+            }
+            v.pos = str.val.text_pos(0);
+            Some(v)
+        }
+        Err(mut e) => {
+            e.map_spans(|s| str.val.text_range_span(s.start.char..s.end.char));
             ctx.error(e);
             None
         }
@@ -628,10 +1279,25 @@ fn parse_dependency_features<'a>(
     path: &map::Path,
     entry: &'a MapTableEntry<'a>,
 ) {
-    let Some(array) = expect_array_in_table(ctx, path, entry) else {
-        return;
-    };
-    let array = match array {
+    if let Some(array) = expect_string_array_in_table(ctx, path, entry) {
+        features.extend(array.items.into_iter().map(|(_, str)| str));
+    }
+}
+
+/// The valid strings of an inline array of strings.
+#[derive(Debug, PartialEq)]
+struct StringArray<'a> {
+    parent: ParentId,
+    /// The strings and their index in the array.
+    items: Vec<(usize, &'a StringVal<'a>)>,
+}
+
+fn expect_string_array_in_table<'a>(
+    ctx: &mut impl IdeCtx,
+    path: &map::Path,
+    entry: &'a MapTableEntry<'a>,
+) -> Option<StringArray<'a>> {
+    let array = match expect_array_in_table(ctx, path, entry)? {
         MapArray::Toplevel(_) => {
             for (i, repr) in entry.reprs.iter().enumerate() {
                 ctx.error(cargo::Error::new(
@@ -644,17 +1310,22 @@ fn parse_dependency_features<'a>(
                     },
                 ));
             }
-            return;
+            return None;
         }
         MapArray::Inline(i) => i,
     };
 
+    let mut items = Vec::with_capacity(array.len());
     for (i, entry) in array.iter().enumerate() {
         let path = path.append_index(i);
         if let Some(str) = expect_string_in_array(ctx, &path, array.parent, entry) {
-            features.push(str);
+            items.push((i, str));
         }
     }
+    Some(StringArray {
+        parent: array.parent,
+        items,
+    })
 }
 
 fn expect_table_in_table<'a>(
@@ -755,6 +1426,47 @@ fn expect_bool_in_table<'a>(
     }
 }
 
+fn expect_int_in_table<'a>(
+    ctx: &mut impl IdeCtx,
+    path: &map::Path,
+    entry: &'a MapTableEntry<'a>,
+) -> Option<IntAssignment<'a>> {
+    match &entry.node {
+        MapNode::Scalar(Scalar::Int(val)) => {
+            let repr = entry.reprs.first();
+            Some(IntAssignment { repr, val })
+        }
+        MapNode::Scalar(Scalar::Invalid(_, _)) => None,
+        n => {
+            for repr in entry.reprs.iter() {
+                ctx.error(wrong_datatype(path, n, repr, Datatype::Int));
+            }
+            None
+        }
+    }
+}
+
+/// Only checks the datatype, for values that can have one of two datatypes.
+fn expect_either_in_table(
+    ctx: &mut impl IdeCtx,
+    path: &map::Path,
+    entry: &MapTableEntry,
+    expected: [Datatype; 2],
+) {
+    let found = entry.node.datatype();
+    if found == Datatype::Invalid || expected.contains(&found) {
+        return;
+    }
+    for repr in entry.reprs.iter() {
+        ctx.error(cargo::Error::new(
+            map::context_lines(path.prev, [repr.parent]),
+            path.fmt_path(),
+            repr.repr_span(),
+            cargo::ErrorKind::WrongDatatypeEither { expected, found },
+        ));
+    }
+}
+
 fn warn_unused(ctx: &mut impl IdeCtx, path: &map::Path, entry: &MapTableEntry) {
     for repr in entry.reprs.iter() {
         ctx.warn(cargo::Warning::new(
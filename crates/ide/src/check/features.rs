@@ -0,0 +1,469 @@
+//! The `[features]` table and the feature graph it describes.
+
+use std::collections::{BTreeSet, HashMap};
+
+use common::{FmtStr, Span};
+use toml::map::{self, MapTable, MapTableEntry};
+use toml::parse::StringVal;
+
+use super::{expect_string_array_in_table, Dependency, DependencyKind};
+use crate::{cargo, IdeCtx};
+
+#[derive(Debug, Default, PartialEq)]
+pub struct FeatureGraph<'a> {
+    features: Vec<Feature<'a>>,
+    /// Optional dependencies that aren't dev-dependencies.
+    optional_deps: Vec<&'a str>,
+    /// Optional dependencies that are referenced with `dep:<name>`, so they have no implicit
+    /// feature.
+    hidden_deps: Vec<&'a str>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Feature<'a> {
+    pub name: &'a str,
+    pub members: Vec<FeatureMember<'a>>,
+    /// The entire toml entry
+    pub entry: &'a MapTableEntry<'a>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FeatureMember<'a> {
+    /// The index inside the array of the feature.
+    pub index: usize,
+    pub val: &'a StringVal<'a>,
+    pub kind: FeatureMemberKind<'a>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeatureMemberKind<'a> {
+    /// `"<feature>"`, either a feature or the implicit feature of an optional dependency.
+    Feature(&'a str),
+    /// `"dep:<dependency>"`
+    Dep(&'a str),
+    /// `"<dependency>/<feature>"` or `"<dependency>?/<feature>"`
+    DepFeature {
+        dep: &'a str,
+        feature: &'a str,
+        /// `?/`, only enables the feature if the dependency is enabled by something else.
+        weak: bool,
+    },
+}
+
+impl<'a> FeatureMemberKind<'a> {
+    pub fn parse(text: &'a str) -> Self {
+        if let Some(dep) = text.strip_prefix("dep:") {
+            return Self::Dep(dep);
+        }
+        match text.split_once('/') {
+            Some((dep, feature)) => match dep.strip_suffix('?') {
+                Some(dep) => Self::DepFeature {
+                    dep,
+                    feature,
+                    weak: true,
+                },
+                None => Self::DepFeature {
+                    dep,
+                    feature,
+                    weak: false,
+                },
+            },
+            None => Self::Feature(text),
+        }
+    }
+}
+
+/// Everything that is enabled by a set of features.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ResolvedFeatures<'a> {
+    /// Features, including implicit features of optional dependencies.
+    pub features: BTreeSet<&'a str>,
+    /// Optional dependencies.
+    pub deps: BTreeSet<&'a str>,
+    /// Features of dependencies, `(dependency, feature)`.
+    pub dep_features: BTreeSet<(&'a str, &'a str)>,
+}
+
+impl<'a> FeatureGraph<'a> {
+    pub fn features(&self) -> &[Feature<'a>] {
+        &self.features
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Feature<'a>> {
+        self.features.iter().find(|f| f.name == name)
+    }
+
+    /// Whether `name` is an optional dependency that has an implicit feature of the same name.
+    pub fn has_implicit_feature(&self, name: &str) -> bool {
+        self.optional_deps.contains(&name) && !self.hidden_deps.contains(&name)
+    }
+
+    /// Resolve everything that is enabled by `enabled`, unknown features are ignored. This doesn't
+    /// include `default`, unless it is passed explicitly.
+    pub fn resolve<'b>(&self, enabled: impl IntoIterator<Item = &'b str>) -> ResolvedFeatures<'a> {
+        let mut resolved = ResolvedFeatures::default();
+        let mut stack = Vec::new();
+        let mut weak = Vec::new();
+        for name in enabled {
+            self.enable(&mut resolved, &mut stack, name);
+        }
+
+        while let Some(feature) = stack.pop() {
+            for member in feature.members.iter() {
+                match member.kind {
+                    FeatureMemberKind::Feature(name) => {
+                        self.enable(&mut resolved, &mut stack, name);
+                    }
+                    FeatureMemberKind::Dep(dep) => {
+                        resolved.deps.insert(dep);
+                    }
+                    FeatureMemberKind::DepFeature {
+                        dep,
+                        feature,
+                        weak: false,
+                    } => {
+                        self.enable(&mut resolved, &mut stack, dep);
+                        if self.optional_deps.contains(&dep) {
+                            resolved.deps.insert(dep);
+                        }
+                        resolved.dep_features.insert((dep, feature));
+                    }
+                    FeatureMemberKind::DepFeature {
+                        dep,
+                        feature,
+                        weak: true,
+                    } => weak.push((dep, feature)),
+                }
+            }
+        }
+
+        for (dep, feature) in weak {
+            if !self.optional_deps.contains(&dep) || resolved.deps.contains(dep) {
+                resolved.dep_features.insert((dep, feature));
+            }
+        }
+
+        resolved
+    }
+
+    fn enable<'s>(
+        &'s self,
+        resolved: &mut ResolvedFeatures<'a>,
+        stack: &mut Vec<&'s Feature<'a>>,
+        name: &str,
+    ) {
+        if let Some(feature) = self.get(name) {
+            if resolved.features.insert(feature.name) {
+                stack.push(feature);
+            }
+        } else if let Some(dep) = self.optional_deps.iter().find(|d| **d == name) {
+            if !self.hidden_deps.contains(dep) {
+                resolved.features.insert(dep);
+                resolved.deps.insert(dep);
+            }
+        }
+    }
+}
+
+pub fn parse_features<'a>(
+    ctx: &mut impl IdeCtx,
+    dependencies: &[Dependency<'a>],
+    path: &map::Path<'a, '_>,
+    table: &'a MapTable<'a>,
+) -> FeatureGraph<'a> {
+    let mut features = Vec::new();
+    let mut parents = Vec::new();
+    for (&name, entry) in table.iter() {
+        let path = path.append_key(&entry.reprs);
+        check_feature_name(ctx, &path, name, entry);
+
+        let mut members = Vec::new();
+        let mut parent = None;
+        if let Some(array) = expect_string_array_in_table(ctx, &path, entry) {
+            members = (array.items.into_iter())
+                .map(|(index, val)| FeatureMember {
+                    index,
+                    val,
+                    kind: FeatureMemberKind::parse(val.text),
+                })
+                .collect();
+            parent = Some(array.parent);
+        }
+
+        features.push(Feature {
+            name,
+            members,
+            entry,
+        });
+        parents.push(parent);
+    }
+
+    let deps = DepKinds::new(dependencies);
+    let mut optional_deps = Vec::new();
+    for dep in dependencies {
+        if deps.get(dep.name) == DepKind::Optional && !optional_deps.contains(&dep.name) {
+            optional_deps.push(dep.name);
+        }
+    }
+
+    // The first `dep:<name>` member for each dependency
+    let mut dep_uses = HashMap::new();
+    for member in features.iter().flat_map(|f| f.members.iter()) {
+        if let FeatureMemberKind::Dep(dep) = member.kind {
+            dep_uses.entry(dep).or_insert(member.val.lit_span);
+        }
+    }
+
+    let graph = FeatureGraph {
+        features,
+        hidden_deps: (optional_deps.iter())
+            .filter(|d| dep_uses.contains_key(*d))
+            .copied()
+            .collect(),
+        optional_deps,
+    };
+
+    for (feature, parent) in graph.features.iter().zip(parents) {
+        let Some(parent) = parent else {
+            continue;
+        };
+        let path = path.append_key(&feature.entry.reprs);
+        for member in feature.members.iter() {
+            let path = path.append_index(member.index);
+            let val = member.val;
+            let error = |span, kind| {
+                let lines = map::context_lines(path.prev, [parent]);
+                cargo::Error::new(lines, path.fmt_path(), span, kind)
+            };
+
+            match member.kind {
+                FeatureMemberKind::Feature(name) => {
+                    if graph.get(name).is_some() || graph.has_implicit_feature(name) {
+                        continue;
+                    }
+
+                    let name_str = FmtStr::from_str(name);
+                    let kind = match (deps.get(name), dep_uses.get(name)) {
+                        (DepKind::Optional, Some(dep_span)) => {
+                            cargo::ErrorKind::FeatureImplicitDepHidden {
+                                dep: name_str,
+                                dep_span: *dep_span,
+                            }
+                        }
+                        // Optional dependencies without `dep:` have an implicit feature
+                        (DepKind::Optional, None) => unreachable!(),
+                        (DepKind::NotFound, _) => cargo::ErrorKind::UnknownFeature(name_str),
+                        (DepKind::Dev, _) => cargo::ErrorKind::FeatureDevDependency(name_str),
+                        (DepKind::Required, _) => cargo::ErrorKind::FeatureDepNotOptional(name_str),
+                    };
+                    ctx.error(error(val.lit_span, kind));
+                }
+                FeatureMemberKind::Dep(dep) => {
+                    let dep_str = FmtStr::from_str(dep);
+                    let kind = match deps.get(dep) {
+                        DepKind::Optional => continue,
+                        DepKind::NotFound => cargo::ErrorKind::FeatureDepNotFound(dep_str),
+                        DepKind::Dev => cargo::ErrorKind::FeatureDevDependency(dep_str),
+                        DepKind::Required => cargo::ErrorKind::FeatureDepNotOptional(dep_str),
+                    };
+                    let start = "dep:".len() as u32;
+                    let span = val.text_range_span(start..val.text.len() as u32);
+                    ctx.error(error(span, kind));
+                }
+                FeatureMemberKind::DepFeature { dep, weak, .. } => {
+                    let dep_str = FmtStr::from_str(dep);
+                    // Features of dev-dependencies can be enabled, they only apply when the
+                    // dev-dependencies are built.
+                    let kind = match deps.get(dep) {
+                        DepKind::Optional => continue,
+                        DepKind::Required | DepKind::Dev if !weak => continue,
+                        DepKind::Required | DepKind::Dev => {
+                            cargo::ErrorKind::FeatureWeakDepNotOptional(dep_str)
+                        }
+                        DepKind::NotFound => cargo::ErrorKind::FeatureDepNotFound(dep_str),
+                    };
+                    let span = val.text_range_span(0..dep.len() as u32);
+                    ctx.error(error(span, kind));
+                }
+            }
+        }
+    }
+
+    check_cycles(ctx, path, &graph);
+
+    graph
+}
+
+/// Ordered by precedence, if a dependency is declared multiple times.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum DepKind {
+    NotFound,
+    /// Only a dev-dependency.
+    Dev,
+    Required,
+    Optional,
+}
+
+/// The kinds of all dependencies by name. A dependency can be declared multiple times, for
+/// example for different targets, it is optional if any non-dev declaration is optional.
+struct DepKinds<'a> {
+    kinds: HashMap<&'a str, DepKind>,
+}
+
+impl<'a> DepKinds<'a> {
+    fn new(dependencies: &[Dependency<'a>]) -> Self {
+        let mut kinds = HashMap::new();
+        for dep in dependencies {
+            let kind = match dep.kind {
+                DependencyKind::Dev => DepKind::Dev,
+                _ if dep.optional.as_ref().is_some_and(|o| o.val.val) => DepKind::Optional,
+                _ => DepKind::Required,
+            };
+            let entry = kinds.entry(dep.name).or_insert(kind);
+            *entry = (*entry).max(kind);
+        }
+        Self { kinds }
+    }
+
+    fn get(&self, name: &str) -> DepKind {
+        self.kinds.get(name).copied().unwrap_or(DepKind::NotFound)
+    }
+}
+
+fn check_feature_name(ctx: &mut impl IdeCtx, path: &map::Path, name: &str, entry: &MapTableEntry) {
+    for repr in entry.reprs.iter() {
+        let ident = repr.key.repr_ident();
+        let lines = || map::context_lines(path.prev, [repr.parent]);
+
+        let mut chars = name.char_indices();
+        let Some((_, first)) = chars.next() else {
+            ctx.error(cargo::Error::new(
+                lines(),
+                path.fmt_path(),
+                ident.lit_span(),
+                cargo::ErrorKind::EmptyFeatureName,
+            ));
+            continue;
+        };
+
+        if !(first.is_alphanumeric() || first == '_') {
+            let span = ident.text_range_span(0..first.len_utf8() as u32);
+            ctx.error(cargo::Error::new(
+                lines(),
+                path.fmt_path(),
+                span,
+                cargo::ErrorKind::InvalidFeatureNameStart(first),
+            ));
+        }
+        for (i, c) in chars {
+            if !(c.is_alphanumeric() || matches!(c, '_' | '-' | '+' | '.')) {
+                let i = i as u32;
+                let span = ident.text_range_span(i..i + c.len_utf8() as u32);
+                ctx.error(cargo::Error::new(
+                    lines(),
+                    path.fmt_path(),
+                    span,
+                    cargo::ErrorKind::InvalidFeatureNameChar(c),
+                ));
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Visit {
+    New,
+    Active,
+    Done,
+}
+
+/// An edge of the feature graph, the feature and its member that enables the next feature.
+type Edge = (usize, Span);
+
+/// Report every cycle found by a depth-first search, each back edge closes one cycle.
+fn check_cycles(ctx: &mut impl IdeCtx, path: &map::Path, graph: &FeatureGraph) {
+    let indices: HashMap<&str, usize> = (graph.features.iter().enumerate())
+        .map(|(i, f)| (f.name, i))
+        .collect();
+    let edges: Vec<Vec<(usize, Span)>> = (graph.features.iter())
+        .map(|f| {
+            (f.members.iter())
+                .filter_map(|m| match m.kind {
+                    FeatureMemberKind::Feature(name) => Some((indices.get(name)?, m.val.lit_span)),
+                    _ => None,
+                })
+                .map(|(i, span)| (*i, span))
+                .collect()
+        })
+        .collect();
+
+    let mut visits = vec![Visit::New; graph.features.len()];
+    let mut stack = Vec::new();
+    let mut cycles = Vec::new();
+    for i in 0..graph.features.len() {
+        visit(i, &edges, &mut visits, &mut stack, &mut cycles);
+    }
+
+    for cycle in cycles {
+        let name = |i: usize| graph.features[i].name;
+        let mut chain = String::new();
+        for (i, _) in cycle.iter() {
+            chain.push_str(&format!("`{}` -> ", name(*i)));
+        }
+        chain.push_str(&format!("`{}`", name(cycle[0].0)));
+
+        let edge_labels = (cycle.iter().enumerate())
+            .map(|(n, (i, span))| {
+                let next = cycle[(n + 1) % cycle.len()].0;
+                let annotation = format!("`{}` enables `{}`", name(*i), name(next));
+                (*span, FmtStr::from_string(annotation))
+            })
+            .collect::<Box<[_]>>();
+
+        let feature = &graph.features[cycle[0].0];
+        let path = path.append_key(&feature.entry.reprs);
+        for repr in feature.entry.reprs.iter() {
+            ctx.error(cargo::Error::new(
+                map::context_lines(path.prev, [repr.parent]),
+                path.fmt_path(),
+                repr.key.repr_ident().lit_span(),
+                cargo::ErrorKind::FeatureCycle {
+                    cycle: FmtStr::from_string(chain.clone()),
+                    edges: edge_labels.clone(),
+                },
+            ));
+        }
+    }
+}
+
+fn visit(
+    i: usize,
+    edges: &[Vec<(usize, Span)>],
+    visits: &mut [Visit],
+    stack: &mut Vec<Edge>,
+    cycles: &mut Vec<Vec<Edge>>,
+) {
+    if visits[i] != Visit::New {
+        return;
+    }
+    visits[i] = Visit::Active;
+    for &(next, span) in edges[i].iter() {
+        match visits[next] {
+            Visit::New => {
+                stack.push((i, span));
+                visit(next, edges, visits, stack, cycles);
+                stack.pop();
+            }
+            Visit::Active => {
+                let start = (stack.iter())
+                    .position(|(n, _)| *n == next)
+                    .unwrap_or(stack.len());
+                let mut cycle = stack[start..].to_vec();
+                cycle.push((i, span));
+                cycles.push(cycle);
+            }
+            Visit::Done => (),
+        }
+    }
+    visits[i] = Visit::Done;
+}
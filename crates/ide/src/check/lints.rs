@@ -0,0 +1,263 @@
+//! The `[lints]` and `[workspace.lints]` tables.
+
+use common::FmtStr;
+use toml::map::{self, MapNode, MapTable, MapTableEntry, Scalar};
+use toml::util::Datatype;
+
+use super::{
+    expect_bool_in_table, expect_either_in_table, expect_int_in_table,
+    expect_string_array_in_table, expect_string_in_table, expect_table_in_table, warn_unused,
+    StringAssignment,
+};
+use crate::{cargo, IdeCtx};
+
+mod names;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LintLevel {
+    Allow,
+    Warn,
+    Deny,
+    Forbid,
+}
+
+impl LintLevel {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "allow" => Some(Self::Allow),
+            "warn" => Some(Self::Warn),
+            "deny" => Some(Self::Deny),
+            "forbid" => Some(Self::Forbid),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Lint<'a> {
+    /// `rust`, `clippy`, `rustdoc` or `cargo`
+    pub tool: &'a str,
+    pub name: &'a str,
+    /// `None` if the level is missing or invalid.
+    pub level: Option<LintLevel>,
+    pub priority: i64,
+    /// The entire toml entry
+    pub entry: &'a MapTableEntry<'a>,
+}
+
+/// Lint tools and the lints they know, `cargo` lints aren't checked.
+const TOOLS: [(&str, &[&str]); 4] = [
+    ("rust", &names::RUSTC),
+    ("clippy", &names::CLIPPY),
+    ("rustdoc", &names::RUSTDOC),
+    ("cargo", &[]),
+];
+
+/// Parse a `[lints]` table, or `[workspace.lints]` if `inheritable` is false.
+pub fn parse_lints<'a>(
+    ctx: &mut impl IdeCtx,
+    path: &map::Path<'a, '_>,
+    table: &'a MapTable<'a>,
+    inheritable: bool,
+) -> Vec<Lint<'a>> {
+    let mut lints = Vec::new();
+    if inheritable {
+        if let Some(entry) = table.get("workspace") {
+            let workspace_path = path.append_key(&entry.reprs);
+            if let Some(workspace) = expect_bool_in_table(ctx, &workspace_path, entry) {
+                #[allow(clippy::bool_comparison)]
+                if workspace.val.val == false {
+                    ctx.error(cargo::Error::new(
+                        path.context_lines([workspace.repr.parent]),
+                        path.fmt_path(),
+                        workspace.span(),
+                        cargo::ErrorKind::InheritedWorkspaceIsFalse,
+                    ));
+                }
+            }
+
+            for (key, entry) in table.iter() {
+                if *key == "workspace" {
+                    continue;
+                }
+                let path = path.append_key(&entry.reprs);
+                for repr in entry.reprs.iter() {
+                    ctx.error(cargo::Error::new(
+                        map::context_lines(path.prev, [repr.parent]),
+                        path.fmt_path(),
+                        repr.repr_span(),
+                        cargo::ErrorKind::LintsWorkspaceOverride,
+                    ));
+                }
+            }
+            return lints;
+        }
+    }
+
+    for (&tool, entry) in table.iter() {
+        let path = path.append_key(&entry.reprs);
+        let Some((_, known)) = TOOLS.iter().find(|(t, _)| *t == tool) else {
+            for repr in entry.reprs.iter() {
+                ctx.warn(cargo::Warning::new(
+                    map::context_lines(path.prev, [repr.parent]),
+                    path.fmt_path(),
+                    repr.repr_span(),
+                    cargo::WarningKind::UnknownLintTool(FmtStr::from_str(tool)),
+                ));
+            }
+            continue;
+        };
+        let Some(tool_table) = expect_table_in_table(ctx, &path, entry) else {
+            continue;
+        };
+
+        for (&name, entry) in tool_table.iter() {
+            let path = path.append_key(&entry.reprs);
+            check_lint_name(ctx, &path, entry, name, known);
+            let (level, priority) = parse_lint_level(ctx, &path, entry);
+            lints.push(Lint {
+                tool,
+                name,
+                level,
+                priority,
+                entry,
+            });
+        }
+    }
+    lints
+}
+
+fn check_lint_name(
+    ctx: &mut impl IdeCtx,
+    path: &map::Path,
+    entry: &MapTableEntry,
+    name: &str,
+    known: &[&'static str],
+) {
+    let repr = entry.reprs.first();
+    let lines = || map::context_lines(path.prev, [repr.parent]);
+    let span = repr.key.repr_ident().lit_span();
+
+    if let Some((prefix, name)) = name.split_once("::") {
+        let suggestion = FmtStr::from_string(format!("lints.{prefix}.{name}"));
+        ctx.error(cargo::Error::new(
+            lines(),
+            path.fmt_path(),
+            span,
+            cargo::ErrorKind::LintNameHasTool(suggestion),
+        ));
+        return;
+    }
+    if known.is_empty() {
+        return;
+    }
+
+    // rustc accepts dashes instead of underscores.
+    let normalized = name.replace('-', "_");
+    if known.binary_search(&normalized.as_str()).is_ok() {
+        return;
+    }
+    let suggestion = suggest(&normalized, known);
+    ctx.warn(cargo::Warning::new(
+        lines(),
+        path.fmt_path(),
+        span,
+        cargo::WarningKind::UnknownLint(suggestion),
+    ));
+}
+
+/// Returns the level and priority.
+fn parse_lint_level(
+    ctx: &mut impl IdeCtx,
+    path: &map::Path,
+    entry: &MapTableEntry,
+) -> (Option<LintLevel>, i64) {
+    match &entry.node {
+        MapNode::Table(table) => {
+            let mut level = None;
+            let mut priority = 0;
+            for (key, entry) in table.iter() {
+                let path = path.append_key(&entry.reprs);
+                match *key {
+                    "level" => {
+                        level = expect_string_in_table(ctx, &path, entry)
+                            .and_then(|str| check_level(ctx, &path, &str));
+                    }
+                    "priority" => {
+                        if let Some(int) = expect_int_in_table(ctx, &path, entry) {
+                            priority = int.val.val;
+                        }
+                    }
+                    // Only used by `unexpected_cfgs`.
+                    "check-cfg" => {
+                        expect_string_array_in_table(ctx, &path, entry);
+                    }
+                    _ => warn_unused(ctx, &path, entry),
+                }
+            }
+            if table.get("level").is_none() {
+                for repr in entry.reprs.iter() {
+                    ctx.error(cargo::Error::new(
+                        map::context_lines(path.prev, [repr.parent]),
+                        path.fmt_path(),
+                        repr.repr_span(),
+                        cargo::ErrorKind::MissingLintLevel,
+                    ));
+                }
+            }
+            (level, priority)
+        }
+        MapNode::Scalar(Scalar::String(_)) => {
+            let level = expect_string_in_table(ctx, path, entry)
+                .and_then(|str| check_level(ctx, path, &str));
+            (level, 0)
+        }
+        _ => {
+            expect_either_in_table(ctx, path, entry, [Datatype::String, Datatype::Table]);
+            (None, 0)
+        }
+    }
+}
+
+fn check_level(
+    ctx: &mut impl IdeCtx,
+    path: &map::Path,
+    str: &StringAssignment,
+) -> Option<LintLevel> {
+    let level = LintLevel::parse(str.val.text);
+    if level.is_none() {
+        ctx.error(cargo::Error::new(
+            path.context_lines([str.repr.parent]),
+            path.fmt_path(),
+            str.val.lit_span,
+            cargo::ErrorKind::UnknownLintLevel(FmtStr::from_str(str.val.text)),
+        ));
+    }
+    level
+}
+
+/// The most similar known lint name, if any is similar enough.
+fn suggest(name: &str, known: &[&'static str]) -> Option<&'static str> {
+    let max_distance = (name.len() / 3).max(1);
+    (known.iter())
+        .map(|k| (edit_distance(name, k), *k))
+        .filter(|(d, _)| *d <= max_distance)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, k)| k)
+}
+
+/// The levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + (ca != *cb) as usize;
+            current[j + 1] = substitution.min(prev[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut current);
+    }
+    prev[b.len()]
+}
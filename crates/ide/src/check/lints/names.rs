@@ -0,0 +1,1095 @@
+//! Names of the lints and lint groups known to rustc, clippy and rustdoc 1.95.0, generated from
+//! the output of `rustc -W help`, `clippy-driver -W help` and `rustdoc -W help`. The lists are
+//! sorted, so they can be binary searched.
+
+/// `[lints.rust]`
+pub const RUSTC: [&str; 252] = [
+    "aarch64_softfloat_neon",
+    "absolute_paths_not_starting_with_crate",
+    "ambiguous_associated_items",
+    "ambiguous_derive_helpers",
+    "ambiguous_glob_imported_traits",
+    "ambiguous_glob_imports",
+    "ambiguous_glob_reexports",
+    "ambiguous_import_visibilities",
+    "ambiguous_negative_literals",
+    "ambiguous_panic_imports",
+    "ambiguous_wide_pointer_comparisons",
+    "anonymous_parameters",
+    "arithmetic_overflow",
+    "array_into_iter",
+    "asm_sub_register",
+    "async_fn_in_trait",
+    "bad_asm_style",
+    "bare_trait_objects",
+    "binary_asm_labels",
+    "bindings_with_variant_name",
+    "boxed_slice_into_iter",
+    "break_with_label_and_loop",
+    "clashing_extern_declarations",
+    "closure_returning_async_block",
+    "coherence_leak_check",
+    "conflicting_repr_hints",
+    "confusable_idents",
+    "const_evaluatable_unchecked",
+    "const_item_interior_mutations",
+    "const_item_mutation",
+    "dangerous_implicit_autorefs",
+    "dangling_pointers_from_locals",
+    "dangling_pointers_from_temporaries",
+    "dead_code",
+    "default_overrides_default_fields",
+    "dependency_on_unit_never_type_fallback",
+    "deprecated",
+    "deprecated_in_future",
+    "deprecated_safe",
+    "deprecated_safe_2024",
+    "deprecated_where_clause_location",
+    "deref_into_dyn_supertrait",
+    "deref_nullptr",
+    "double_negations",
+    "drop_bounds",
+    "dropping_copy_types",
+    "dropping_references",
+    "duplicate_macro_attributes",
+    "dyn_drop",
+    "edition_2024_expr_fragment_specifier",
+    "elided_lifetimes_in_associated_constant",
+    "elided_lifetimes_in_paths",
+    "ellipsis_inclusive_range_patterns",
+    "enum_intrinsics_non_enums",
+    "explicit_builtin_cfgs_in_flags",
+    "explicit_outlives_requirements",
+    "exported_private_dependencies",
+    "ffi_unwind_calls",
+    "for_loops_over_fallibles",
+    "forbidden_lint_groups",
+    "forgetting_copy_types",
+    "forgetting_references",
+    "function_casts_as_integer",
+    "function_item_references",
+    "future_incompatible",
+    "fuzzy_provenance_casts",
+    "hidden_glob_reexports",
+    "if_let_rescope",
+    "ill_formed_attribute_input",
+    "impl_trait_overcaptures",
+    "impl_trait_redundant_captures",
+    "improper_ctypes",
+    "improper_ctypes_definitions",
+    "improper_gpu_kernel_arg",
+    "incomplete_features",
+    "incomplete_include",
+    "ineffective_unstable_trait_impl",
+    "inline_always_mismatching_target_features",
+    "inline_no_sanitize",
+    "integer_to_ptr_transmutes",
+    "internal_eq_trait_method_impls",
+    "internal_features",
+    "invalid_atomic_ordering",
+    "invalid_doc_attributes",
+    "invalid_from_utf8",
+    "invalid_from_utf8_unchecked",
+    "invalid_macro_export_arguments",
+    "invalid_nan_comparisons",
+    "invalid_null_arguments",
+    "invalid_reference_casting",
+    "invalid_type_param_default",
+    "invalid_value",
+    "irrefutable_let_patterns",
+    "keyword_idents",
+    "keyword_idents_2018",
+    "keyword_idents_2024",
+    "large_assignments",
+    "late_bound_lifetime_arguments",
+    "legacy_derive_helpers",
+    "let_underscore",
+    "let_underscore_drop",
+    "let_underscore_lock",
+    "linker_messages",
+    "long_running_const_eval",
+    "lossy_provenance_casts",
+    "macro_expanded_macro_exports_accessed_by_absolute_paths",
+    "macro_use_extern_crate",
+    "malformed_diagnostic_attributes",
+    "malformed_diagnostic_format_literals",
+    "map_unit_fn",
+    "meta_variable_misuse",
+    "mismatched_lifetime_syntaxes",
+    "misplaced_diagnostic_attributes",
+    "missing_abi",
+    "missing_copy_implementations",
+    "missing_debug_implementations",
+    "missing_docs",
+    "missing_gpu_kernel_export_name",
+    "missing_unsafe_on_extern",
+    "mixed_script_confusables",
+    "multiple_supertrait_upcastable",
+    "must_not_suspend",
+    "mutable_transmutes",
+    "named_arguments_used_positionally",
+    "named_asm_labels",
+    "never_type_fallback_flowing_into_unsafe",
+    "no_mangle_const_items",
+    "no_mangle_generic_items",
+    "non_ascii_idents",
+    "non_camel_case_types",
+    "non_contiguous_range_endpoints",
+    "non_exhaustive_omitted_patterns",
+    "non_fmt_panics",
+    "non_local_definitions",
+    "non_shorthand_field_patterns",
+    "non_snake_case",
+    "non_upper_case_globals",
+    "nonstandard_style",
+    "noop_method_call",
+    "opaque_hidden_inferred_bound",
+    "out_of_scope_macro_calls",
+    "overflowing_literals",
+    "overlapping_range_endpoints",
+    "path_statements",
+    "patterns_in_fns_without_body",
+    "private_bounds",
+    "private_interfaces",
+    "proc_macro_derive_resolution_fallback",
+    "ptr_to_integer_transmute_in_consts",
+    "pub_use_of_private_extern_crate",
+    "redundant_imports",
+    "redundant_lifetimes",
+    "redundant_semicolons",
+    "refining_impl_trait",
+    "refining_impl_trait_internal",
+    "refining_impl_trait_reachable",
+    "renamed_and_removed_lints",
+    "repr_c_enums_larger_than_int",
+    "repr_transparent_non_zst_fields",
+    "resolving_to_items_shadowing_supertrait_items",
+    "rtsan_nonblocking_async",
+    "rust_2018_compatibility",
+    "rust_2018_idioms",
+    "rust_2021_compatibility",
+    "rust_2021_incompatible_closure_captures",
+    "rust_2021_incompatible_or_patterns",
+    "rust_2021_prefixes_incompatible_syntax",
+    "rust_2021_prelude_collisions",
+    "rust_2024_compatibility",
+    "rust_2024_guarded_string_incompatible_syntax",
+    "rust_2024_incompatible_pat",
+    "rust_2024_prelude_collisions",
+    "self_constructor_from_outer_item",
+    "semicolon_in_expressions_from_macros",
+    "shadowing_supertrait_items",
+    "single_use_lifetimes",
+    "soft_unstable",
+    "special_module_name",
+    "stable_features",
+    "static_mut_refs",
+    "suspicious_double_ref_op",
+    "tail_expr_drop_order",
+    "test_unstable_lint",
+    "text_direction_codepoint_in_comment",
+    "text_direction_codepoint_in_literal",
+    "trivial_bounds",
+    "trivial_casts",
+    "trivial_numeric_casts",
+    "type_alias_bounds",
+    "tyvar_behind_raw_pointer",
+    "uncommon_codepoints",
+    "unconditional_panic",
+    "unconditional_recursion",
+    "uncovered_param_in_projection",
+    "undropped_manually_drops",
+    "unexpected_cfgs",
+    "unfulfilled_lint_expectations",
+    "ungated_async_fn_track_caller",
+    "uninhabited_static",
+    "unit_bindings",
+    "unknown_crate_types",
+    "unknown_diagnostic_attributes",
+    "unknown_lints",
+    "unknown_or_malformed_diagnostic_attributes",
+    "unnameable_test_items",
+    "unnameable_types",
+    "unnecessary_transmutes",
+    "unpredictable_function_pointer_comparisons",
+    "unqualified_local_imports",
+    "unreachable_cfg_select_predicates",
+    "unreachable_code",
+    "unreachable_patterns",
+    "unreachable_pub",
+    "unsafe_attr_outside_unsafe",
+    "unsafe_code",
+    "unsafe_op_in_unsafe_fn",
+    "unstable_features",
+    "unstable_name_collisions",
+    "unstable_syntax_pre_expansion",
+    "unsupported_calling_conventions",
+    "unused",
+    "unused_allocation",
+    "unused_assignments",
+    "unused_associated_type_bounds",
+    "unused_attributes",
+    "unused_braces",
+    "unused_comparisons",
+    "unused_crate_dependencies",
+    "unused_doc_comments",
+    "unused_extern_crates",
+    "unused_features",
+    "unused_import_braces",
+    "unused_imports",
+    "unused_labels",
+    "unused_lifetimes",
+    "unused_macro_rules",
+    "unused_macros",
+    "unused_must_use",
+    "unused_mut",
+    "unused_parens",
+    "unused_qualifications",
+    "unused_results",
+    "unused_unsafe",
+    "unused_variables",
+    "unused_visibilities",
+    "useless_deprecated",
+    "useless_ptr_null_checks",
+    "uses_power_alignment",
+    "varargs_without_pattern",
+    "variant_size_differences",
+    "warnings",
+    "while_true",
+];
+
+/// `[lints.clippy]`
+pub const CLIPPY: [&str; 816] = [
+    "absolute_paths",
+    "absurd_extreme_comparisons",
+    "all",
+    "alloc_instead_of_core",
+    "allow_attributes",
+    "allow_attributes_without_reason",
+    "almost_complete_range",
+    "almost_swapped",
+    "approx_constant",
+    "arbitrary_source_item_ordering",
+    "arc_with_non_send_sync",
+    "arithmetic_side_effects",
+    "as_conversions",
+    "as_pointer_underscore",
+    "as_ptr_cast_mut",
+    "as_underscore",
+    "assertions_on_constants",
+    "assertions_on_result_states",
+    "assign_op_pattern",
+    "assigning_clones",
+    "async_yields_async",
+    "await_holding_invalid_type",
+    "await_holding_lock",
+    "await_holding_refcell_ref",
+    "bad_bit_mask",
+    "big_endian_bytes",
+    "bind_instead_of_map",
+    "blanket_clippy_restriction_lints",
+    "blocks_in_conditions",
+    "bool_assert_comparison",
+    "bool_comparison",
+    "bool_to_int_with_if",
+    "borrow_as_ptr",
+    "borrow_deref_ref",
+    "borrow_interior_mutable_const",
+    "borrowed_box",
+    "box_collection",
+    "box_default",
+    "boxed_local",
+    "branches_sharing_code",
+    "builtin_type_shadow",
+    "byte_char_slices",
+    "bytes_count_to_len",
+    "bytes_nth",
+    "cargo",
+    "cargo_common_metadata",
+    "case_sensitive_file_extension_comparisons",
+    "cast_abs_to_unsigned",
+    "cast_enum_constructor",
+    "cast_enum_truncation",
+    "cast_lossless",
+    "cast_nan_to_int",
+    "cast_possible_truncation",
+    "cast_possible_wrap",
+    "cast_precision_loss",
+    "cast_ptr_alignment",
+    "cast_sign_loss",
+    "cast_slice_different_sizes",
+    "cast_slice_from_raw_parts",
+    "cfg_not_test",
+    "char_indices_as_byte_indices",
+    "char_lit_as_u8",
+    "chars_last_cmp",
+    "chars_next_cmp",
+    "checked_conversions",
+    "clear_with_drain",
+    "clone_on_copy",
+    "clone_on_ref_ptr",
+    "cloned_instead_of_copied",
+    "cloned_ref_to_slice_refs",
+    "cmp_null",
+    "cmp_owned",
+    "coerce_container_to_any",
+    "cognitive_complexity",
+    "collapsible_else_if",
+    "collapsible_if",
+    "collapsible_match",
+    "collapsible_str_replace",
+    "collection_is_never_read",
+    "comparison_chain",
+    "comparison_to_empty",
+    "complexity",
+    "confusing_method_to_numeric_cast",
+    "const_is_empty",
+    "copy_iterator",
+    "correctness",
+    "crate_in_macro_def",
+    "create_dir",
+    "crosspointer_transmute",
+    "dbg_macro",
+    "debug_assert_with_mut_call",
+    "decimal_bitwise_operands",
+    "decimal_literal_representation",
+    "declare_interior_mutable_const",
+    "default_constructed_unit_structs",
+    "default_instead_of_iter_empty",
+    "default_numeric_fallback",
+    "default_trait_access",
+    "default_union_representation",
+    "deprecated_cfg_attr",
+    "deprecated_clippy_cfg_attr",
+    "deprecated_semver",
+    "deref_addrof",
+    "deref_by_slicing",
+    "derivable_impls",
+    "derive_ord_xor_partial_ord",
+    "derive_partial_eq_without_eq",
+    "derived_hash_with_manual_eq",
+    "disallowed_fields",
+    "disallowed_macros",
+    "disallowed_methods",
+    "disallowed_names",
+    "disallowed_script_idents",
+    "disallowed_types",
+    "diverging_sub_expression",
+    "doc_broken_link",
+    "doc_comment_double_space_linebreaks",
+    "doc_include_without_cfg",
+    "doc_lazy_continuation",
+    "doc_link_code",
+    "doc_link_with_quotes",
+    "doc_markdown",
+    "doc_nested_refdefs",
+    "doc_overindented_list_items",
+    "doc_paragraphs_missing_punctuation",
+    "doc_suspicious_footnotes",
+    "double_comparisons",
+    "double_ended_iterator_last",
+    "double_must_use",
+    "double_parens",
+    "drain_collect",
+    "drop_non_drop",
+    "duplicate_mod",
+    "duplicate_underscore_argument",
+    "duplicated_attributes",
+    "duration_suboptimal_units",
+    "duration_subsec",
+    "eager_transmute",
+    "elidable_lifetime_names",
+    "else_if_without_else",
+    "empty_docs",
+    "empty_drop",
+    "empty_enum_variants_with_brackets",
+    "empty_enums",
+    "empty_line_after_doc_comments",
+    "empty_line_after_outer_attr",
+    "empty_loop",
+    "empty_structs_with_brackets",
+    "enum_clike_unportable_variant",
+    "enum_glob_use",
+    "enum_variant_names",
+    "eq_op",
+    "equatable_if_let",
+    "erasing_op",
+    "err_expect",
+    "error_impl_error",
+    "excessive_nesting",
+    "excessive_precision",
+    "exhaustive_enums",
+    "exhaustive_structs",
+    "exit",
+    "expect_fun_call",
+    "expect_used",
+    "expl_impl_clone_on_copy",
+    "explicit_auto_deref",
+    "explicit_counter_loop",
+    "explicit_deref_methods",
+    "explicit_into_iter_loop",
+    "explicit_iter_loop",
+    "explicit_write",
+    "extend_with_drain",
+    "extra_unused_lifetimes",
+    "extra_unused_type_parameters",
+    "fallible_impl_from",
+    "field_reassign_with_default",
+    "field_scoped_visibility_modifiers",
+    "filetype_is_file",
+    "filter_map_bool_then",
+    "filter_map_identity",
+    "filter_map_next",
+    "filter_next",
+    "flat_map_identity",
+    "flat_map_option",
+    "float_arithmetic",
+    "float_cmp",
+    "float_cmp_const",
+    "float_equality_without_abs",
+    "fn_params_excessive_bools",
+    "fn_to_numeric_cast",
+    "fn_to_numeric_cast_any",
+    "fn_to_numeric_cast_with_truncation",
+    "for_kv_map",
+    "forget_non_drop",
+    "format_collect",
+    "format_in_format_args",
+    "format_push_string",
+    "four_forward_slashes",
+    "from_iter_instead_of_collect",
+    "from_over_into",
+    "from_raw_with_void_ptr",
+    "from_str_radix_10",
+    "future_not_send",
+    "get_first",
+    "get_last_with_len",
+    "get_unwrap",
+    "host_endian_bytes",
+    "identity_op",
+    "if_let_mutex",
+    "if_not_else",
+    "if_same_then_else",
+    "if_then_some_else_none",
+    "ifs_same_cond",
+    "ignore_without_reason",
+    "ignored_unit_patterns",
+    "impl_hash_borrow_with_str_and_bytes",
+    "impl_trait_in_params",
+    "implicit_clone",
+    "implicit_hasher",
+    "implicit_return",
+    "implicit_saturating_add",
+    "implicit_saturating_sub",
+    "implied_bounds_in_impls",
+    "impossible_comparisons",
+    "imprecise_flops",
+    "incompatible_msrv",
+    "inconsistent_digit_grouping",
+    "inconsistent_struct_constructor",
+    "index_refutable_slice",
+    "indexing_slicing",
+    "ineffective_bit_mask",
+    "ineffective_open_options",
+    "inefficient_to_string",
+    "infallible_destructuring_match",
+    "infallible_try_from",
+    "infinite_iter",
+    "infinite_loop",
+    "inherent_to_string",
+    "inherent_to_string_shadow_display",
+    "init_numbered_fields",
+    "inline_always",
+    "inline_asm_x86_att_syntax",
+    "inline_asm_x86_intel_syntax",
+    "inline_fn_without_body",
+    "inspect_for_each",
+    "int_plus_one",
+    "integer_division",
+    "integer_division_remainder_used",
+    "into_iter_on_ref",
+    "into_iter_without_iter",
+    "invalid_regex",
+    "invalid_upcast_comparisons",
+    "inverted_saturating_sub",
+    "invisible_characters",
+    "io_other_error",
+    "ip_constant",
+    "is_digit_ascii_radix",
+    "items_after_statements",
+    "items_after_test_module",
+    "iter_cloned_collect",
+    "iter_count",
+    "iter_filter_is_ok",
+    "iter_filter_is_some",
+    "iter_kv_map",
+    "iter_next_loop",
+    "iter_next_slice",
+    "iter_not_returning_iterator",
+    "iter_nth",
+    "iter_nth_zero",
+    "iter_on_empty_collections",
+    "iter_on_single_items",
+    "iter_out_of_bounds",
+    "iter_over_hash_type",
+    "iter_overeager_cloned",
+    "iter_skip_next",
+    "iter_skip_zero",
+    "iter_with_drain",
+    "iter_without_into_iter",
+    "iterator_step_by_zero",
+    "join_absolute_paths",
+    "just_underscores_and_digits",
+    "large_const_arrays",
+    "large_digit_groups",
+    "large_enum_variant",
+    "large_futures",
+    "large_include_file",
+    "large_stack_arrays",
+    "large_stack_frames",
+    "large_types_passed_by_value",
+    "legacy_numeric_constants",
+    "len_without_is_empty",
+    "len_zero",
+    "let_and_return",
+    "let_underscore_future",
+    "let_underscore_lock",
+    "let_underscore_must_use",
+    "let_underscore_untyped",
+    "let_unit_value",
+    "let_with_type_underscore",
+    "lines_filter_map_ok",
+    "linkedlist",
+    "lint_groups_priority",
+    "literal_string_with_formatting_args",
+    "little_endian_bytes",
+    "lossy_float_literal",
+    "macro_metavars_in_unsafe",
+    "macro_use_imports",
+    "main_recursion",
+    "manual_abs_diff",
+    "manual_assert",
+    "manual_async_fn",
+    "manual_bits",
+    "manual_c_str_literals",
+    "manual_checked_ops",
+    "manual_clamp",
+    "manual_contains",
+    "manual_dangling_ptr",
+    "manual_div_ceil",
+    "manual_filter",
+    "manual_filter_map",
+    "manual_find",
+    "manual_find_map",
+    "manual_flatten",
+    "manual_hash_one",
+    "manual_ignore_case_cmp",
+    "manual_ilog2",
+    "manual_inspect",
+    "manual_instant_elapsed",
+    "manual_is_ascii_check",
+    "manual_is_finite",
+    "manual_is_infinite",
+    "manual_is_multiple_of",
+    "manual_is_power_of_two",
+    "manual_is_variant_and",
+    "manual_let_else",
+    "manual_main_separator_str",
+    "manual_map",
+    "manual_memcpy",
+    "manual_midpoint",
+    "manual_next_back",
+    "manual_non_exhaustive",
+    "manual_ok_err",
+    "manual_ok_or",
+    "manual_option_as_slice",
+    "manual_pattern_char_comparison",
+    "manual_range_contains",
+    "manual_range_patterns",
+    "manual_rem_euclid",
+    "manual_repeat_n",
+    "manual_retain",
+    "manual_rotate",
+    "manual_saturating_arithmetic",
+    "manual_slice_fill",
+    "manual_slice_size_calculation",
+    "manual_split_once",
+    "manual_str_repeat",
+    "manual_string_new",
+    "manual_strip",
+    "manual_swap",
+    "manual_take",
+    "manual_try_fold",
+    "manual_unwrap_or",
+    "manual_unwrap_or_default",
+    "manual_while_let_some",
+    "many_single_char_names",
+    "map_all_any_identity",
+    "map_clone",
+    "map_collect_result_unit",
+    "map_entry",
+    "map_err_ignore",
+    "map_flatten",
+    "map_identity",
+    "map_unwrap_or",
+    "map_with_unused_argument_over_ranges",
+    "match_as_ref",
+    "match_bool",
+    "match_like_matches_macro",
+    "match_overlapping_arm",
+    "match_ref_pats",
+    "match_result_ok",
+    "match_same_arms",
+    "match_single_binding",
+    "match_str_case_mismatch",
+    "match_wild_err_arm",
+    "match_wildcard_for_single_variants",
+    "maybe_infinite_iter",
+    "mem_forget",
+    "mem_replace_option_with_none",
+    "mem_replace_option_with_some",
+    "mem_replace_with_default",
+    "mem_replace_with_uninit",
+    "min_ident_chars",
+    "min_max",
+    "mismatching_type_param_order",
+    "misnamed_getters",
+    "misrefactored_assign_op",
+    "missing_assert_message",
+    "missing_asserts_for_indexing",
+    "missing_const_for_fn",
+    "missing_const_for_thread_local",
+    "missing_docs_in_private_items",
+    "missing_enforced_import_renames",
+    "missing_errors_doc",
+    "missing_fields_in_debug",
+    "missing_inline_in_public_items",
+    "missing_panics_doc",
+    "missing_safety_doc",
+    "missing_spin_loop",
+    "missing_trait_methods",
+    "missing_transmute_annotations",
+    "mistyped_literal_suffixes",
+    "mixed_attributes_style",
+    "mixed_case_hex_literals",
+    "mixed_read_write_in_expression",
+    "mod_module_files",
+    "module_inception",
+    "module_name_repetitions",
+    "modulo_arithmetic",
+    "modulo_one",
+    "multi_assignments",
+    "multiple_bound_locations",
+    "multiple_crate_versions",
+    "multiple_inherent_impl",
+    "multiple_unsafe_ops_per_block",
+    "must_use_candidate",
+    "must_use_unit",
+    "mut_from_ref",
+    "mut_mut",
+    "mut_mutex_lock",
+    "mut_range_bound",
+    "mutable_key_type",
+    "mutex_atomic",
+    "mutex_integer",
+    "naive_bytecount",
+    "needless_arbitrary_self_type",
+    "needless_as_bytes",
+    "needless_bitwise_bool",
+    "needless_bool",
+    "needless_bool_assign",
+    "needless_borrow",
+    "needless_borrowed_reference",
+    "needless_borrows_for_generic_args",
+    "needless_character_iteration",
+    "needless_collect",
+    "needless_continue",
+    "needless_doctest_main",
+    "needless_else",
+    "needless_for_each",
+    "needless_ifs",
+    "needless_late_init",
+    "needless_lifetimes",
+    "needless_match",
+    "needless_maybe_sized",
+    "needless_option_as_deref",
+    "needless_option_take",
+    "needless_parens_on_range_literals",
+    "needless_pass_by_ref_mut",
+    "needless_pass_by_value",
+    "needless_pub_self",
+    "needless_question_mark",
+    "needless_range_loop",
+    "needless_raw_string_hashes",
+    "needless_raw_strings",
+    "needless_return",
+    "needless_return_with_question_mark",
+    "needless_splitn",
+    "needless_type_cast",
+    "needless_update",
+    "neg_cmp_op_on_partial_ord",
+    "neg_multiply",
+    "negative_feature_names",
+    "never_loop",
+    "new_ret_no_self",
+    "new_without_default",
+    "no_effect",
+    "no_effect_replace",
+    "no_effect_underscore_binding",
+    "no_mangle_with_rust_abi",
+    "non_ascii_literal",
+    "non_canonical_clone_impl",
+    "non_canonical_partial_ord_impl",
+    "non_minimal_cfg",
+    "non_octal_unix_permissions",
+    "non_send_fields_in_send_ty",
+    "non_std_lazy_statics",
+    "non_zero_suggestions",
+    "nonminimal_bool",
+    "nonsensical_open_options",
+    "nonstandard_macro_braces",
+    "not_unsafe_ptr_arg_deref",
+    "nursery",
+    "obfuscated_if_else",
+    "octal_escapes",
+    "ok_expect",
+    "only_used_in_recursion",
+    "op_ref",
+    "option_as_ref_cloned",
+    "option_as_ref_deref",
+    "option_env_unwrap",
+    "option_filter_map",
+    "option_if_let_else",
+    "option_map_or_none",
+    "option_map_unit_fn",
+    "option_option",
+    "or_fun_call",
+    "or_then_unwrap",
+    "out_of_bounds_indexing",
+    "overly_complex_bool_expr",
+    "owned_cow",
+    "panic",
+    "panic_in_result_fn",
+    "panicking_overflow_checks",
+    "panicking_unwrap",
+    "partial_pub_fields",
+    "partialeq_ne_impl",
+    "partialeq_to_none",
+    "path_buf_push_overwrite",
+    "path_ends_with_ext",
+    "pathbuf_init_then_push",
+    "pattern_type_mismatch",
+    "pedantic",
+    "perf",
+    "permissions_set_readonly_false",
+    "pointer_format",
+    "pointers_in_nomem_asm_block",
+    "possible_missing_comma",
+    "possible_missing_else",
+    "precedence",
+    "precedence_bits",
+    "print_in_format_impl",
+    "print_literal",
+    "print_stderr",
+    "print_stdout",
+    "print_with_newline",
+    "println_empty_string",
+    "ptr_arg",
+    "ptr_as_ptr",
+    "ptr_cast_constness",
+    "ptr_eq",
+    "ptr_offset_by_literal",
+    "ptr_offset_with_cast",
+    "pub_underscore_fields",
+    "pub_use",
+    "pub_with_shorthand",
+    "pub_without_shorthand",
+    "question_mark",
+    "question_mark_used",
+    "range_minus_one",
+    "range_plus_one",
+    "range_zip_with_len",
+    "rc_buffer",
+    "rc_clone_in_vec_init",
+    "rc_mutex",
+    "read_line_without_trim",
+    "read_zero_byte_vec",
+    "readonly_write_lock",
+    "recursive_format_impl",
+    "redundant_allocation",
+    "redundant_as_str",
+    "redundant_async_block",
+    "redundant_at_rest_pattern",
+    "redundant_clone",
+    "redundant_closure",
+    "redundant_closure_call",
+    "redundant_closure_for_method_calls",
+    "redundant_comparisons",
+    "redundant_else",
+    "redundant_feature_names",
+    "redundant_field_names",
+    "redundant_guards",
+    "redundant_iter_cloned",
+    "redundant_locals",
+    "redundant_pattern",
+    "redundant_pattern_matching",
+    "redundant_pub_crate",
+    "redundant_slicing",
+    "redundant_static_lifetimes",
+    "redundant_test_prefix",
+    "redundant_type_annotations",
+    "ref_as_ptr",
+    "ref_binding_to_reference",
+    "ref_option",
+    "ref_option_ref",
+    "ref_patterns",
+    "regex_creation_in_loops",
+    "renamed_function_params",
+    "repeat_once",
+    "repeat_vec_with_capacity",
+    "replace_box",
+    "repr_packed_without_abi",
+    "reserve_after_initialization",
+    "rest_pat_in_fully_bound_structs",
+    "restriction",
+    "result_filter_map",
+    "result_large_err",
+    "result_map_or_into_option",
+    "result_map_unit_fn",
+    "result_unit_err",
+    "return_and_then",
+    "return_self_not_must_use",
+    "reversed_empty_ranges",
+    "same_functions_in_if_condition",
+    "same_item_push",
+    "same_length_and_capacity",
+    "same_name_method",
+    "search_is_some",
+    "seek_from_current",
+    "seek_to_start_instead_of_rewind",
+    "self_assignment",
+    "self_named_constructors",
+    "self_named_module_files",
+    "self_only_used_in_recursion",
+    "semicolon_if_nothing_returned",
+    "semicolon_inside_block",
+    "semicolon_outside_block",
+    "separated_literal_suffix",
+    "serde_api_misuse",
+    "set_contains_or_insert",
+    "shadow_reuse",
+    "shadow_same",
+    "shadow_unrelated",
+    "short_circuit_statement",
+    "should_implement_trait",
+    "should_panic_without_expect",
+    "significant_drop_in_scrutinee",
+    "significant_drop_tightening",
+    "similar_names",
+    "single_call_fn",
+    "single_char_add_str",
+    "single_char_lifetime_names",
+    "single_char_pattern",
+    "single_component_path_imports",
+    "single_element_loop",
+    "single_match",
+    "single_match_else",
+    "single_option_map",
+    "single_range_in_vec_init",
+    "size_of_in_element_count",
+    "size_of_ref",
+    "skip_while_next",
+    "sliced_string_as_bytes",
+    "slow_vector_initialization",
+    "stable_sort_primitive",
+    "std_instead_of_alloc",
+    "std_instead_of_core",
+    "str_split_at_newline",
+    "str_to_string",
+    "string_add",
+    "string_add_assign",
+    "string_extend_chars",
+    "string_from_utf8_as_bytes",
+    "string_lit_as_bytes",
+    "string_lit_chars_any",
+    "string_slice",
+    "strlen_on_c_strings",
+    "struct_excessive_bools",
+    "struct_field_names",
+    "style",
+    "suboptimal_flops",
+    "suspicious",
+    "suspicious_arithmetic_impl",
+    "suspicious_assignment_formatting",
+    "suspicious_command_arg_space",
+    "suspicious_doc_comments",
+    "suspicious_else_formatting",
+    "suspicious_map",
+    "suspicious_op_assign_impl",
+    "suspicious_open_options",
+    "suspicious_operation_groupings",
+    "suspicious_splitn",
+    "suspicious_to_owned",
+    "suspicious_unary_op_formatting",
+    "suspicious_xor_used_as_pow",
+    "swap_ptr_to_ref",
+    "swap_with_temporary",
+    "tabs_in_doc_comments",
+    "temporary_assignment",
+    "test_attr_in_doctest",
+    "tests_outside_test_module",
+    "to_digit_is_some",
+    "to_string_in_format_args",
+    "to_string_trait_impl",
+    "todo",
+    "too_long_first_doc_paragraph",
+    "too_many_arguments",
+    "too_many_lines",
+    "toplevel_ref_arg",
+    "trailing_empty_array",
+    "trait_duplication_in_bounds",
+    "transmute_bytes_to_str",
+    "transmute_int_to_bool",
+    "transmute_int_to_non_zero",
+    "transmute_null_to_fn",
+    "transmute_ptr_to_ptr",
+    "transmute_ptr_to_ref",
+    "transmute_undefined_repr",
+    "transmutes_expressible_as_ptr_casts",
+    "transmuting_null",
+    "trim_split_whitespace",
+    "trivial_regex",
+    "trivially_copy_pass_by_ref",
+    "try_err",
+    "tuple_array_conversions",
+    "type_complexity",
+    "type_id_on_box",
+    "type_repetition_in_bounds",
+    "unbuffered_bytes",
+    "unchecked_time_subtraction",
+    "unconditional_recursion",
+    "undocumented_unsafe_blocks",
+    "unicode_not_nfc",
+    "unimplemented",
+    "uninhabited_references",
+    "uninit_assumed_init",
+    "uninit_vec",
+    "uninlined_format_args",
+    "unit_arg",
+    "unit_cmp",
+    "unit_hash",
+    "unit_return_expecting_ord",
+    "unnecessary_box_returns",
+    "unnecessary_cast",
+    "unnecessary_clippy_cfg",
+    "unnecessary_debug_formatting",
+    "unnecessary_fallible_conversions",
+    "unnecessary_filter_map",
+    "unnecessary_find_map",
+    "unnecessary_first_then_check",
+    "unnecessary_fold",
+    "unnecessary_get_then_check",
+    "unnecessary_join",
+    "unnecessary_lazy_evaluations",
+    "unnecessary_literal_bound",
+    "unnecessary_literal_unwrap",
+    "unnecessary_map_on_constructor",
+    "unnecessary_map_or",
+    "unnecessary_min_or_max",
+    "unnecessary_mut_passed",
+    "unnecessary_operation",
+    "unnecessary_option_map_or_else",
+    "unnecessary_owned_empty_strings",
+    "unnecessary_result_map_or_else",
+    "unnecessary_safety_comment",
+    "unnecessary_safety_doc",
+    "unnecessary_self_imports",
+    "unnecessary_semicolon",
+    "unnecessary_sort_by",
+    "unnecessary_struct_initialization",
+    "unnecessary_to_owned",
+    "unnecessary_trailing_comma",
+    "unnecessary_unwrap",
+    "unnecessary_wraps",
+    "unneeded_field_pattern",
+    "unneeded_struct_pattern",
+    "unneeded_wildcard_pattern",
+    "unnested_or_patterns",
+    "unreachable",
+    "unreadable_literal",
+    "unsafe_derive_deserialize",
+    "unsafe_removed_from_name",
+    "unseparated_literal_suffix",
+    "unsound_collection_transmute",
+    "unused_async",
+    "unused_enumerate_index",
+    "unused_format_specs",
+    "unused_io_amount",
+    "unused_peekable",
+    "unused_result_ok",
+    "unused_rounding",
+    "unused_self",
+    "unused_trait_names",
+    "unused_unit",
+    "unusual_byte_groupings",
+    "unwrap_in_result",
+    "unwrap_or_default",
+    "unwrap_used",
+    "upper_case_acronyms",
+    "use_debug",
+    "use_self",
+    "used_underscore_binding",
+    "used_underscore_items",
+    "useless_asref",
+    "useless_attribute",
+    "useless_concat",
+    "useless_conversion",
+    "useless_format",
+    "useless_let_if_seq",
+    "useless_nonzero_new_unchecked",
+    "useless_transmute",
+    "useless_vec",
+    "vec_box",
+    "vec_init_then_push",
+    "vec_resize_to_zero",
+    "verbose_bit_mask",
+    "verbose_file_reads",
+    "volatile_composites",
+    "waker_clone_wake",
+    "while_float",
+    "while_immutable_condition",
+    "while_let_loop",
+    "while_let_on_iterator",
+    "wildcard_dependencies",
+    "wildcard_enum_match_arm",
+    "wildcard_imports",
+    "wildcard_in_or_patterns",
+    "write_literal",
+    "write_with_newline",
+    "writeln_empty_string",
+    "wrong_self_convention",
+    "wrong_transmute",
+    "zero_divided_by_zero",
+    "zero_prefixed_literal",
+    "zero_ptr",
+    "zero_repeat_side_effects",
+    "zero_sized_map_values",
+    "zombie_processes",
+    "zst_offset",
+];
+
+/// `[lints.rustdoc]`
+pub const RUSTDOC: [&str; 12] = [
+    "all",
+    "bare_urls",
+    "broken_intra_doc_links",
+    "invalid_codeblock_attributes",
+    "invalid_html_tags",
+    "invalid_rust_codeblocks",
+    "missing_crate_level_docs",
+    "missing_doc_code_examples",
+    "private_doc_tests",
+    "private_intra_doc_links",
+    "redundant_explicit_links",
+    "unescaped_backticks",
+];
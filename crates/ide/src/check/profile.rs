@@ -0,0 +1,327 @@
+//! The `[profile]` table, with the built-in and custom profiles.
+
+use common::FmtStr;
+use toml::map::{self, MapNode, MapTable, MapTableEntry, Scalar};
+
+use super::{expect_string_in_table, expect_table_in_table, warn_unused, StringAssignment};
+use crate::{cargo, IdeCtx};
+
+#[derive(Debug, PartialEq)]
+pub struct Profile<'a> {
+    pub name: &'a str,
+    /// `inherits`, only allowed in custom profiles.
+    pub inherits: Option<StringAssignment<'a>>,
+    /// The entire toml entry
+    pub entry: &'a MapTableEntry<'a>,
+}
+
+impl Profile<'_> {
+    pub fn is_builtin(&self) -> bool {
+        BUILTIN_PROFILES.contains(&self.name)
+    }
+}
+
+const BUILTIN_PROFILES: [&str; 4] = ["dev", "release", "test", "bench"];
+/// Profile names that are reserved by cargo.
+const RESERVED_PROFILES: [&str; 21] = [
+    "build",
+    "check",
+    "clean",
+    "config",
+    "debug",
+    "doc",
+    "fetch",
+    "fix",
+    "install",
+    "metadata",
+    "package",
+    "publish",
+    "report",
+    "root",
+    "run",
+    "rust",
+    "rustc",
+    "rustdoc",
+    "target",
+    "tmp",
+    "uninstall",
+];
+/// Profile settings and a description of their valid values.
+const PROFILE_KEYS: [(&str, &str); 11] = [
+    ("opt-level", "`0`, `1`, `2`, `3`, `\"s\"` or `\"z\"`"),
+    ("debug", "a bool, `0`, `1`, `2`, `\"none\"`, `\"line-directives-only\"`, `\"line-tables-only\"`, `\"limited\"` or `\"full\"`"),
+    ("split-debuginfo", "`\"off\"`, `\"packed\"` or `\"unpacked\"`"),
+    ("strip", "a bool, `\"none\"`, `\"debuginfo\"` or `\"symbols\"`"),
+    ("debug-assertions", "a bool"),
+    ("overflow-checks", "a bool"),
+    ("lto", "a bool, `\"fat\"`, `\"thin\"` or `\"off\"`"),
+    ("panic", "`\"unwind\"` or `\"abort\"`"),
+    ("incremental", "a bool"),
+    ("codegen-units", "a positive integer"),
+    ("rpath", "a bool"),
+];
+/// Keys that are only allowed at the top level of a profile, and not in `package.<spec>` or
+/// `build-override` sections.
+const PROFILE_ONLY_KEYS: [&str; 6] = [
+    "panic",
+    "lto",
+    "rpath",
+    "inherits",
+    "package",
+    "build-override",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Section {
+    Profile,
+    Package,
+    BuildOverride,
+}
+
+pub fn parse_profiles<'a>(
+    ctx: &mut impl IdeCtx,
+    path: &map::Path<'a, '_>,
+    table: &'a MapTable<'a>,
+) -> Vec<Profile<'a>> {
+    let mut profiles = Vec::new();
+    for (&name, entry) in table.iter() {
+        let path = path.append_key(&entry.reprs);
+        let builtin = BUILTIN_PROFILES.contains(&name);
+        if !builtin {
+            check_profile_name(ctx, &path, entry, name);
+        }
+        let Some(profile_table) = expect_table_in_table(ctx, &path, entry) else {
+            continue;
+        };
+
+        let inherits = parse_profile_section(ctx, &path, profile_table, Section::Profile, name);
+        match &inherits {
+            Some(inherits) if builtin => {
+                ctx.error(cargo::Error::new(
+                    path.context_lines([inherits.repr.parent]),
+                    path.joined_path(inherits.ident()),
+                    inherits.span(),
+                    cargo::ErrorKind::BuiltinProfileInherits(FmtStr::from_str(name)),
+                ));
+            }
+            None if !builtin && profile_table.get("inherits").is_none() => {
+                for repr in entry.reprs.iter() {
+                    ctx.error(cargo::Error::new(
+                        map::context_lines(path.prev, [repr.parent]),
+                        path.fmt_path(),
+                        repr.key.repr_ident().lit_span(),
+                        cargo::ErrorKind::MissingProfileInherits,
+                    ));
+                }
+            }
+            _ => (),
+        }
+
+        profiles.push(Profile {
+            name,
+            inherits,
+            entry,
+        });
+    }
+
+    check_inherits(ctx, path, &profiles);
+
+    profiles
+}
+
+fn check_profile_name(ctx: &mut impl IdeCtx, path: &map::Path, entry: &MapTableEntry, name: &str) {
+    let repr = entry.reprs.first();
+    let lines = || map::context_lines(path.prev, [repr.parent]);
+    let ident = repr.key.repr_ident();
+
+    if let Some((i, c)) =
+        (name.char_indices()).find(|(_, c)| !(c.is_ascii_alphanumeric() || *c == '-' || *c == '_'))
+    {
+        let i = i as u32;
+        ctx.error(cargo::Error::new(
+            lines(),
+            path.fmt_path(),
+            ident.text_range_span(i..i + c.len_utf8() as u32),
+            cargo::ErrorKind::InvalidProfileNameChar(c),
+        ));
+    } else if RESERVED_PROFILES.contains(&name) {
+        ctx.error(cargo::Error::new(
+            lines(),
+            path.fmt_path(),
+            ident.lit_span(),
+            cargo::ErrorKind::ReservedProfileName(FmtStr::from_str(name)),
+        ));
+    }
+}
+
+/// Returns `inherits`.
+fn parse_profile_section<'a>(
+    ctx: &mut impl IdeCtx,
+    path: &map::Path<'a, '_>,
+    table: &'a MapTable<'a>,
+    section: Section,
+    profile: &str,
+) -> Option<StringAssignment<'a>> {
+    let mut inherits = None;
+    for (key, entry) in table.iter() {
+        let path = path.append_key(&entry.reprs);
+        if section != Section::Profile && PROFILE_ONLY_KEYS.contains(key) {
+            let section = match section {
+                Section::Package => "package",
+                _ => "build-override",
+            };
+            for repr in entry.reprs.iter() {
+                ctx.error(cargo::Error::new(
+                    map::context_lines(path.prev, [repr.parent]),
+                    path.fmt_path(),
+                    repr.repr_span(),
+                    cargo::ErrorKind::ProfileOverrideKey(section),
+                ));
+            }
+            continue;
+        }
+
+        match *key {
+            "inherits" => inherits = expect_string_in_table(ctx, &path, entry),
+            "package" => {
+                let Some(specs) = expect_table_in_table(ctx, &path, entry) else {
+                    continue;
+                };
+                for (_, entry) in specs.iter() {
+                    let path = path.append_key(&entry.reprs);
+                    if let Some(table) = expect_table_in_table(ctx, &path, entry) {
+                        parse_profile_section(ctx, &path, table, Section::Package, profile);
+                    }
+                }
+            }
+            "build-override" => {
+                if let Some(table) = expect_table_in_table(ctx, &path, entry) {
+                    parse_profile_section(ctx, &path, table, Section::BuildOverride, profile);
+                }
+            }
+            _ => check_profile_value(ctx, &path, entry, key, profile),
+        }
+    }
+    inherits
+}
+
+fn check_profile_value(
+    ctx: &mut impl IdeCtx,
+    path: &map::Path,
+    entry: &MapTableEntry,
+    key: &str,
+    profile: &str,
+) {
+    let Some((_, expected)) = PROFILE_KEYS.iter().find(|(k, _)| *k == key) else {
+        warn_unused(ctx, path, entry);
+        return;
+    };
+
+    let valid = match (key, &entry.node) {
+        (_, MapNode::Scalar(Scalar::Invalid(..))) => return,
+        ("opt-level", MapNode::Scalar(Scalar::Int(i))) => (0..=3).contains(&i.val),
+        ("opt-level", MapNode::Scalar(Scalar::String(s))) => ["s", "z"].contains(&s.text),
+        ("debug", MapNode::Scalar(Scalar::Int(i))) => (0..=2).contains(&i.val),
+        ("debug", MapNode::Scalar(Scalar::String(s))) => [
+            "none",
+            "line-directives-only",
+            "line-tables-only",
+            "limited",
+            "full",
+        ]
+        .contains(&s.text),
+        ("split-debuginfo", MapNode::Scalar(Scalar::String(s))) => {
+            ["off", "packed", "unpacked"].contains(&s.text)
+        }
+        ("strip", MapNode::Scalar(Scalar::String(s))) => {
+            ["none", "debuginfo", "symbols"].contains(&s.text)
+        }
+        ("lto", MapNode::Scalar(Scalar::String(s))) => ["fat", "thin", "off"].contains(&s.text),
+        ("panic", MapNode::Scalar(Scalar::String(s))) => ["unwind", "abort"].contains(&s.text),
+        ("codegen-units", MapNode::Scalar(Scalar::Int(i))) => i.val > 0,
+        (
+            "debug" | "strip" | "debug-assertions" | "overflow-checks" | "lto" | "incremental"
+            | "rpath",
+            MapNode::Scalar(Scalar::Bool(_)),
+        ) => true,
+        _ => false,
+    };
+
+    for repr in entry.reprs.iter() {
+        if !valid {
+            ctx.error(cargo::Error::new(
+                map::context_lines(path.prev, [repr.parent]),
+                path.fmt_path(),
+                repr.repr_span(),
+                cargo::ErrorKind::InvalidProfileValue(expected),
+            ));
+        } else if key == "panic" && ["test", "bench"].contains(&profile) {
+            ctx.warn(cargo::Warning::new(
+                map::context_lines(path.prev, [repr.parent]),
+                path.fmt_path(),
+                repr.repr_span(),
+                cargo::WarningKind::ProfilePanicIgnored(FmtStr::from_str(profile)),
+            ));
+        }
+    }
+}
+
+/// Check that the profiles custom profiles inherit from exist, and that there are no cycles.
+fn check_inherits(ctx: &mut impl IdeCtx, path: &map::Path, profiles: &[Profile]) {
+    let find = |name: &str| profiles.iter().position(|p| p.name == name);
+
+    for (i, profile) in profiles.iter().enumerate() {
+        let Some(inherits) = &profile.inherits else {
+            continue;
+        };
+        if profile.is_builtin() {
+            continue;
+        }
+        let path = path.append_key(&profile.entry.reprs);
+        let target = inherits.val.text;
+        if BUILTIN_PROFILES.contains(&target) {
+            continue;
+        }
+        if find(target).is_none() {
+            ctx.error(cargo::Error::new(
+                path.context_lines([inherits.repr.parent]),
+                path.joined_path(inherits.ident()),
+                inherits.val.lit_span,
+                cargo::ErrorKind::UnknownInheritedProfile(FmtStr::from_str(target)),
+            ));
+            continue;
+        }
+
+        // Follow the chain of custom profiles, and report a cycle once, at the first profile
+        // that is part of it.
+        let mut chain = vec![i];
+        let mut current = i;
+        while let Some(next) = (profiles[current].inherits.as_ref())
+            .filter(|_| !profiles[current].is_builtin())
+            .and_then(|p| find(p.val.text))
+        {
+            if next == i {
+                if chain.iter().all(|&c| c >= i) {
+                    let mut cycle = String::new();
+                    for &c in chain.iter() {
+                        cycle.push_str(&format!("`{}` -> ", profiles[c].name));
+                    }
+                    cycle.push_str(&format!("`{}`", profile.name));
+                    ctx.error(cargo::Error::new(
+                        path.context_lines([inherits.repr.parent]),
+                        path.joined_path(inherits.ident()),
+                        inherits.val.lit_span,
+                        cargo::ErrorKind::ProfileInheritsCycle(FmtStr::from_string(cycle)),
+                    ));
+                }
+                break;
+            }
+            if chain.contains(&next) {
+                // A cycle that doesn't include this profile.
+                break;
+            }
+            chain.push(next);
+            current = next;
+        }
+    }
+}
@@ -0,0 +1,346 @@
+//! The target tables `[lib]`, `[[bin]]`, `[[example]]`, `[[test]]` and `[[bench]]`.
+
+use common::{FmtStr, Span};
+use toml::map::{self, MapArray, MapNode, MapTable, MapTableEntry, ParentId, Scalar};
+use toml::parse::StringVal;
+use toml::util::Datatype;
+
+use super::{
+    deprecated_underscore, expect_array_in_table, expect_bool_in_table,
+    expect_string_array_in_table, expect_string_in_table, expect_table_in_table, parse_edition,
+    warn_unused, BoolAssignment, Edition, FeatureGraph, StringAssignment,
+};
+use crate::{cargo, IdeCtx};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TargetKind {
+    Lib,
+    Bin,
+    Example,
+    Test,
+    Bench,
+}
+
+impl TargetKind {
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Lib => "lib",
+            Self::Bin => "bin",
+            Self::Example => "example",
+            Self::Test => "test",
+            Self::Bench => "bench",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Target<'a> {
+    pub kind: TargetKind,
+    /// Optional for `[lib]`, which defaults to the package name.
+    pub name: Option<StringAssignment<'a>>,
+    pub path: Option<StringAssignment<'a>>,
+    pub crate_types: Vec<&'a StringVal<'a>>,
+    pub proc_macro: Option<BoolAssignment<'a>>,
+    pub required_features: Vec<&'a StringVal<'a>>,
+    pub edition: Option<Edition>,
+    /// The entire toml table
+    pub table: &'a MapTable<'a>,
+}
+
+const CRATE_TYPES: [&str; 7] = [
+    "bin",
+    "lib",
+    "rlib",
+    "dylib",
+    "cdylib",
+    "staticlib",
+    "proc-macro",
+];
+const TARGET_BOOL_KEYS: [&str; 6] = [
+    "test",
+    "doctest",
+    "bench",
+    "doc",
+    "harness",
+    "doc-scrape-examples",
+];
+
+/// Parse the target tables, `required-features` refer to the `features`.
+pub fn parse_targets<'a>(
+    ctx: &mut impl IdeCtx,
+    features: &FeatureGraph,
+    entries: Vec<(TargetKind, &'a MapTableEntry<'a>)>,
+) -> Vec<Target<'a>> {
+    let mut targets: Vec<Target> = Vec::new();
+    for (kind, entry) in entries {
+        let path = map::Path::root(&entry.reprs);
+
+        if kind == TargetKind::Lib {
+            if let Some(table) = expect_table_in_table(ctx, &path, entry) {
+                let repr = entry.reprs.first();
+                let location = (repr.parent, repr.key.repr_ident().lit_span());
+                let target = parse_target(ctx, features, &targets, &path, table, kind, location);
+                targets.push(target);
+            }
+            continue;
+        }
+
+        let Some(array) = expect_array_in_table(ctx, &path, entry) else {
+            continue;
+        };
+        match array {
+            MapArray::Toplevel(array) => {
+                for (i, array_entry) in array.iter().enumerate() {
+                    let path = path.append_index(i);
+                    let location = (array_entry.parent, array_entry.repr.header.span());
+                    let table = &array_entry.node;
+                    let target =
+                        parse_target(ctx, features, &targets, &path, table, kind, location);
+                    targets.push(target);
+                }
+            }
+            MapArray::Inline(array) => {
+                for (i, array_entry) in array.iter().enumerate() {
+                    let path = path.append_index(i);
+                    match &array_entry.node {
+                        MapNode::Table(table) => {
+                            let location = (array.parent, array_entry.repr.span());
+                            let target =
+                                parse_target(ctx, features, &targets, &path, table, kind, location);
+                            targets.push(target);
+                        }
+                        MapNode::Scalar(Scalar::Invalid(..)) => (),
+                        n => ctx.error(cargo::Error::new(
+                            map::context_lines(path.prev, [array.parent]),
+                            path.fmt_path(),
+                            array_entry.repr.span(),
+                            cargo::ErrorKind::WrongDatatype {
+                                expected: Datatype::Table,
+                                found: n.datatype(),
+                            },
+                        )),
+                    }
+                }
+            }
+        }
+    }
+    targets
+}
+
+fn parse_target<'a>(
+    ctx: &mut impl IdeCtx,
+    features: &FeatureGraph,
+    previous: &[Target],
+    path: &map::Path<'a, '_>,
+    table: &'a MapTable<'a>,
+    kind: TargetKind,
+    (parent, span): (ParentId, Span),
+) -> Target<'a> {
+    let mut target = Target {
+        kind,
+        name: None,
+        path: None,
+        crate_types: Vec::new(),
+        proc_macro: None,
+        required_features: Vec::new(),
+        edition: None,
+        table,
+    };
+    let is_lib = kind == TargetKind::Lib;
+    let has_crate_type = is_lib || kind == TargetKind::Example;
+
+    for (key, entry) in table.iter() {
+        let path = path.append_key(&entry.reprs);
+        match *key {
+            "name" => {
+                if let Some(str) = expect_string_in_table(ctx, &path, entry) {
+                    check_target_name(ctx, &path, &str, kind, previous);
+                    target.name = Some(str);
+                }
+            }
+            "path" => target.path = expect_string_in_table(ctx, &path, entry),
+            "crate-type" if has_crate_type => {
+                parse_crate_types(ctx, &mut target.crate_types, &path, entry);
+            }
+            "crate_type" if has_crate_type => {
+                const OLD: &str = "crate_type";
+                const NEW: &str = "crate-type";
+                let ignored = deprecated_underscore(ctx, path.prev, table, OLD, NEW, entry);
+                if !ignored {
+                    parse_crate_types(ctx, &mut target.crate_types, &path, entry);
+                }
+            }
+            "proc-macro" if is_lib => target.proc_macro = expect_bool_in_table(ctx, &path, entry),
+            "proc_macro" if is_lib => {
+                const OLD: &str = "proc_macro";
+                const NEW: &str = "proc-macro";
+                let ignored = deprecated_underscore(ctx, path.prev, table, OLD, NEW, entry);
+                if !ignored {
+                    target.proc_macro = expect_bool_in_table(ctx, &path, entry);
+                }
+            }
+            "required-features" if !is_lib => {
+                if let Some(array) = expect_string_array_in_table(ctx, &path, entry) {
+                    for &(i, feature) in array.items.iter() {
+                        let path = path.append_index(i);
+                        check_required_feature(ctx, features, &path, array.parent, feature);
+                        target.required_features.push(feature);
+                    }
+                }
+            }
+            "edition" => {
+                if let Some(str) = expect_string_in_table(ctx, &path, entry) {
+                    target.edition = parse_edition(ctx, &path, &str);
+                }
+            }
+            "plugin" => {
+                for repr in entry.reprs.iter() {
+                    ctx.warn(cargo::Warning::new(
+                        map::context_lines(path.prev, [repr.parent]),
+                        path.fmt_path(),
+                        repr.repr_span(),
+                        cargo::WarningKind::TargetPluginIgnored,
+                    ));
+                }
+            }
+            k if TARGET_BOOL_KEYS.contains(&k) => {
+                expect_bool_in_table(ctx, &path, entry);
+            }
+            _ => warn_unused(ctx, &path, entry),
+        }
+    }
+
+    if target.name.is_none() && !is_lib && table.get("name").is_none() {
+        ctx.error(cargo::Error::new(
+            map::context_lines(path.prev, [parent]),
+            path.fmt_path(),
+            span,
+            cargo::ErrorKind::MissingTargetName,
+        ));
+    }
+
+    check_proc_macro(ctx, path, &target);
+
+    target
+}
+
+fn check_target_name(
+    ctx: &mut impl IdeCtx,
+    path: &map::Path,
+    str: &StringAssignment,
+    kind: TargetKind,
+    previous: &[Target],
+) {
+    let lines = || map::context_lines(path.prev, [str.repr.parent]);
+    if str.val.text.is_empty() {
+        ctx.error(cargo::Error::new(
+            lines(),
+            path.fmt_path(),
+            str.val.lit_span,
+            cargo::ErrorKind::EmptyTargetName,
+        ));
+        return;
+    }
+
+    // Library names are used as crate names, so they can't contain dashes.
+    let valid = |c: char| c.is_alphanumeric() || c == '_' || (c == '-' && kind != TargetKind::Lib);
+    if let Some((i, c)) = str.val.text.char_indices().find(|(_, c)| !valid(*c)) {
+        let i = i as u32;
+        ctx.error(cargo::Error::new(
+            lines(),
+            path.fmt_path(),
+            str.val.text_range_span(i..i + c.len_utf8() as u32),
+            cargo::ErrorKind::InvalidTargetNameChar(c),
+        ));
+    }
+
+    // Names have to be unique per target kind.
+    let first = (previous.iter())
+        .filter(|t| t.kind == kind)
+        .filter_map(|t| t.name.as_ref())
+        .find(|n| n.val.text == str.val.text);
+    if let Some(first) = first {
+        ctx.error(cargo::Error::new(
+            lines(),
+            path.fmt_path(),
+            str.val.lit_span,
+            cargo::ErrorKind::DuplicateTargetName {
+                kind: kind.key(),
+                first_span: first.val.lit_span,
+            },
+        ));
+    }
+}
+
+fn parse_crate_types<'a>(
+    ctx: &mut impl IdeCtx,
+    crate_types: &mut Vec<&'a StringVal<'a>>,
+    path: &map::Path,
+    entry: &'a MapTableEntry<'a>,
+) {
+    let Some(array) = expect_string_array_in_table(ctx, path, entry) else {
+        return;
+    };
+    for &(i, crate_type) in array.items.iter() {
+        if !CRATE_TYPES.contains(&crate_type.text) {
+            let path = path.append_index(i);
+            ctx.error(cargo::Error::new(
+                map::context_lines(path.prev, [array.parent]),
+                path.fmt_path(),
+                crate_type.lit_span,
+                cargo::ErrorKind::UnknownCrateType(FmtStr::from_str(crate_type.text)),
+            ));
+        }
+        crate_types.push(crate_type);
+    }
+}
+
+/// A proc-macro library can't have other crate types.
+fn check_proc_macro(ctx: &mut impl IdeCtx, path: &map::Path, target: &Target) {
+    let proc_macro_type = target.crate_types.iter().find(|t| t.text == "proc-macro");
+    let proc_macro = target.proc_macro.as_ref().filter(|p| p.val.val);
+    let has_other_types = target.crate_types.iter().any(|t| t.text != "proc-macro");
+    if !has_other_types {
+        return;
+    }
+
+    if let Some(proc_macro) = proc_macro {
+        ctx.error(cargo::Error::new(
+            path.context_lines([proc_macro.repr.parent]),
+            path.joined_path(proc_macro.ident()),
+            proc_macro.span(),
+            cargo::ErrorKind::ProcMacroWithOtherCrateTypes,
+        ));
+    }
+    let entry = (target.table.get("crate-type")).or_else(|| target.table.get("crate_type"));
+    if let (Some(entry), Some(crate_type)) = (entry, proc_macro_type) {
+        let path = path.append_key(&entry.reprs);
+        let repr = entry.reprs.first();
+        ctx.error(cargo::Error::new(
+            map::context_lines(path.prev, [repr.parent]),
+            path.fmt_path(),
+            crate_type.lit_span,
+            cargo::ErrorKind::ProcMacroWithOtherCrateTypes,
+        ));
+    }
+}
+
+fn check_required_feature(
+    ctx: &mut impl IdeCtx,
+    features: &FeatureGraph,
+    path: &map::Path,
+    parent: ParentId,
+    feature: &StringVal,
+) {
+    let name = feature.text;
+    // Features of dependencies, `<dep>/<feature>`, aren't checked.
+    if name.contains('/') || features.get(name).is_some() || features.has_implicit_feature(name) {
+        return;
+    }
+    ctx.error(cargo::Error::new(
+        map::context_lines(path.prev, [parent]),
+        path.fmt_path(),
+        feature.lit_span,
+        cargo::ErrorKind::UnknownRequiredFeature(FmtStr::from_str(name)),
+    ));
+}
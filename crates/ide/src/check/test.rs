@@ -0,0 +1,1333 @@
+use bumpalo::Bump;
+use common::diagnostic::{Diagnostic, DiagnosticHint};
+use pretty_assertions::assert_eq;
+use toml::TomlCtx;
+
+use super::*;
+use crate::{Error, IdeDiagnostics, Warning};
+
+#[track_caller]
+fn check_str(input: &str, f: impl FnOnce(&IdeDiagnostics, State)) {
+    let mut ctx = IdeDiagnostics::default();
+    let bump = Bump::new();
+    let tokens = ctx.lex(&bump, input);
+    let asts = ctx.parse(&bump, &tokens);
+    let map = ctx.map(&asts);
+    let state = check(&mut ctx, &map);
+    f(&ctx, state);
+}
+
+#[test]
+fn rust_version() {
+    for (input, expected) in [
+        ("1.70", PartialVersion::new(1, Some(70), None)),
+        ("1.70.1", PartialVersion::new(1, Some(70), Some(1))),
+        ("1", PartialVersion::new(1, None, None)),
+    ] {
+        let input = format!("[package]\nrust-version = \"{input}\"\n");
+        check_str(&input, |ctx, state| {
+            assert_eq!(Vec::<Error>::new(), ctx.errors);
+            let version = state.rust_version.and_then(|r| r.version);
+            assert_eq!(Some(expected), version);
+        });
+    }
+}
+
+#[test]
+fn rust_version_invalid() {
+    let input = "[package]\nrust-version = \"1.70-nightly\"\n";
+    check_str(input, |ctx, state| {
+        let spans: Vec<_> = ctx.errors.iter().map(|e| e.span()).collect();
+        assert_eq!(vec![Span::new(Pos::new(1, 20), Pos::new(1, 28))], spans);
+        assert_eq!(None, state.rust_version.and_then(|r| r.version));
+    });
+
+    let input = "[package]\nrust-version = { workspace = true }\n";
+    check_str(input, |ctx, state| {
+        assert_eq!(Vec::<Error>::new(), ctx.errors);
+        assert_eq!(None, state.rust_version);
+    });
+}
+
+fn descriptions<D: Diagnostic>(diagnostics: &[D]) -> Vec<String> {
+    let mut descriptions = Vec::new();
+    for d in diagnostics {
+        let mut s = String::new();
+        d.description(&mut s).unwrap();
+        descriptions.push(s);
+    }
+    descriptions
+}
+
+#[test]
+fn package_valid() {
+    let input = r#"
+[package]
+name = "crates-nvim"
+version = "0.1.0-alpha.1"
+edition = "2021"
+rust-version = "1.70"
+authors = ["a", "b"]
+description = "a description"
+license = "MIT"
+readme = false
+build = "build.rs"
+links = "git2"
+publish = ["my-registry"]
+keywords = ["neovim", "cargo", "semver"]
+categories = ["development-tools"]
+include = ["src/**"]
+autobins = false
+
+[package.metadata.docs]
+all-features = true
+"#;
+    check_str(input, |ctx, state| {
+        assert_eq!(Vec::<Error>::new(), ctx.errors);
+        assert_eq!(Vec::<String>::new(), descriptions(&ctx.warnings));
+        assert_eq!(Some("crates-nvim"), state.package_name.map(|n| n.val.text));
+        let version = state.package_version.and_then(|v| v.version);
+        let expected = semver::parse_version("0.1.0-alpha.1", Pos::ZERO).unwrap();
+        assert_eq!(Some(expected), version);
+        assert_eq!(Some(Edition::E2021), state.edition);
+    });
+}
+
+#[test]
+fn package_inherited() {
+    let input = r#"
+[package]
+name = "a"
+version.workspace = true
+edition = { workspace = true }
+authors = { workspace = false }
+license = { path = "LICENSE" }
+"#;
+    check_str(input, |ctx, state| {
+        assert_eq!(
+            vec![
+                "invalid inherited field `package.authors`; `workspace` cannot be false",
+                "expected `package.license` to be inherited from the workspace with `workspace = true`",
+            ],
+            descriptions(&ctx.errors),
+        );
+        assert_eq!(
+            vec!["unknown key `package.license.path` is ignored"],
+            descriptions(&ctx.warnings),
+        );
+        assert_eq!(None, state.package_version);
+        assert_eq!(None, state.edition);
+    });
+}
+
+#[test]
+fn package_name_invalid() {
+    let input = "[package]\nname = \"1a.b\"\n";
+    check_str(input, |ctx, state| {
+        assert_eq!(
+            vec![
+                "invalid character `1` in package name, the first character must be an ASCII letter or `_`",
+                "invalid character `.` in package name, only ASCII alphanumerics, `-` and `_` are allowed",
+            ],
+            descriptions(&ctx.errors),
+        );
+        let spans: Vec<_> = ctx.errors.iter().map(|e| e.span()).collect();
+        assert_eq!(
+            vec![
+                Span::new(Pos::new(1, 8), Pos::new(1, 9)),
+                Span::new(Pos::new(1, 10), Pos::new(1, 11)),
+            ],
+            spans
+        );
+        assert!(state.package_name.is_some());
+    });
+
+    let input = "[package]\nname = \"\"\n";
+    check_str(input, |ctx, _| {
+        assert_eq!(
+            vec!["package name cannot be empty"],
+            descriptions(&ctx.errors)
+        );
+    });
+}
+
+#[test]
+fn package_name_reserved() {
+    for (name, expected) in [
+        (
+            "fn",
+            "package name `fn` is a rust keyword, it cannot be used as a crate name",
+        ),
+        (
+            "proc-macro",
+            "package name `proc-macro` conflicts with a standard library crate",
+        ),
+        (
+            &"a".repeat(65),
+            "package name is longer than 64 characters, crates.io will reject it",
+        ),
+    ] {
+        let input = format!("[package]\nname = \"{name}\"\n");
+        check_str(&input, |ctx, _| {
+            assert_eq!(Vec::<Error>::new(), ctx.errors);
+            assert_eq!(vec![expected], descriptions(&ctx.warnings));
+        });
+    }
+}
+
+#[test]
+fn package_version_invalid() {
+    let input = "[package]\nversion = \"1.2\"\n";
+    check_str(input, |ctx, state| {
+        assert_eq!(1, ctx.errors.len());
+        assert!(matches!(ctx.errors[0], Error::Semver(_)));
+        assert_eq!(None, state.package_version.and_then(|v| v.version));
+    });
+}
+
+#[test]
+fn package_edition_invalid() {
+    let input = "[package]\nedition = \"2020\"\n";
+    check_str(input, |ctx, state| {
+        assert_eq!(
+            vec!["unknown edition `2020`, expected one of `2015`, `2018`, `2021` or `2024`"],
+            descriptions(&ctx.errors),
+        );
+        let spans: Vec<_> = ctx.errors.iter().map(|e| e.span()).collect();
+        assert_eq!(vec![Span::new(Pos::new(1, 10), Pos::new(1, 16))], spans);
+        assert_eq!(None, state.edition);
+    });
+}
+
+#[test]
+fn package_keywords_and_categories() {
+    let input = r#"
+[package]
+keywords = ["a", "b", "c", "d", "-e", "abcdefghijklmnopqrstuvwxyz"]
+categories = ["a", "b", "c", "d", "e", "f", 3]
+"#;
+    check_str(input, |ctx, _| {
+        assert_eq!(
+            vec!["expected `package.categories[6]` to be of type string, found int"],
+            descriptions(&ctx.errors),
+        );
+        assert_eq!(
+            vec![
+                "invalid keyword `-e`, keywords must start with an ASCII alphanumeric and only contain ASCII alphanumerics, `_`, `-` or `+`",
+                "`package.keywords[5]` has more than 5 keywords, crates.io will reject it",
+                "`package.keywords[5]` is longer than 20 characters, crates.io will reject it",
+                "`package.categories[5]` has more than 5 categories, crates.io will reject it",
+            ],
+            descriptions(&ctx.warnings),
+        );
+    });
+}
+
+#[test]
+fn package_wrong_datatypes() {
+    let input = r#"
+[package]
+publish = "yes"
+readme = 1
+links = false
+autotests = "no"
+authors = "me"
+unknown = 2
+"#;
+    check_str(input, |ctx, _| {
+        assert_eq!(
+            vec![
+                "expected `package.publish` to be of type bool or array, found string",
+                "expected `package.readme` to be of type string or bool, found int",
+                "expected `package.links` to be of type string, found bool",
+                "expected `package.autotests` to be of type bool, found string",
+                "expected `package.authors` to be of type array, found string",
+            ],
+            descriptions(&ctx.errors),
+        );
+        assert_eq!(
+            vec!["unknown key `package.unknown` is ignored"],
+            descriptions(&ctx.warnings),
+        );
+    });
+}
+
+#[test]
+fn package_license() {
+    let input = "[package]\nlicense = \"MIT/Apache-2.0 OR Foo\"\n";
+    check_str(input, |ctx, _| {
+        assert_eq!(
+            vec!["unknown SPDX license identifier `Foo`"],
+            descriptions(&ctx.errors),
+        );
+        let spans: Vec<_> = ctx.errors.iter().map(|e| e.span()).collect();
+        assert_eq!(vec![Span::new(Pos::new(1, 29), Pos::new(1, 32))], spans);
+        assert_eq!(
+            vec!["`/` is deprecated as a license separator, use `OR` instead"],
+            descriptions(&ctx.warnings),
+        );
+    });
+}
+
+#[test]
+fn package_license_and_license_file() {
+    let input = "[package]\nlicense = \"MIT\"\nlicense-file = \"LICENSE\"\n";
+    check_str(input, |ctx, _| {
+        assert_eq!(Vec::<Error>::new(), ctx.errors);
+        assert_eq!(
+            vec!["only one of `license` or `license-file` is necessary"],
+            descriptions(&ctx.warnings),
+        );
+        let hints: Vec<_> = ctx
+            .warnings
+            .iter()
+            .map(|w| w.hint().unwrap().span())
+            .collect();
+        assert_eq!(vec![Span::new(Pos::new(2, 0), Pos::new(2, 24))], hints);
+    });
+}
+
+#[test]
+fn package_name_invalid_unicode() {
+    let input = "[package]\nname = \"aé-ö\"\n";
+    check_str(input, |ctx, _| {
+        let spans: Vec<_> = ctx.errors.iter().map(|e| e.span()).collect();
+        assert_eq!(
+            vec![
+                Span::new(Pos::new(1, 9), Pos::new(1, 11)),
+                Span::new(Pos::new(1, 12), Pos::new(1, 14)),
+            ],
+            spans
+        );
+    });
+}
+
+#[test]
+fn features_resolve() {
+    let input = r#"
+[features]
+default = ["std"]
+std = ["serde?/std", "dep:log"]
+derive = ["serde/derive"]
+full = ["std", "derive", "rand"]
+
+[dependencies]
+serde = { version = "1", optional = true }
+log = { version = "0.4", optional = true }
+rand = { version = "0.8", optional = true }
+"#;
+    check_str(input, |ctx, state| {
+        assert_eq!(Vec::<Error>::new(), ctx.errors);
+        let features = state.features();
+        let names: Vec<_> = features.features().iter().map(|f| f.name).collect();
+        assert_eq!(vec!["default", "std", "derive", "full"], names);
+        assert!(!features.has_implicit_feature("log"));
+        assert!(features.has_implicit_feature("rand"));
+
+        let resolved = features.resolve(["default"]);
+        assert_eq!(
+            ResolvedFeatures {
+                features: ["default", "std"].into(),
+                deps: ["log"].into(),
+                dep_features: [].into(),
+            },
+            resolved
+        );
+
+        let resolved = features.resolve(["full"]);
+        assert_eq!(
+            ResolvedFeatures {
+                features: ["derive", "full", "rand", "serde", "std"].into(),
+                deps: ["log", "rand", "serde"].into(),
+                dep_features: [("serde", "derive"), ("serde", "std")].into(),
+            },
+            resolved
+        );
+    });
+}
+
+#[test]
+fn features_invalid_members() {
+    let input = r#"
+[features]
+a = ["b", "dep:c", "dep:d", "e/f", "g?/h", "dev/x", "log", "dep:log"]
+
+[dependencies]
+c = "1"
+g = "1"
+log = { version = "0.4", optional = true }
+
+[dev-dependencies]
+dev = "1"
+"#;
+    check_str(input, |ctx, _| {
+        assert_eq!(
+            vec![
+                "`features.a[0]` includes `b`, which is neither a feature nor an optional dependency",
+                "`features.a[1]` refers to `c`, which is not an optional dependency",
+                "`features.a[2]` refers to `d`, which is not a dependency",
+                "`features.a[3]` refers to `e`, which is not a dependency",
+                "`features.a[4]` includes `g?/` with a `?`, but `g` is not an optional dependency",
+                "`features.a[6]` includes `log`, but the optional dependency `log` has no implicit feature, because `dep:log` is used",
+            ],
+            descriptions(&ctx.errors),
+        );
+        let spans: Vec<_> = ctx.errors.iter().map(|e| e.span()).collect();
+        assert_eq!(
+            vec![
+                Span::new(Pos::new(2, 5), Pos::new(2, 8)),
+                Span::new(Pos::new(2, 15), Pos::new(2, 16)),
+                Span::new(Pos::new(2, 24), Pos::new(2, 25)),
+                Span::new(Pos::new(2, 29), Pos::new(2, 30)),
+                Span::new(Pos::new(2, 36), Pos::new(2, 37)),
+                Span::new(Pos::new(2, 52), Pos::new(2, 57)),
+            ],
+            spans
+        );
+        let hint = ctx.errors[5].hint().map(|h| h.span());
+        assert_eq!(Some(Span::new(Pos::new(2, 59), Pos::new(2, 68))), hint);
+    });
+}
+
+#[test]
+fn features_cycle() {
+    let input = r#"
+[features]
+a = ["b"]
+b = ["c"]
+c = ["a"]
+d = ["d"]
+"#;
+    check_str(input, |ctx, _| {
+        assert_eq!(
+            vec![
+                "cyclic feature dependency: `a` -> `b` -> `c` -> `a`",
+                "cyclic feature dependency: `d` -> `d`",
+            ],
+            descriptions(&ctx.errors),
+        );
+        let labels: Vec<_> = (ctx.errors[0].labels().into_iter())
+            .map(|l| (l.span, l.annotation.to_string()))
+            .collect();
+        assert_eq!(
+            vec![
+                (
+                    Span::new(Pos::new(2, 5), Pos::new(2, 8)),
+                    "`a` enables `b`".to_string()
+                ),
+                (
+                    Span::new(Pos::new(3, 5), Pos::new(3, 8)),
+                    "`b` enables `c`".to_string()
+                ),
+                (
+                    Span::new(Pos::new(4, 5), Pos::new(4, 8)),
+                    "`c` enables `a`".to_string()
+                ),
+            ],
+            labels
+        );
+    });
+}
+
+#[test]
+fn features_invalid_name() {
+    let input = "[features]\n\"-a\" = []\n\"b/c\" = []\n";
+    check_str(input, |ctx, _| {
+        assert_eq!(
+            vec![
+                "invalid character `-` in feature name, the first character must be an alphanumeric or `_`",
+                "invalid character `/` in feature name, only alphanumerics, `_`, `-`, `+` and `.` are allowed",
+            ],
+            descriptions(&ctx.errors),
+        );
+        let spans: Vec<_> = ctx.errors.iter().map(|e| e.span()).collect();
+        assert_eq!(
+            vec![
+                Span::new(Pos::new(1, 1), Pos::new(1, 2)),
+                Span::new(Pos::new(2, 2), Pos::new(2, 3)),
+            ],
+            spans
+        );
+    });
+}
+
+#[test]
+fn workspace_parse() {
+    let input = r#"
+[workspace]
+members = ["crates/*"]
+exclude = ["crates/old"]
+default-members = ["crates/cli"]
+resolver = "2"
+
+[workspace.package]
+version = "0.1.0"
+name = "foo"
+
+[workspace.dependencies]
+serde = { version = "1", optional = true }
+toml = { workspace = true }
+"#;
+    check_str(input, |ctx, state| {
+        assert_eq!(
+            vec![
+                "workspace dependency `workspace.dependencies.serde` cannot be optional",
+                "workspace dependency `workspace.dependencies.toml` cannot be inherited from the workspace",
+            ],
+            descriptions(&ctx.errors),
+        );
+        assert_eq!(
+            vec!["unknown key `workspace.package.name` is ignored"],
+            descriptions(&ctx.warnings),
+        );
+
+        let workspace = state.workspace().unwrap();
+        let texts = |iter: &mut dyn Iterator<Item = &StringVal>| -> Vec<String> {
+            iter.map(|s| s.text.to_string()).collect()
+        };
+        assert_eq!(vec!["crates/*"], texts(&mut workspace.members()));
+        assert_eq!(vec!["crates/old"], texts(&mut workspace.exclude()));
+        assert_eq!(vec!["crates/cli"], texts(&mut workspace.default_members()));
+        assert_eq!(Some(Resolver::V2), workspace.resolver());
+        let deps: Vec<_> = workspace.dependencies().iter().map(|d| d.name).collect();
+        assert_eq!(vec!["serde", "toml"], deps);
+        assert!(workspace.package_field("version").is_some());
+    });
+}
+
+#[test]
+fn workspace_unknown_resolver() {
+    let input = "[workspace]\nresolver = \"4\"\n";
+    check_str(input, |ctx, state| {
+        assert_eq!(
+            vec!["unknown resolver `4`, expected one of `1`, `2` or `3`"],
+            descriptions(&ctx.errors),
+        );
+        let spans: Vec<_> = ctx.errors.iter().map(|e| e.span()).collect();
+        assert_eq!(vec![Span::new(Pos::new(1, 11), Pos::new(1, 14))], spans);
+        assert_eq!(None, state.workspace().unwrap().resolver());
+    });
+}
+
+#[track_caller]
+fn check_member(root: &str, member: &str, f: impl FnOnce(&IdeDiagnostics, Inherited)) {
+    let mut root_ctx = IdeDiagnostics::default();
+    let root_bump = Bump::new();
+    let tokens = root_ctx.lex(&root_bump, root);
+    let asts = root_ctx.parse(&root_bump, &tokens);
+    let root_map = root_ctx.map(&asts);
+    let root_state = check(&mut root_ctx, &root_map);
+    assert_eq!(Vec::<Error>::new(), root_ctx.errors);
+
+    let mut ctx = IdeDiagnostics::default();
+    let bump = Bump::new();
+    let tokens = ctx.lex(&bump, member);
+    let asts = ctx.parse(&bump, &tokens);
+    let map = ctx.map(&asts);
+    check(&mut ctx, &map);
+    let inherited = resolve_inherited(&mut ctx, &map, root_state.workspace());
+    f(&ctx, inherited);
+}
+
+const ROOT: &str = r#"
+[workspace.package]
+version = "0.1.0"
+license = "MIT"
+
+[workspace.dependencies]
+serde = { version = "1", default-features = false }
+toml = "0.8"
+"#;
+
+#[test]
+fn workspace_resolve() {
+    let member = r#"
+[package]
+name = "member"
+version.workspace = true
+license = { workspace = true }
+
+[dependencies]
+serde = { workspace = true, features = ["derive"], optional = true, default-features = false }
+
+[target.'cfg(unix)'.dev-dependencies]
+toml = { workspace = true }
+"#;
+    check_member(ROOT, member, |ctx, inherited| {
+        assert_eq!(Vec::<Error>::new(), ctx.errors);
+        assert_eq!(Vec::<crate::Warning>::new(), ctx.warnings);
+
+        let fields: Vec<_> = inherited.fields.iter().map(|f| f.key).collect();
+        assert_eq!(vec!["version", "license"], fields);
+        let deps: Vec<_> = (inherited.dependencies.iter())
+            .map(|d| (d.name, d.kind, d.target, d.workspace.name))
+            .collect();
+        assert_eq!(
+            vec![
+                ("serde", DependencyKind::Normal, None, "serde"),
+                ("toml", DependencyKind::Dev, Some("cfg(unix)"), "toml"),
+            ],
+            deps
+        );
+    });
+}
+
+#[test]
+fn workspace_resolve_invalid() {
+    let member = r#"
+[package]
+name = "member"
+edition = { workspace = true }
+
+[dependencies]
+toml = { workspace = true, default-features = false }
+rand = { workspace = true }
+"#;
+    check_member(ROOT, member, |ctx, inherited| {
+        assert_eq!(
+            vec![
+                "`package.edition` is inherited from the workspace, but `workspace.package.edition` doesn't exist",
+                "dependency `dependencies.rand` is inherited from the workspace, but `workspace.dependencies.rand` doesn't exist",
+            ],
+            descriptions(&ctx.errors),
+        );
+        let spans: Vec<_> = ctx.errors.iter().map(|e| e.span()).collect();
+        assert_eq!(
+            vec![
+                Span::new(Pos::new(3, 12), Pos::new(3, 28)),
+                Span::new(Pos::new(7, 9), Pos::new(7, 25)),
+            ],
+            spans
+        );
+        let root_locations: Vec<_> = ctx.errors.iter().map(|e| e.root_location()).collect();
+        assert_eq!(
+            vec![
+                None,
+                Some((
+                    Span::new(Pos::new(5, 11), Pos::new(5, 23)),
+                    "workspace dependencies are defined here"
+                )),
+            ],
+            root_locations
+        );
+        assert_eq!(
+            vec!["`dependencies.toml.default-features` is ignored, because `workspace.dependencies.toml` enables default features"],
+            descriptions(&ctx.warnings),
+        );
+        let root_locations: Vec<_> = ctx.warnings.iter().map(|w| w.root_location()).collect();
+        assert_eq!(
+            vec![Some((
+                Span::new(Pos::new(7, 0), Pos::new(7, 4)),
+                "default features are enabled here"
+            ))],
+            root_locations
+        );
+        let deps: Vec<_> = inherited.dependencies.iter().map(|d| d.name).collect();
+        assert_eq!(vec!["toml"], deps);
+    });
+
+    let mut ctx = IdeDiagnostics::default();
+    let bump = Bump::new();
+    let tokens = ctx.lex(&bump, member);
+    let asts = ctx.parse(&bump, &tokens);
+    let map = ctx.map(&asts);
+    let inherited = resolve_inherited(&mut ctx, &map, None);
+    assert_eq!(Inherited::default(), inherited);
+    assert_eq!(
+        vec![
+            "`package.edition` is inherited from the workspace, but no workspace root was found",
+            "`dependencies.toml` is inherited from the workspace, but no workspace root was found",
+            "`dependencies.rand` is inherited from the workspace, but no workspace root was found",
+        ],
+        descriptions(&ctx.errors),
+    );
+}
+
+#[test]
+fn workspace_find_root_manifest() {
+    let dir = std::env::temp_dir().join(format!("crates-ide-find-root-{}", std::process::id()));
+    let package = |path: &str, text: &str| {
+        let path = dir.join(path);
+        std::fs::create_dir_all(&path).unwrap();
+        std::fs::write(path.join("Cargo.toml"), text).unwrap();
+    };
+    let root = r#"
+[workspace]
+members = ["crates/*", "excluded/kept"]
+exclude = ["excluded"]
+"#;
+    package("", root);
+    package("crates", "[package]\nname = \"a\"\n");
+    package("crates/member", "[package]\nname = \"member\"\n");
+    package(
+        "excluded/p",
+        "[package]\nname = \"p\"\nedition.workspace = true\n",
+    );
+    package("excluded/kept", "[package]\nname = \"kept\"\n");
+    package("other", "[workspace]\n");
+    package(
+        "crates/explicit",
+        "[package]\nname = \"explicit\"\nworkspace = \"../../other\"\n",
+    );
+    package(
+        "crates/invalid",
+        "[package]\nname = \"invalid\"\nworkspace = \"..\"\n",
+    );
+
+    let find = |path: &str| find_root_manifest(&dir.join(path).join("Cargo.toml"));
+    let member = find("crates/member");
+    let root_of_root = find("");
+    let excluded = find("excluded/p");
+    let kept = find("excluded/kept");
+    let explicit = find("crates/explicit");
+    let invalid = find("crates/invalid");
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(Some(dir.join("Cargo.toml")), member);
+    assert_eq!(Some(dir.join("Cargo.toml")), root_of_root);
+    assert_eq!(None, excluded);
+    assert_eq!(Some(dir.join("Cargo.toml")), kept);
+    assert_eq!(Some(dir.join("other/Cargo.toml")), explicit);
+    assert_eq!(None, invalid);
+}
+
+#[test]
+fn workspace_discover_members() {
+    let dir = std::env::temp_dir().join(format!("crates-ide-members-{}", std::process::id()));
+    let package = |path: &str, text: &str| {
+        let path = dir.join(path);
+        std::fs::create_dir_all(&path).unwrap();
+        std::fs::write(path.join("Cargo.toml"), text).unwrap();
+    };
+    let a = r#"
+[package]
+name = "a"
+
+[dependencies]
+a-macros = { path = "../../libs/a-macros" }
+outside = { path = "../../.." }
+
+[target.'cfg(unix)'.dev-dependencies]
+shared = { workspace = true }
+"#;
+    package("crates/a", a);
+    package(
+        "libs/a-macros",
+        "[package]\nname = \"a-macros\"\n\n[dependencies]\nutil = { path = \"../util\" }\n",
+    );
+    package("libs/util", "[package]\nname = \"util\"\n");
+    package("libs/shared", "[package]\nname = \"shared\"\n");
+    package("crates/b-1", "[package]\nname = \"b\"\n");
+    package("crates/old", "[package]\nname = \"old\"\n");
+    std::fs::create_dir_all(dir.join("crates/empty")).unwrap();
+    package("tools/x", "[package]\nname = \"x\"\n");
+    package("nested/ws", "[workspace]\n");
+    package("nested/ws/y", "[package]\nname = \"y\"\n");
+    package("target/debug/z", "[package]\nname = \"z\"\n");
+
+    let root = r#"
+[workspace]
+members = ["crates/[a-b]*", "crates/empty", "missing", "plugins/*"]
+exclude = ["crates/old"]
+default-members = ["crates/a", "tools/x"]
+
+[workspace.dependencies]
+shared = { path = "libs/shared" }
+"#;
+    std::fs::write(dir.join("Cargo.toml"), root).unwrap();
+
+    let mut members = Members::default();
+    check_str(root, |_, state| {
+        let mut ctx = IdeDiagnostics::default();
+        members = discover_members(&mut ctx, &dir.join("Cargo.toml"), &state);
+        assert_eq!(
+            vec![
+                "workspace member `crates/empty` has no `Cargo.toml`",
+                "workspace member `missing` doesn't exist",
+                "default member `tools/x` is not a workspace member",
+            ],
+            descriptions(&ctx.errors),
+        );
+        assert_eq!(
+            vec![
+                "`plugins/*` doesn't match any directory",
+                "package `tools/x` is nested in the workspace, but isn't a member",
+            ],
+            descriptions(&ctx.warnings),
+        );
+        let spans: Vec<_> = ctx.errors.iter().map(|e| e.span()).collect();
+        assert_eq!(
+            vec![
+                Span::new(Pos::new(2, 28), Pos::new(2, 42)),
+                Span::new(Pos::new(2, 44), Pos::new(2, 53)),
+                Span::new(Pos::new(4, 31), Pos::new(4, 40)),
+            ],
+            spans
+        );
+    });
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(
+        vec![
+            dir.join("crates/a/Cargo.toml"),
+            dir.join("crates/b-1/Cargo.toml"),
+            dir.join("libs/a-macros/Cargo.toml"),
+            dir.join("libs/shared/Cargo.toml"),
+            dir.join("libs/util/Cargo.toml"),
+        ],
+        members.manifests
+    );
+    assert_eq!(
+        vec![dir.join("crates/a/Cargo.toml")],
+        members.default_manifests
+    );
+}
+
+#[test]
+fn lints_valid() {
+    let input = r#"
+[lints.rust]
+unsafe_code = "forbid"
+unused = { level = "deny", priority = -1 }
+unexpected_cfgs = { level = "warn", check-cfg = ["cfg(fuzzing)"] }
+dead-code = "allow"
+
+[lints.clippy]
+pedantic = { level = "warn", priority = -1 }
+unwrap_used = "deny"
+
+[lints.rustdoc]
+broken_intra_doc_links = "deny"
+"#;
+    check_str(input, |ctx, state| {
+        assert_eq!(Vec::<Error>::new(), ctx.errors);
+        assert_eq!(Vec::<crate::Warning>::new(), ctx.warnings);
+        let lints: Vec<_> = (state.lints().iter())
+            .map(|l| (l.tool, l.name, l.level, l.priority))
+            .collect();
+        assert_eq!(
+            vec![
+                ("rust", "unsafe_code", Some(LintLevel::Forbid), 0),
+                ("rust", "unused", Some(LintLevel::Deny), -1),
+                ("rust", "unexpected_cfgs", Some(LintLevel::Warn), 0),
+                ("rust", "dead-code", Some(LintLevel::Allow), 0),
+                ("clippy", "pedantic", Some(LintLevel::Warn), -1),
+                ("clippy", "unwrap_used", Some(LintLevel::Deny), 0),
+                (
+                    "rustdoc",
+                    "broken_intra_doc_links",
+                    Some(LintLevel::Deny),
+                    0
+                ),
+            ],
+            lints
+        );
+    });
+}
+
+#[test]
+fn lints_invalid() {
+    let input = r#"
+[lints.rust]
+unsafe_code = "error"
+unused = { priority = 1 }
+dead_code = { level = "warn", priority = "1" }
+"clippy::all" = "warn"
+
+[lints.clippy]
+unwarp_used = "deny"
+not_a_lint_at_all = "deny"
+
+[lints.foo]
+bar = "warn"
+"#;
+    check_str(input, |ctx, _| {
+        assert_eq!(
+            vec![
+                "unknown lint level `error`, expected one of `forbid`, `deny`, `warn` or `allow`",
+                "`lints.rust.unused` is missing `level`",
+                "expected `lints.rust.dead_code.priority` to be of type int, found string",
+                "`lints.rust.'clippy::all'` is not a valid lint name, use `lints.clippy.all` instead",
+            ],
+            descriptions(&ctx.errors),
+        );
+        assert_eq!(
+            vec![
+                "unknown lint `lints.clippy.unwarp_used`",
+                "unknown lint `lints.clippy.not_a_lint_at_all`",
+                "unknown lint tool `foo`, expected one of `rust`, `clippy`, `rustdoc` or `cargo`",
+            ],
+            descriptions(&ctx.warnings),
+        );
+        let footers: Vec<_> = (ctx.warnings.iter())
+            .map(|w| {
+                w.footers()
+                    .into_iter()
+                    .map(|f| f.msg().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                vec!["did you mean `unwrap_used`".to_string()],
+                vec![],
+                vec![],
+            ],
+            footers
+        );
+        let spans: Vec<_> = ctx.warnings.iter().map(|w| w.span()).collect();
+        assert_eq!(
+            vec![
+                Span::new(Pos::new(8, 0), Pos::new(8, 11)),
+                Span::new(Pos::new(9, 0), Pos::new(9, 17)),
+                Span::new(Pos::new(11, 7), Pos::new(12, 12)),
+            ],
+            spans
+        );
+    });
+}
+
+#[test]
+fn lints_workspace() {
+    let input = "[lints]\nworkspace = true\nrust.unsafe_code = \"forbid\"\n";
+    check_str(input, |ctx, state| {
+        assert_eq!(
+            vec!["`lints.rust` cannot be specified, because the lints are inherited with `lints.workspace = true`"],
+            descriptions(&ctx.errors),
+        );
+        assert_eq!(Vec::<Lint>::new(), *state.lints());
+    });
+
+    let root = "[workspace.lints.clippy]\nall = \"warn\"\n";
+    check_member(root, "[lints]\nworkspace = true\n", |ctx, inherited| {
+        assert_eq!(Vec::<Error>::new(), ctx.errors);
+        let fields: Vec<_> = inherited.fields.iter().map(|f| f.key).collect();
+        assert_eq!(vec!["lints"], fields);
+    });
+    check_member(ROOT, "[lints]\nworkspace = true\n", |ctx, _| {
+        assert_eq!(
+            vec!["`lints` is inherited from the workspace, but `workspace.lints` doesn't exist"],
+            descriptions(&ctx.errors),
+        );
+    });
+}
+
+#[test]
+fn profile_valid() {
+    let input = r#"
+[profile.dev]
+opt-level = 1
+debug = "line-tables-only"
+split-debuginfo = "unpacked"
+incremental = true
+
+[profile.dev.package."*"]
+opt-level = "s"
+codegen-units = 16
+
+[profile.dev.build-override]
+opt-level = 3
+debug = false
+
+[profile.release]
+lto = "thin"
+strip = true
+panic = "abort"
+codegen-units = 1
+
+[profile.profiling]
+inherits = "release"
+debug = 2
+
+[profile.ci]
+inherits = "profiling"
+"#;
+    check_str(input, |ctx, state| {
+        assert_eq!(Vec::<Error>::new(), ctx.errors);
+        assert_eq!(Vec::<crate::Warning>::new(), ctx.warnings);
+        let profiles: Vec<_> = (state.profiles().iter())
+            .map(|p| (p.name, p.inherits.as_ref().map(|i| i.val.text)))
+            .collect();
+        assert_eq!(
+            vec![
+                ("dev", None),
+                ("release", None),
+                ("profiling", Some("release")),
+                ("ci", Some("profiling")),
+            ],
+            profiles
+        );
+    });
+}
+
+#[test]
+fn profile_invalid_values() {
+    let input = r#"
+[profile.dev]
+opt-level = 4
+debug = "yes"
+lto = 1
+strip = "all"
+codegen-units = 0
+rpath = "no"
+unknown = true
+
+[profile.test]
+panic = "abort"
+
+[profile.release.package.foo]
+panic = "abort"
+opt-level = "z"
+
+[profile.release.build-override]
+lto = true
+"#;
+    check_str(input, |ctx, _| {
+        assert_eq!(
+            vec![
+                "invalid value for `profile.dev.opt-level`, expected `0`, `1`, `2`, `3`, `\"s\"` or `\"z\"`",
+                "invalid value for `profile.dev.debug`, expected a bool, `0`, `1`, `2`, `\"none\"`, `\"line-directives-only\"`, `\"line-tables-only\"`, `\"limited\"` or `\"full\"`",
+                "invalid value for `profile.dev.lto`, expected a bool, `\"fat\"`, `\"thin\"` or `\"off\"`",
+                "invalid value for `profile.dev.strip`, expected a bool, `\"none\"`, `\"debuginfo\"` or `\"symbols\"`",
+                "invalid value for `profile.dev.codegen-units`, expected a positive integer",
+                "invalid value for `profile.dev.rpath`, expected a bool",
+                "`profile.release.package.foo.panic` cannot be specified in a `package` section",
+                "`profile.release.build-override.lto` cannot be specified in a `build-override` section",
+            ],
+            descriptions(&ctx.errors),
+        );
+        assert_eq!(
+            vec![
+                "unknown key `profile.dev.unknown` is ignored",
+                "`profile.test.panic` is ignored, because the `test` profile always unwinds",
+            ],
+            descriptions(&ctx.warnings),
+        );
+        let span = ctx.errors[0].span();
+        assert_eq!(Span::new(Pos::new(2, 0), Pos::new(2, 13)), span);
+    });
+}
+
+#[test]
+fn profile_custom() {
+    let input = r#"
+[profile.dev]
+inherits = "release"
+
+[profile.a]
+opt-level = 1
+
+[profile.b]
+inherits = "missing"
+
+[profile.c]
+inherits = "d"
+
+[profile.d]
+inherits = "c"
+
+[profile.e]
+inherits = "c"
+
+[profile.debug]
+inherits = "dev"
+
+[profile."my.profile"]
+inherits = "dev"
+"#;
+    check_str(input, |ctx, _| {
+        assert_eq!(
+            vec![
+                "`inherits` cannot be specified in the built-in profile `dev`",
+                "custom profile `profile.a` is missing `inherits`",
+                "profile name `debug` is reserved",
+                "invalid character `.` in profile name, only ASCII alphanumerics, `-` and `_` are allowed",
+                "`profile.b.inherits` refers to the profile `missing`, which doesn't exist",
+                "cyclic profile inheritance: `c` -> `d` -> `c`",
+            ],
+            descriptions(&ctx.errors),
+        );
+        let spans: Vec<_> = ctx.errors.iter().map(|e| e.span()).collect();
+        assert_eq!(
+            vec![
+                Span::new(Pos::new(2, 0), Pos::new(2, 20)),
+                Span::new(Pos::new(4, 9), Pos::new(4, 10)),
+                Span::new(Pos::new(19, 9), Pos::new(19, 14)),
+                Span::new(Pos::new(22, 12), Pos::new(22, 13)),
+                Span::new(Pos::new(8, 11), Pos::new(8, 20)),
+                Span::new(Pos::new(11, 11), Pos::new(11, 14)),
+            ],
+            spans
+        );
+    });
+}
+
+#[test]
+fn targets_valid() {
+    let input = r#"
+bench = [{ name = "speed" }]
+
+[features]
+extra = []
+
+[dependencies]
+serde = { version = "1", optional = true }
+
+[lib]
+name = "my_lib"
+crate-type = ["rlib", "cdylib"]
+doctest = false
+
+[[bin]]
+name = "my-bin"
+path = "src/main.rs"
+required-features = ["extra", "serde", "serde/derive"]
+
+[[example]]
+name = "demo"
+crate-type = ["staticlib"]
+
+[[test]]
+name = "integration"
+harness = false
+edition = "2021"
+"#;
+    check_str(input, |ctx, state| {
+        assert_eq!(Vec::<Error>::new(), ctx.errors);
+        assert_eq!(Vec::<Warning>::new(), ctx.warnings);
+        let targets: Vec<_> = (state.targets().iter())
+            .map(|t| (t.kind, t.name.as_ref().map(|n| n.val.text)))
+            .collect();
+        assert_eq!(
+            vec![
+                (TargetKind::Bench, Some("speed")),
+                (TargetKind::Lib, Some("my_lib")),
+                (TargetKind::Bin, Some("my-bin")),
+                (TargetKind::Example, Some("demo")),
+                (TargetKind::Test, Some("integration")),
+            ],
+            targets
+        );
+        let bin = &state.targets()[2];
+        let features: Vec<_> = bin.required_features.iter().map(|f| f.text).collect();
+        assert_eq!(vec!["extra", "serde", "serde/derive"], features);
+        assert_eq!(Some(Edition::E2021), state.targets()[4].edition);
+    });
+}
+
+#[test]
+fn targets_invalid() {
+    let input = r#"
+[lib]
+name = "my-lib"
+crate-type = ["proc-macro", "rlib", "exe"]
+required-features = ["a"]
+
+[[bin]]
+path = "src/main.rs"
+crate-type = ["bin"]
+
+[[bin]]
+name = "tool"
+required-features = ["missing"]
+plugin = true
+
+[[bin]]
+name = "tool"
+
+[[test]]
+name = ""
+"#;
+    check_str(input, |ctx, _| {
+        assert_eq!(
+            vec![
+                "invalid character `-` in target name, only alphanumerics, `-` and `_` are allowed",
+                "unknown crate type `exe`, expected one of `bin`, `lib`, `rlib`, `dylib`, `cdylib`, `staticlib` or `proc-macro`",
+                "a proc-macro library cannot have other crate types",
+                "target `bin[0]` is missing `name`",
+                "`bin[1].required-features[0]` requires `missing`, which is neither a feature nor an optional dependency",
+                "duplicate `bin` target name, target names must be unique",
+                "target name cannot be empty",
+            ],
+            descriptions(&ctx.errors),
+        );
+        let spans: Vec<_> = ctx.errors.iter().map(|e| e.span()).collect();
+        assert_eq!(
+            vec![
+                Span::new(Pos::new(2, 10), Pos::new(2, 11)),
+                Span::new(Pos::new(3, 36), Pos::new(3, 41)),
+                Span::new(Pos::new(3, 14), Pos::new(3, 26)),
+                Span::new(Pos::new(6, 0), Pos::new(6, 7)),
+                Span::new(Pos::new(12, 21), Pos::new(12, 30)),
+                Span::new(Pos::new(16, 7), Pos::new(16, 13)),
+                Span::new(Pos::new(19, 7), Pos::new(19, 9)),
+            ],
+            spans
+        );
+        let hint = ctx.errors[5].hint().map(|h| h.span());
+        assert_eq!(Some(Span::new(Pos::new(11, 7), Pos::new(11, 13))), hint);
+
+        assert_eq!(
+            vec![
+                "unknown key `lib.required-features` is ignored",
+                "unknown key `bin[0].crate-type` is ignored",
+                "`bin[1].plugin` is ignored, compiler plugins have been removed",
+            ],
+            descriptions(&ctx.warnings),
+        );
+    });
+}
+
+#[test]
+fn target_spec() {
+    let input = r#"
+[target.'cfg(all(unix, windows))'.dependencies]
+a = "1"
+
+[target."cfg(os = \"linux\")".dependencies]
+b = "1"
+
+[target.x86_64-pc-windows-msvc.dependencies]
+c = "1"
+
+[target.'cfg(unix'.dependencies]
+d = "1"
+"#;
+    check_str(input, |ctx, state| {
+        assert_eq!(
+            vec!["unclosed parenthesis in cfg expression"],
+            descriptions(&ctx.errors),
+        );
+        assert_eq!(
+            vec![
+                "cfg expression can never be true, `unix` and `windows` are mutually exclusive",
+                "unknown cfg key `os`",
+            ],
+            descriptions(&ctx.warnings),
+        );
+        let error_spans: Vec<_> = ctx.errors.iter().map(|e| e.span()).collect();
+        assert_eq!(
+            vec![Span::new(Pos::new(10, 12), Pos::new(10, 13))],
+            error_spans
+        );
+        let warning_spans: Vec<_> = ctx.warnings.iter().map(|w| w.span()).collect();
+        assert_eq!(
+            vec![
+                Span::new(Pos::new(1, 13), Pos::new(1, 31)),
+                Span::new(Pos::new(4, 13), Pos::new(4, 15)),
+            ],
+            warning_spans
+        );
+
+        let targets: Vec<_> = state.dependencies().iter().map(|d| d.target).collect();
+        assert_eq!(
+            vec![
+                Some("cfg(all(unix, windows))"),
+                Some("cfg(os = \"linux\")"),
+                Some("x86_64-pc-windows-msvc"),
+                Some("cfg(unix"),
+            ],
+            targets
+        );
+    });
+}
+
+#[test]
+fn dependency_is_active() {
+    let input = r#"
+[dependencies]
+a = "1"
+
+[target.'cfg(unix)'.dependencies]
+b = "1"
+
+[target.'cfg(windows)'.dependencies]
+c = "1"
+
+[target.x86_64-pc-windows-msvc.dependencies]
+d = "1"
+
+[target.'cfg(unix'.dependencies]
+e = "1"
+"#;
+    check_str(input, |_, state| {
+        let linux = cfg::Platform::builtin("x86_64-unknown-linux-gnu").unwrap();
+        let windows = cfg::Platform::builtin("x86_64-pc-windows-msvc").unwrap();
+        let active: Vec<_> = (state.dependencies().iter())
+            .map(|d| (d.name, d.is_active(&linux), d.is_active(&windows)))
+            .collect();
+        assert_eq!(
+            vec![
+                ("a", true, true),
+                ("b", true, false),
+                ("c", false, true),
+                ("d", false, true),
+                ("e", true, true),
+            ],
+            active
+        );
+        let specs: Vec<_> = (state.dependencies().iter())
+            .map(|d| d.target_spec.is_some())
+            .collect();
+        assert_eq!(vec![false, true, true, true, false], specs);
+    });
+}
+
+#[track_caller]
+fn check_library_lints(input: &str, implicit_lib: bool, expected: Vec<&str>) {
+    let mut ctx = IdeDiagnostics::default();
+    let bump = Bump::new();
+    let tokens = ctx.lex(&bump, input);
+    let asts = ctx.parse(&bump, &tokens);
+    let map = ctx.map(&asts);
+    let state = check(&mut ctx, &map);
+    lint_version_reqs(&mut ctx, &state, implicit_lib);
+    assert_eq!(expected, descriptions(&ctx.infos));
+}
+
+#[test]
+fn version_reqs_library() {
+    let package = r#"
+[package]
+name = "a"
+
+[dependencies]
+serde = "=1.2.3"
+
+[dev-dependencies]
+rand = "=0.8.5"
+"#;
+    let exact =
+        "exact version requirement in a library, this can cause resolution conflicts for dependents";
+
+    // A package with `src/lib.rs` is a library without a `[lib]` table.
+    check_library_lints(package, true, vec![exact]);
+    check_library_lints(package, false, vec![]);
+
+    let lib = format!("{package}\n[lib]\npath = \"lib.rs\"\n");
+    check_library_lints(&lib, false, vec![exact]);
+}
+
+#[test]
+fn dependency_ambigous_git_spec() {
+    let input = r#"
+[dependencies]
+a = { git = "https://github.com/a/a", branch = "main", tag = "v1", rev = "abc" }
+"#;
+    check_str(input, |ctx, _| {
+        assert_eq!(
+            vec!["dependency specification `dependencies.a` is ambigous, only one of `branch`, `tag` or `rev` is allowed"],
+            descriptions(&ctx.errors),
+        );
+        let error = &ctx.errors[0];
+        assert_eq!(Span::new(Pos::new(2, 38), Pos::new(2, 54)), error.span());
+        let labels: Vec<_> = error.labels().into_iter().map(|l| l.span).collect();
+        assert_eq!(
+            vec![
+                Span::new(Pos::new(2, 55), Pos::new(2, 66)),
+                Span::new(Pos::new(2, 67), Pos::new(2, 78)),
+            ],
+            labels
+        );
+    });
+}
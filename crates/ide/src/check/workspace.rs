@@ -0,0 +1,565 @@
+//! The `[workspace]` table of a root manifest, and the resolution of fields and dependencies that
+//! members inherit from it with `workspace = true`.
+
+use std::path::{Path, PathBuf};
+
+use bumpalo::Bump;
+use common::FmtStr;
+use toml::map::{self, MapNode, MapTable, MapTableEntry, Scalar};
+use toml::parse::StringVal;
+use toml::TomlCtx;
+
+use super::{
+    check_license_and_license_file, expect_string_array_in_table, expect_string_in_table,
+    expect_table_in_table, lints, parse_dependencies, parse_package_field, parse_resolver,
+    warn_unused, BoolAssignment, Dependency, DependencyKind, DependencySpec, Lint, Resolver, State,
+    StringArray, INHERITABLE_PACKAGE_KEYS,
+};
+use crate::{cargo, IdeCtx, IdeDiagnostics};
+
+pub use members::{discover_members, Members};
+
+mod members;
+
+#[derive(Debug, PartialEq)]
+pub struct Workspace<'a> {
+    /// The entire toml entry
+    entry: &'a MapTableEntry<'a>,
+    table: &'a MapTable<'a>,
+    members: Option<StringArray<'a>>,
+    exclude: Option<StringArray<'a>>,
+    default_members: Option<StringArray<'a>>,
+    resolver: Option<Resolver>,
+    /// `[workspace.dependencies]`
+    dependencies: Vec<Dependency<'a>>,
+    /// `[workspace.package]`
+    package: Option<&'a MapTable<'a>>,
+    /// `[workspace.lints]`
+    lints: Vec<Lint<'a>>,
+}
+
+impl<'a> Workspace<'a> {
+    /// The glob patterns of `workspace.members`.
+    pub fn members(&self) -> impl Iterator<Item = &'a StringVal<'a>> + '_ {
+        string_array_items(&self.members)
+    }
+
+    /// The glob patterns of `workspace.exclude`.
+    pub fn exclude(&self) -> impl Iterator<Item = &'a StringVal<'a>> + '_ {
+        string_array_items(&self.exclude)
+    }
+
+    /// The paths of `workspace.default-members`.
+    pub fn default_members(&self) -> impl Iterator<Item = &'a StringVal<'a>> + '_ {
+        string_array_items(&self.default_members)
+    }
+
+    pub fn resolver(&self) -> Option<Resolver> {
+        self.resolver
+    }
+
+    pub fn dependencies(&self) -> &[Dependency<'a>] {
+        &self.dependencies
+    }
+
+    pub fn dependency(&self, name: &str) -> Option<&Dependency<'a>> {
+        self.dependencies.iter().find(|d| d.name == name)
+    }
+
+    pub fn lints(&self) -> &[Lint<'a>] {
+        &self.lints
+    }
+
+    /// An entry of `[workspace.package]`.
+    pub fn package_field(&self, key: &str) -> Option<&'a MapTableEntry<'a>> {
+        let (_, entry) = self.package?.iter().find(|(k, _)| **k == key)?;
+        Some(entry)
+    }
+}
+
+fn string_array_items<'a, 'b>(
+    array: &'b Option<StringArray<'a>>,
+) -> impl Iterator<Item = &'a StringVal<'a>> + 'b {
+    array.iter().flat_map(|a| a.items.iter().map(|(_, s)| *s))
+}
+
+/// Fields and dependencies of a member that are inherited from the workspace.
+#[derive(Debug, Default, PartialEq)]
+pub struct Inherited<'a, 'w> {
+    pub fields: Vec<InheritedField<'a, 'w>>,
+    pub dependencies: Vec<InheritedDependency<'a, 'w>>,
+}
+
+/// ```toml
+/// [package]
+/// <key> = { workspace = true }
+///
+/// [lints]
+/// workspace = true
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct InheritedField<'a, 'w> {
+    pub key: &'static str,
+    /// The entry in the member's `[package]` table, or the member's `[lints]` table.
+    pub member: &'a MapTableEntry<'a>,
+    /// The entry in `[workspace.package]`, or the `[workspace.lints]` table.
+    pub workspace: &'w MapTableEntry<'w>,
+}
+
+/// ```toml
+/// [dependencies]
+/// <name> = { workspace = true }
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct InheritedDependency<'a, 'w> {
+    pub name: &'a str,
+    pub kind: DependencyKind,
+    pub target: Option<&'a str>,
+    /// The entry in the member's dependency table.
+    pub member: &'a MapTableEntry<'a>,
+    /// The dependency in `[workspace.dependencies]`.
+    pub workspace: &'w Dependency<'w>,
+}
+
+/// Find the root manifest of the workspace that contains `manifest`. This is the manifest itself
+/// if it has a `[workspace]` table, or the root at `package.workspace` if that is set. Otherwise
+/// it's the closest `Cargo.toml` in the parent directories with a `[workspace]` table that doesn't
+/// exclude `manifest`.
+pub fn find_root_manifest(manifest: &Path) -> Option<PathBuf> {
+    let manifest = std::path::absolute(manifest).ok()?;
+    let manifest_dir = manifest.parent()?;
+    if let Ok(text) = std::fs::read_to_string(&manifest) {
+        let (is_root, explicit_root) = with_manifest(&text, |map, state| {
+            (state.workspace().is_some(), package_workspace(map))
+        });
+        if is_root {
+            return Some(manifest);
+        }
+        if let Some(root) = explicit_root {
+            let root = members::normalize(&manifest_dir.join(root).join("Cargo.toml"));
+            let text = std::fs::read_to_string(&root).ok()?;
+            let is_root = manifest_has_tables(&text, ["workspace"]) == [true];
+            return is_root.then_some(root);
+        }
+    }
+
+    for dir in manifest_dir.ancestors().skip(1) {
+        let path = dir.join("Cargo.toml");
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let is_root = with_manifest(&text, |_, state| {
+            let workspace = state.workspace()?;
+            Some(!is_excluded(workspace, dir, manifest_dir))
+        });
+        if is_root == Some(true) {
+            return Some(path);
+        }
+    }
+    None
+}
+
+/// The `package.workspace` path of a member.
+fn package_workspace(map: &MapTable) -> Option<String> {
+    let MapNode::Table(package) = &map.get("package")?.node else {
+        return None;
+    };
+    match &package.get("workspace")?.node {
+        MapNode::Scalar(Scalar::String(str)) => Some(str.text.to_string()),
+        _ => None,
+    }
+}
+
+/// Whether `dir` is excluded from the workspace at `root_dir`. Paths listed in `members` take
+/// precedence over `exclude`, like in cargo.
+fn is_excluded(workspace: &Workspace, root_dir: &Path, dir: &Path) -> bool {
+    let covers = |s: &StringVal| dir.starts_with(members::normalize(&root_dir.join(s.text)));
+    let explicit_member = workspace.members().any(covers);
+    !explicit_member && workspace.exclude().any(covers)
+}
+
+fn with_manifest<R>(text: &str, f: impl FnOnce(&MapTable, &State) -> R) -> R {
+    let mut ctx = IdeDiagnostics::default();
+    let bump = Bump::new();
+    let tokens = ctx.lex(&bump, text);
+    let asts = ctx.parse(&bump, &tokens);
+    let map = ctx.map(&asts);
+    let state = ctx.check(&map);
+    f(&map, &state)
+}
+
+/// Whether the manifest has each of the toplevel tables.
+fn manifest_has_tables<const LEN: usize>(text: &str, keys: [&str; LEN]) -> [bool; LEN] {
+    let mut ctx = IdeDiagnostics::default();
+    let bump = Bump::new();
+    let tokens = ctx.lex(&bump, text);
+    let asts = ctx.parse(&bump, &tokens);
+    let map = ctx.map(&asts);
+    keys.map(|key| matches!(map.get(key), Some(e) if matches!(e.node, MapNode::Table(_))))
+}
+
+pub(super) fn parse_workspace<'a>(
+    ctx: &mut impl IdeCtx,
+    path: &map::Path<'a, '_>,
+    entry: &'a MapTableEntry<'a>,
+    table: &'a MapTable<'a>,
+) -> Workspace<'a> {
+    let mut workspace = Workspace {
+        entry,
+        table,
+        members: None,
+        exclude: None,
+        default_members: None,
+        resolver: None,
+        dependencies: Vec::new(),
+        package: None,
+        lints: Vec::new(),
+    };
+    for (key, entry) in table.iter() {
+        let path = path.append_key(&entry.reprs);
+        match *key {
+            "members" => workspace.members = expect_string_array_in_table(ctx, &path, entry),
+            "exclude" => workspace.exclude = expect_string_array_in_table(ctx, &path, entry),
+            "default-members" => {
+                workspace.default_members = expect_string_array_in_table(ctx, &path, entry)
+            }
+            "resolver" => {
+                if let Some(str) = expect_string_in_table(ctx, &path, entry) {
+                    workspace.resolver = parse_resolver(ctx, &path, &str);
+                }
+            }
+            "dependencies" => {
+                if let Some(table) = expect_table_in_table(ctx, &path, entry) {
+                    workspace.dependencies = parse_workspace_dependencies(ctx, &path, table);
+                }
+            }
+            "package" => {
+                if let Some(table) = expect_table_in_table(ctx, &path, entry) {
+                    parse_workspace_package(ctx, &path, table);
+                    workspace.package = Some(table);
+                }
+            }
+            "lints" => {
+                if let Some(table) = expect_table_in_table(ctx, &path, entry) {
+                    workspace.lints = lints::parse_lints(ctx, &path, table, false);
+                }
+            }
+            // Arbitrary data for external tools.
+            "metadata" => (),
+            _ => warn_unused(ctx, &path, entry),
+        }
+    }
+    workspace
+}
+
+fn parse_workspace_dependencies<'a>(
+    ctx: &mut impl IdeCtx,
+    path: &map::Path<'a, '_>,
+    table: &'a MapTable<'a>,
+) -> Vec<Dependency<'a>> {
+    let mut state = State::default();
+    parse_dependencies(ctx, &mut state, path, table, DependencyKind::Normal, None);
+
+    for dep in state.dependencies.iter() {
+        let path = path.append_key(&dep.entry.reprs);
+        if let Some(optional) = &dep.optional {
+            ctx.error(cargo::Error::new(
+                path.context_lines([optional.repr.parent]),
+                path.fmt_path(),
+                optional.span(),
+                cargo::ErrorKind::WorkspaceDepOptional,
+            ));
+        }
+        if let DependencySpec::Workspace(workspace) = &dep.spec {
+            ctx.error(cargo::Error::new(
+                path.context_lines([workspace.repr.parent]),
+                path.fmt_path(),
+                workspace.span(),
+                cargo::ErrorKind::WorkspaceDepInherited,
+            ));
+        }
+    }
+
+    state.dependencies
+}
+
+/// Only the inheritable keys are allowed, and they can't be inherited themselves.
+fn parse_workspace_package<'a>(
+    ctx: &mut impl IdeCtx,
+    path: &map::Path<'a, '_>,
+    table: &'a MapTable<'a>,
+) {
+    let mut state = State::default();
+    for (key, entry) in table.iter() {
+        let path = path.append_key(&entry.reprs);
+        if INHERITABLE_PACKAGE_KEYS.contains(key) {
+            parse_package_field(ctx, &mut state, &path, key, entry);
+        } else {
+            warn_unused(ctx, &path, entry);
+        }
+    }
+    check_license_and_license_file(ctx, path, table);
+}
+
+/// Dependency tables and the newer key that takes precedence over deprecated ones.
+const DEPENDENCY_TABLES: [(&str, Option<&str>, DependencyKind); 5] = [
+    ("dependencies", None, DependencyKind::Normal),
+    ("dev-dependencies", None, DependencyKind::Dev),
+    (
+        "dev_dependencies",
+        Some("dev-dependencies"),
+        DependencyKind::Dev,
+    ),
+    ("build-dependencies", None, DependencyKind::Build),
+    (
+        "build_dependencies",
+        Some("build-dependencies"),
+        DependencyKind::Build,
+    ),
+];
+
+/// Resolve the fields and dependencies of a member manifest that are inherited with
+/// `workspace = true` against the `workspace` of the root manifest. Errors with the datatypes of
+/// the member manifest are reported by [`super::check`] and are ignored here.
+pub fn resolve_inherited<'a, 'w>(
+    ctx: &mut impl IdeCtx,
+    table: &'a MapTable<'a>,
+    workspace: Option<&'w Workspace<'w>>,
+) -> Inherited<'a, 'w> {
+    let mut inherited = Inherited::default();
+
+    if let Some(entry) = table.get("package") {
+        if let MapNode::Table(package) = &entry.node {
+            let path = map::Path::root(&entry.reprs);
+            resolve_fields(ctx, &mut inherited, &path, package, workspace);
+        }
+    }
+
+    if let Some(entry) = table.get("lints") {
+        resolve_lints(ctx, &mut inherited, entry, workspace);
+    }
+
+    for (key, new, kind) in DEPENDENCY_TABLES {
+        if new.is_some_and(|new| table.get(new).is_some()) {
+            continue;
+        }
+        let Some(entry) = table.get(key) else {
+            continue;
+        };
+        if let MapNode::Table(deps) = &entry.node {
+            let path = map::Path::root(&entry.reprs);
+            resolve_dependencies(ctx, &mut inherited, &path, deps, kind, None, workspace);
+        }
+    }
+
+    if let Some(entry) = table.get("target") {
+        if let MapNode::Table(targets) = &entry.node {
+            let path = map::Path::root(&entry.reprs);
+            for (&target, entry) in targets.iter() {
+                let MapNode::Table(target_table) = &entry.node else {
+                    continue;
+                };
+                let path = path.append_key(&entry.reprs);
+                for (key, new, kind) in DEPENDENCY_TABLES {
+                    if new.is_some_and(|new| target_table.get(new).is_some()) {
+                        continue;
+                    }
+                    let Some(entry) = target_table.get(key) else {
+                        continue;
+                    };
+                    if let MapNode::Table(deps) = &entry.node {
+                        let path = path.append_key(&entry.reprs);
+                        let target = Some(target);
+                        resolve_dependencies(
+                            ctx,
+                            &mut inherited,
+                            &path,
+                            deps,
+                            kind,
+                            target,
+                            workspace,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    inherited
+}
+
+fn resolve_fields<'a, 'w>(
+    ctx: &mut impl IdeCtx,
+    inherited: &mut Inherited<'a, 'w>,
+    path: &map::Path,
+    package: &'a MapTable<'a>,
+    workspace: Option<&'w Workspace<'w>>,
+) {
+    for key in INHERITABLE_PACKAGE_KEYS {
+        let Some(entry) = package.get(key) else {
+            continue;
+        };
+        let Some(inherit) = inherits_workspace(entry) else {
+            continue;
+        };
+        let path = path.append_key(&entry.reprs);
+
+        let kind = match workspace {
+            Some(workspace) => match workspace.package_field(key) {
+                Some(workspace) => {
+                    inherited.fields.push(InheritedField {
+                        key,
+                        member: entry,
+                        workspace,
+                    });
+                    continue;
+                }
+                None => cargo::ErrorKind::InheritedFieldNotFound(key),
+            },
+            None => cargo::ErrorKind::NoWorkspaceRoot,
+        };
+        ctx.error(cargo::Error::new(
+            path.context_lines([inherit.repr.parent]),
+            path.fmt_path(),
+            inherit.span(),
+            kind,
+        ));
+    }
+}
+
+fn resolve_lints<'a, 'w>(
+    ctx: &mut impl IdeCtx,
+    inherited: &mut Inherited<'a, 'w>,
+    entry: &'a MapTableEntry<'a>,
+    workspace: Option<&'w Workspace<'w>>,
+) {
+    let Some(inherit) = inherits_workspace(entry) else {
+        return;
+    };
+    let path = map::Path::root(&entry.reprs);
+
+    let kind = match workspace {
+        Some(workspace) => match workspace.table.get("lints") {
+            Some(workspace) => {
+                inherited.fields.push(InheritedField {
+                    key: "lints",
+                    member: entry,
+                    workspace,
+                });
+                return;
+            }
+            None => cargo::ErrorKind::InheritedLintsNotFound,
+        },
+        None => cargo::ErrorKind::NoWorkspaceRoot,
+    };
+    ctx.error(cargo::Error::new(
+        path.context_lines([inherit.repr.parent]),
+        path.fmt_path(),
+        inherit.span(),
+        kind,
+    ));
+}
+
+fn resolve_dependencies<'a, 'w>(
+    ctx: &mut impl IdeCtx,
+    inherited: &mut Inherited<'a, 'w>,
+    path: &map::Path,
+    deps: &'a MapTable<'a>,
+    kind: DependencyKind,
+    target: Option<&'a str>,
+    workspace: Option<&'w Workspace<'w>>,
+) {
+    for (&name, entry) in deps.iter() {
+        let Some(inherit) = inherits_workspace(entry) else {
+            continue;
+        };
+        let path = path.append_key(&entry.reprs);
+
+        let Some(workspace) = workspace else {
+            ctx.error(cargo::Error::new(
+                path.context_lines([inherit.repr.parent]),
+                path.fmt_path(),
+                inherit.span(),
+                cargo::ErrorKind::NoWorkspaceRoot,
+            ));
+            continue;
+        };
+        let Some(workspace_dep) = workspace.dependency(name) else {
+            let root_entry = workspace
+                .table
+                .get("dependencies")
+                .unwrap_or(workspace.entry);
+            ctx.error(cargo::Error::new(
+                path.context_lines([inherit.repr.parent]),
+                path.fmt_path(),
+                inherit.span(),
+                cargo::ErrorKind::InheritedDepNotFound {
+                    name: FmtStr::from_str(name),
+                    root_span: root_entry.reprs.first().key.repr_ident().lit_span(),
+                },
+            ));
+            continue;
+        };
+
+        // Features are additive and `optional` can only be specified by members, but default
+        // features can't be disabled if the workspace dependency enables them.
+        let workspace_default = (workspace_dep.features.default.as_ref()).is_none_or(|d| d.val.val);
+        if let Some(default) = disables_default_features(entry) {
+            if workspace_default {
+                ctx.warn(cargo::Warning::new(
+                    path.context_lines([default.repr.parent]),
+                    path.joined_path(default.ident()),
+                    default.span(),
+                    cargo::WarningKind::InheritedDefaultFeaturesIgnored {
+                        name: FmtStr::from_str(name),
+                        root_span: workspace_dep
+                            .entry
+                            .reprs
+                            .first()
+                            .key
+                            .repr_ident()
+                            .lit_span(),
+                    },
+                ));
+            }
+        }
+
+        inherited.dependencies.push(InheritedDependency {
+            name,
+            kind,
+            target,
+            member: entry,
+            workspace: workspace_dep,
+        });
+    }
+}
+
+/// `{ workspace = true }`
+fn inherits_workspace<'a>(entry: &'a MapTableEntry<'a>) -> Option<BoolAssignment<'a>> {
+    let MapNode::Table(table) = &entry.node else {
+        return None;
+    };
+    let entry = table.get("workspace")?;
+    match &entry.node {
+        MapNode::Scalar(Scalar::Bool(val)) if val.val => Some(BoolAssignment {
+            repr: entry.reprs.first(),
+            val,
+        }),
+        _ => None,
+    }
+}
+
+/// `{ default-features = false }`
+fn disables_default_features<'a>(entry: &'a MapTableEntry<'a>) -> Option<BoolAssignment<'a>> {
+    let MapNode::Table(table) = &entry.node else {
+        return None;
+    };
+    let entry = (table.get("default-features")).or_else(|| table.get("default_features"))?;
+    match &entry.node {
+        MapNode::Scalar(Scalar::Bool(val)) if !val.val => Some(BoolAssignment {
+            repr: entry.reprs.first(),
+            val,
+        }),
+        _ => None,
+    }
+}
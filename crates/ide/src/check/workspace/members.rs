@@ -0,0 +1,392 @@
+//! Discovery of the workspace members, by expanding the glob patterns of `workspace.members`
+//! against the file system and following the path dependencies of the members.
+
+use std::path::{Component, Path, PathBuf};
+
+use bumpalo::Bump;
+use common::{FmtStr, Span};
+use toml::map::{self, MapTableEntry};
+use toml::TomlCtx;
+
+use super::{manifest_has_tables, Dependency, DependencySpec, State, Workspace};
+use crate::{cargo, IdeCtx, IdeDiagnostics};
+
+/// The members of a workspace.
+#[derive(Debug, Default, PartialEq)]
+pub struct Members {
+    /// The manifests of all members, including the root package, sorted by path.
+    pub manifests: Vec<PathBuf>,
+    /// The manifests of `workspace.default-members`. If that isn't set, this is the root
+    /// package, or all members in a virtual workspace.
+    pub default_manifests: Vec<PathBuf>,
+}
+
+/// Find the members of the workspace defined by the root manifest at `root_manifest`. The
+/// diagnostics are reported in the root manifest.
+pub fn discover_members(ctx: &mut impl IdeCtx, root_manifest: &Path, root: &State) -> Members {
+    let Some(workspace) = root.workspace() else {
+        return Members::default();
+    };
+    let Some(root_dir) = std::path::absolute(root_manifest)
+        .ok()
+        .and_then(|m| m.parent().map(normalize))
+    else {
+        return Members::default();
+    };
+    let ws_path = map::Path::root(&workspace.entry.reprs);
+
+    let excluded: Vec<PathBuf> = (workspace.exclude())
+        .map(|s| normalize(&root_dir.join(s.text)))
+        .collect();
+    let is_excluded = |dir: &Path| excluded.iter().any(|e| dir.starts_with(e));
+
+    let mut dirs = Vec::new();
+    if root.package_name.is_some() {
+        dirs.push(root_dir.clone());
+    }
+
+    if let (Some(entry), Some(array)) = (workspace.table.get("members"), &workspace.members) {
+        let path = ws_path.append_key(&entry.reprs);
+        for &(i, pattern) in array.items.iter() {
+            let path = path.append_index(i);
+            let lines = || map::context_lines(path.prev, [array.parent]);
+            let error = |kind| cargo::Error::new(lines(), path.fmt_path(), pattern.lit_span, kind);
+
+            if is_glob(pattern.text) {
+                let mut matched = expand_pattern(&root_dir, pattern.text);
+                matched.retain(|dir| !is_excluded(dir));
+                if matched.is_empty() {
+                    ctx.warn(cargo::Warning::new(
+                        lines(),
+                        path.fmt_path(),
+                        pattern.lit_span,
+                        cargo::WarningKind::MemberPatternNoMatch(FmtStr::from_str(pattern.text)),
+                    ));
+                }
+                for dir in matched {
+                    if dir.join("Cargo.toml").is_file() {
+                        dirs.push(dir);
+                    } else {
+                        let dir = FmtStr::from_string(relative_path(&root_dir, &dir));
+                        ctx.error(error(cargo::ErrorKind::MemberMissingManifest(dir)));
+                    }
+                }
+            } else {
+                let dir = normalize(&root_dir.join(pattern.text));
+                let text = || FmtStr::from_str(pattern.text);
+                if !dir.is_dir() {
+                    ctx.error(error(cargo::ErrorKind::MemberNotFound(text())));
+                } else if !dir.join("Cargo.toml").is_file() {
+                    ctx.error(error(cargo::ErrorKind::MemberMissingManifest(text())));
+                } else {
+                    dirs.push(dir);
+                }
+            }
+        }
+    }
+    dirs.sort();
+    dirs.dedup();
+
+    // Path dependencies of members that are inside the workspace are members too.
+    let mut queue = dirs.clone();
+    while let Some(dir) = queue.pop() {
+        for dep_dir in path_dependencies(root, workspace, &root_dir, &dir) {
+            let is_member = dep_dir.starts_with(&root_dir)
+                && !is_excluded(&dep_dir)
+                && dep_dir.join("Cargo.toml").is_file();
+            if is_member && !dirs.contains(&dep_dir) {
+                dirs.push(dep_dir.clone());
+                queue.push(dep_dir);
+            }
+        }
+    }
+    dirs.sort();
+
+    let default_dirs = default_members(ctx, &ws_path, workspace, &root_dir, &dirs);
+    let default_dirs = match default_dirs {
+        Some(default_dirs) => default_dirs,
+        None if root.package_name.is_some() => vec![root_dir.clone()],
+        None => dirs.clone(),
+    };
+
+    let mut nested = Vec::new();
+    find_nested_packages(&root_dir, &mut nested);
+    for dir in nested {
+        if dirs.contains(&dir) || is_excluded(&dir) {
+            continue;
+        }
+        let entry = workspace.table.get("members");
+        let (path, entry) = match entry {
+            Some(entry) => (ws_path.append_key(&entry.reprs), entry),
+            None => (map::Path::root(&workspace.entry.reprs), workspace.entry),
+        };
+        let (lines, span) = entry_location(&path, entry);
+        let dir = FmtStr::from_string(relative_path(&root_dir, &dir));
+        ctx.warn(cargo::Warning::new(
+            lines,
+            path.fmt_path(),
+            span,
+            cargo::WarningKind::UnlistedPackage(dir),
+        ));
+    }
+
+    let manifest = |dir: PathBuf| dir.join("Cargo.toml");
+    Members {
+        manifests: dirs.into_iter().map(manifest).collect(),
+        default_manifests: default_dirs.into_iter().map(manifest).collect(),
+    }
+}
+
+/// Returns `None` if `workspace.default-members` isn't set.
+fn default_members(
+    ctx: &mut impl IdeCtx,
+    ws_path: &map::Path,
+    workspace: &Workspace,
+    root_dir: &Path,
+    members: &[PathBuf],
+) -> Option<Vec<PathBuf>> {
+    let entry = workspace.table.get("default-members")?;
+    let array = workspace.default_members.as_ref()?;
+    let path = ws_path.append_key(&entry.reprs);
+
+    let mut default_dirs = Vec::new();
+    for &(i, pattern) in array.items.iter() {
+        let path = path.append_index(i);
+        let lines = || map::context_lines(path.prev, [array.parent]);
+
+        let matched = if is_glob(pattern.text) {
+            expand_pattern(root_dir, pattern.text)
+        } else {
+            vec![normalize(&root_dir.join(pattern.text))]
+        };
+        if matched.is_empty() {
+            ctx.warn(cargo::Warning::new(
+                lines(),
+                path.fmt_path(),
+                pattern.lit_span,
+                cargo::WarningKind::MemberPatternNoMatch(FmtStr::from_str(pattern.text)),
+            ));
+        }
+        for dir in matched {
+            if members.contains(&dir) {
+                default_dirs.push(dir);
+            } else {
+                let dir = FmtStr::from_string(relative_path(root_dir, &dir));
+                ctx.error(cargo::Error::new(
+                    lines(),
+                    path.fmt_path(),
+                    pattern.lit_span,
+                    cargo::ErrorKind::DefaultMemberNotMember(dir),
+                ));
+            }
+        }
+    }
+    default_dirs.sort();
+    default_dirs.dedup();
+    Some(default_dirs)
+}
+
+/// The directories of the path dependencies of the package in `dir`, including dependencies
+/// inherited from the workspace. The root package is taken from `root`, other packages are
+/// read from the file system.
+fn path_dependencies(
+    root: &State,
+    workspace: &Workspace,
+    root_dir: &Path,
+    dir: &Path,
+) -> Vec<PathBuf> {
+    if dir == root_dir {
+        return dependency_dirs(root.dependencies(), workspace, root_dir, dir);
+    }
+
+    let Ok(text) = std::fs::read_to_string(dir.join("Cargo.toml")) else {
+        return Vec::new();
+    };
+    let mut ctx = IdeDiagnostics::default();
+    let bump = Bump::new();
+    let tokens = ctx.lex(&bump, &text);
+    let asts = ctx.parse(&bump, &tokens);
+    let map = ctx.map(&asts);
+    let state = ctx.check(&map);
+    dependency_dirs(state.dependencies(), workspace, root_dir, dir)
+}
+
+fn dependency_dirs(
+    dependencies: &[Dependency],
+    workspace: &Workspace,
+    root_dir: &Path,
+    dir: &Path,
+) -> Vec<PathBuf> {
+    let path_dir = |base: &Path, spec: &DependencySpec| match spec {
+        DependencySpec::Path { path, .. } => Some(normalize(&base.join(path.val.text))),
+        _ => None,
+    };
+    (dependencies.iter())
+        .filter_map(|dep| match &dep.spec {
+            DependencySpec::Workspace(inherit) if inherit.val.val => {
+                let ws_dep = workspace.dependency(dep.name)?;
+                path_dir(root_dir, &ws_dep.spec)
+            }
+            spec => path_dir(dir, spec),
+        })
+        .collect()
+}
+
+fn entry_location(path: &map::Path, entry: &MapTableEntry) -> (Box<[u32]>, Span) {
+    let repr = entry.reprs.first();
+    (
+        map::context_lines(path.prev, [repr.parent]),
+        repr.repr_span(),
+    )
+}
+
+/// Packages in subdirectories of `dir`, skipping hidden directories, `target` directories and
+/// nested workspaces.
+fn find_nested_packages(dir: &Path, packages: &mut Vec<PathBuf>) {
+    for sub in subdirs(dir) {
+        let name = sub.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if name.starts_with('.') || name == "target" {
+            continue;
+        }
+
+        if let Ok(text) = std::fs::read_to_string(sub.join("Cargo.toml")) {
+            let [workspace, package] = manifest_has_tables(&text, ["workspace", "package"]);
+            if workspace {
+                continue;
+            }
+            if package {
+                packages.push(sub.clone());
+            }
+        }
+        find_nested_packages(&sub, packages);
+    }
+}
+
+fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '['])
+}
+
+/// Expand a glob pattern relative to `root_dir` to the directories it matches.
+fn expand_pattern(root_dir: &Path, pattern: &str) -> Vec<PathBuf> {
+    let components: Vec<&str> = (pattern.split(['/', '\\']))
+        .filter(|c| !c.is_empty() && *c != ".")
+        .collect();
+    let mut dirs = Vec::new();
+    expand(root_dir, &components, &mut dirs);
+    dirs.sort();
+    dirs.dedup();
+    dirs
+}
+
+fn expand(dir: &Path, components: &[&str], dirs: &mut Vec<PathBuf>) {
+    let Some((&first, rest)) = components.split_first() else {
+        if dir.is_dir() {
+            dirs.push(normalize(dir));
+        }
+        return;
+    };
+
+    if first == "**" {
+        expand(dir, rest, dirs);
+        for sub in subdirs(dir) {
+            expand(&sub, components, dirs);
+        }
+    } else if is_glob(first) {
+        let pattern: Vec<char> = first.chars().collect();
+        for sub in subdirs(dir) {
+            let Some(name) = sub.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let name: Vec<char> = name.chars().collect();
+            if glob_matches(&pattern, &name) {
+                expand(&sub, rest, dirs);
+            }
+        }
+    } else {
+        expand(&dir.join(first), rest, dirs);
+    }
+}
+
+/// Match a single path component against a glob pattern supporting `*`, `?` and character
+/// classes like `[a-z]` or `[!a]`.
+fn glob_matches(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|i| glob_matches(rest, &name[i..])),
+        Some(('?', rest)) => !name.is_empty() && glob_matches(rest, &name[1..]),
+        Some(('[', rest)) => {
+            // A `]` directly after the opening bracket is part of the class.
+            let end = rest.iter().skip(1).position(|&c| c == ']').map(|i| i + 1);
+            let Some(end) = end else {
+                // Unclosed, match `[` literally.
+                return name.first() == Some(&'[') && glob_matches(rest, &name[1..]);
+            };
+            let Some((&c, name_rest)) = name.split_first() else {
+                return false;
+            };
+            let (negated, class) = match &rest[..end] {
+                ['!', class @ ..] => (true, class),
+                class => (false, class),
+            };
+            class_contains(class, c) != negated && glob_matches(&rest[end + 1..], name_rest)
+        }
+        Some((c, rest)) => name.first() == Some(c) && glob_matches(rest, &name[1..]),
+    }
+}
+
+fn class_contains(class: &[char], c: char) -> bool {
+    let mut i = 0;
+    while i < class.len() {
+        if i + 2 < class.len() && class[i + 1] == '-' {
+            if (class[i]..=class[i + 2]).contains(&c) {
+                return true;
+            }
+            i += 3;
+        } else {
+            if class[i] == c {
+                return true;
+            }
+            i += 1;
+        }
+    }
+    false
+}
+
+/// The subdirectories of `dir`, sorted by name.
+fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = (entries.flatten())
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .map(|e| e.path())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+/// Lexically resolve `.` and `..` components.
+pub(super) fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            c => normalized.push(c),
+        }
+    }
+    normalized
+}
+
+/// The path of `dir` relative to `root_dir`, using `/` as separator.
+fn relative_path(root_dir: &Path, dir: &Path) -> String {
+    match dir.strip_prefix(root_dir) {
+        Ok(relative) => {
+            let components: Vec<_> = relative.iter().map(|c| c.to_string_lossy()).collect();
+            components.join("/")
+        }
+        Err(_) => dir.display().to_string(),
+    }
+}
@@ -1,4 +1,4 @@
-use common::diagnostic::{Diagnostic, DiagnosticHint, Severity};
+use common::diagnostic::{Diagnostic, DiagnosticHint, Footer, Label, Severity};
 use common::Span;
 
 use crate::cargo;
@@ -10,6 +10,16 @@ pub enum Error {
     Cargo(cargo::Error),
 }
 
+impl Error {
+    /// See [`cargo::Error::root_location`].
+    pub fn root_location(&self) -> Option<(Span, &'static str)> {
+        match self {
+            Error::Cargo(e) => e.root_location(),
+            _ => None,
+        }
+    }
+}
+
 impl Diagnostic for Error {
     type Hint = Hint;
 
@@ -54,6 +64,22 @@ impl Diagnostic for Error {
             Error::Cargo(e) => e.context_lines(),
         }
     }
+
+    fn labels(&self) -> Vec<Label> {
+        match self {
+            Error::Toml(e) => e.labels(),
+            Error::Semver(e) => e.labels(),
+            Error::Cargo(e) => e.labels(),
+        }
+    }
+
+    fn footers(&self) -> Vec<Footer> {
+        match self {
+            Error::Toml(e) => e.footers(),
+            Error::Semver(e) => e.footers(),
+            Error::Cargo(e) => e.footers(),
+        }
+    }
 }
 
 impl From<toml::Error> for Error {
@@ -81,6 +107,16 @@ pub enum Warning {
     Cargo(cargo::Warning),
 }
 
+impl Warning {
+    /// See [`cargo::Warning::root_location`].
+    pub fn root_location(&self) -> Option<(Span, &'static str)> {
+        match self {
+            Warning::Cargo(w) => w.root_location(),
+            _ => None,
+        }
+    }
+}
+
 impl Diagnostic for Warning {
     type Hint = Hint;
 
@@ -125,6 +161,22 @@ impl Diagnostic for Warning {
             Warning::Cargo(w) => w.context_lines(),
         }
     }
+
+    fn labels(&self) -> Vec<Label> {
+        match self {
+            Warning::Toml(w) => w.labels(),
+            Warning::Semver(w) => w.labels(),
+            Warning::Cargo(w) => w.labels(),
+        }
+    }
+
+    fn footers(&self) -> Vec<Footer> {
+        match self {
+            Warning::Toml(w) => w.footers(),
+            Warning::Semver(w) => w.footers(),
+            Warning::Cargo(w) => w.footers(),
+        }
+    }
 }
 
 impl From<toml::Warning> for Warning {
@@ -196,6 +248,22 @@ impl Diagnostic for Info {
             Info::Cargo(i) => i.context_lines(),
         }
     }
+
+    fn labels(&self) -> Vec<Label> {
+        match self {
+            Info::Toml(i) => i.labels(),
+            Info::Semver(i) => i.labels(),
+            Info::Cargo(i) => i.labels(),
+        }
+    }
+
+    fn footers(&self) -> Vec<Footer> {
+        match self {
+            Info::Toml(i) => i.footers(),
+            Info::Semver(i) => i.footers(),
+            Info::Cargo(i) => i.footers(),
+        }
+    }
 }
 
 impl From<toml::Info> for Info {
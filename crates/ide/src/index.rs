@@ -1,4 +1,5 @@
 use indexmap::IndexMap;
+use semver::{AvailableVersion, SelectOptions};
 use serde::Deserialize;
 
 pub use error::{Error, ErrorKind, ParseError};
@@ -18,12 +19,66 @@ pub struct Crate {
 pub struct Version {
     vers: semver::Version,
     yanked: bool,
-    rust_version: Option<semver::Version>,
+    rust_version: Option<semver::PartialVersion>,
     default_features: Option<Vec<FeatureMember>>,
     features: IndexMap<Box<str>, Vec<FeatureMember>>,
     deps: Vec<Dependency>,
 }
 
+impl Crate {
+    /// The version cargo would select for `req`, see [`semver::highest_match`].
+    pub fn highest_match(
+        &self,
+        req: &semver::VersionReq,
+        options: SelectOptions,
+    ) -> Option<&Version> {
+        semver::highest_match(&self.versions, req, options)
+    }
+
+    /// See [`semver::lowest_match`].
+    pub fn lowest_match(
+        &self,
+        req: &semver::VersionReq,
+        options: SelectOptions,
+    ) -> Option<&Version> {
+        semver::lowest_match(&self.versions, req, options)
+    }
+
+    /// See [`semver::latest_compatible`].
+    pub fn latest_compatible(
+        &self,
+        req: &semver::VersionReq,
+        options: SelectOptions,
+    ) -> Option<&Version> {
+        semver::latest_compatible(&self.versions, req, options)
+    }
+
+    /// See [`semver::latest_incompatible`].
+    pub fn latest_incompatible(
+        &self,
+        req: &semver::VersionReq,
+        options: SelectOptions,
+    ) -> Option<&Version> {
+        semver::latest_incompatible(&self.versions, req, options)
+    }
+
+    /// Complete the version requirement `input` at the byte offset `cursor`, see
+    /// [`semver::complete_requirement`].
+    pub fn complete_requirement(&self, input: &str, cursor: u32) -> Vec<semver::Completion> {
+        semver::complete_requirement(input, cursor, &self.versions)
+    }
+}
+
+impl AvailableVersion for Version {
+    fn version(&self) -> &semver::Version {
+        &self.vers
+    }
+
+    fn is_yanked(&self) -> bool {
+        self.yanked
+    }
+}
+
 /// One of:
 /// - `<feature_name>`              a plain feature
 /// - `dep:<package_name>`          an explicit dependency
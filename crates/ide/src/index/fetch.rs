@@ -1,7 +1,8 @@
 use std::fmt::Write as _;
 use std::time::Duration;
 
-use common::FmtStr;
+use common::diagnostic::Diagnostic as _;
+use common::{FmtStr, Pos};
 use http_req::request::Request;
 use http_req::response::StatusCode;
 use indexmap::IndexMap;
@@ -61,17 +62,47 @@ pub enum DependencyRegistry {
 #[derive(Clone, PartialEq, Eq, Deserialize)]
 struct DeserializeVersion<'a> {
     name: &'a str,
+    #[serde(deserialize_with = "deserialize_vers")]
     vers: semver::Version,
     features: IndexMap<&'a str, Vec<&'a str>>,
     #[serde(default)]
     features2: IndexMap<&'a str, Vec<&'a str>>,
     deps: Vec<Dependency>,
     yanked: bool,
-    rust_version: Option<semver::Version>,
+    rust_version: Option<semver::PartialVersion>,
     // TODO: is this useful?
     // v: &'a str,
 }
 
+/// Parse the version through [`semver::parse_version_ref`], so the prerelease and build metadata
+/// are only copied once. Unlike a borrowed field this also accepts strings containing escapes.
+fn deserialize_vers<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<semver::Version, D::Error> {
+    deserializer.deserialize_str(VersVisitor)
+}
+
+struct VersVisitor;
+
+impl<'de> serde::de::Visitor<'de> for VersVisitor {
+    type Value = semver::Version;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("A semver version")
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        match semver::parse_version_ref(v, Pos::ZERO) {
+            Ok(version) => Ok(version.to_version()),
+            Err(e) => {
+                let mut msg = String::new();
+                _ = e.description(&mut msg);
+                Err(E::custom(msg))
+            }
+        }
+    }
+}
+
 pub fn fetch_crate<'a>(source: DependencySource) -> Result<Crate, Error> {
     let url = source.sparse_index_url();
 
@@ -51,7 +51,7 @@ fn diesel_index() {
         &Version {
             vers: semver_version("2.1.4"),
             yanked: false,
-            rust_version: Some(semver_version("1.65.0")),
+            rust_version: Some(semver::PartialVersion::from(&semver_version("1.65.0"))),
             default_features: Some(feat_members(["32-column-tables", "with-deprecated"])),
             #[rustfmt::skip]
             features: IndexMap::from_iter([
@@ -345,3 +345,70 @@ fn diesel_index() {
         version
     );
 }
+
+type Select = for<'a> fn(&'a Crate, &semver::VersionReq, SelectOptions) -> Option<&'a Version>;
+
+#[test]
+fn select_diesel_versions() {
+    let str = include_str!("../../tests/diesel_index.json");
+    let krate = fetch::parse_crate(str).unwrap();
+    let select = |f: Select, req| {
+        let version = f(&krate, &semver_req(req), SelectOptions::default());
+        version.map(|v| v.vers.to_string())
+    };
+
+    assert_eq!(
+        Some("1.4.8"),
+        select(Crate::highest_match, "1.4").as_deref()
+    );
+    assert_eq!(Some("1.4.0"), select(Crate::lowest_match, "1.4").as_deref());
+    assert_eq!(
+        Some("1.4.8"),
+        select(Crate::latest_compatible, "=1.4.2").as_deref()
+    );
+    assert_eq!(
+        Some("2.2.1"),
+        select(Crate::latest_incompatible, "1.4").as_deref()
+    );
+    assert_eq!(None, select(Crate::latest_incompatible, "2.0").as_deref());
+    assert_eq!(None, select(Crate::latest_incompatible, "*").as_deref());
+}
+
+#[test]
+fn partial_rust_version() {
+    let str = r#"{"name":"foo","vers":"0.1.0","deps":[],"features":{},"yanked":false,"rust_version":"1.70"}"#;
+    let krate = fetch::parse_crate(str).unwrap();
+    let rust_version = krate.versions[0].rust_version.unwrap();
+    assert_eq!(semver::PartialVersion::new(1, Some(70), None), rust_version);
+    assert!(rust_version == semver_version("1.70.0"));
+}
+
+#[test]
+fn escaped_version() {
+    // Escapes can't be borrowed from the input, so the version is passed as a transient string.
+    let str = r#"{"name":"foo","vers":"0.1.0-\u0061lpha","deps":[],"features":{},"yanked":false}"#;
+    let krate = fetch::parse_crate(str).unwrap();
+    assert_eq!(semver_version("0.1.0-alpha"), krate.versions[0].vers);
+
+    let str = r#"{"name":"foo","vers":"0.1.x","deps":[],"features":{},"yanked":false}"#;
+    let res = fetch::parse_crate(str);
+    assert!(matches!(res, Err(ErrorKind::Json(_))));
+}
+
+#[test]
+fn complete_diesel_requirement() {
+    let str = include_str!("../../tests/diesel_index.json");
+    let krate = fetch::parse_crate(str).unwrap();
+    let texts = |input: &str| {
+        let completions = krate.complete_requirement(input, input.len() as u32);
+        completions
+            .into_iter()
+            .map(|c| c.text)
+            .take(4)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(vec!["2.2", "2.2.1", "2"], texts(""));
+    assert_eq!(vec!["^1.4", "^1.4.8", "^1.4.7", "^1.4.6"], texts("^1."));
+    assert_eq!(vec!["2.0.0-rc.1", "2.0.0-rc.0"], texts("2.0.0-"));
+}
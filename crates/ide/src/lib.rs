@@ -2,14 +2,17 @@ use common::{Ctx, Diagnostics};
 use semver::SemverCtx;
 
 pub use cargo::CargoCtx;
-pub use check::{check, State};
+pub use check::{check, check_script, State};
 pub use error::{Error, Hint, Info, Warning};
 use toml::MapTable;
 
 pub mod cargo;
+pub mod cfg;
 pub mod check;
 pub mod error;
 pub mod index;
+pub mod script;
+pub mod spdx;
 
 #[rustfmt::skip]
 pub trait IdeCtx:
@@ -24,6 +27,10 @@ pub trait IdeCtx:
     fn check<'a>(&mut self, map: &'a MapTable<'a>) -> State<'a> {
         check(self, map)
     }
+
+    fn check_script<'a>(&mut self, map: &'a MapTable<'a>) -> State<'a> {
+        check_script(self, map)
+    }
 }
 
 impl<E, W, H> IdeCtx for Diagnostics<E, W, H>
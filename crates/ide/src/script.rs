@@ -0,0 +1,104 @@
+//! Single-file packages (cargo scripts) embed their manifest in a frontmatter block at the top
+//! of a `.rs` file:
+//!
+//! ```text
+//! #!/usr/bin/env -S cargo +nightly -Zscript
+//! ---cargo
+//! [dependencies]
+//! clap = "4"
+//! ---
+//!
+//! fn main() {}
+//! ```
+
+use std::ops::Range;
+
+use common::{FmtStr, Pos, Span};
+
+use crate::{cargo, IdeCtx};
+
+#[cfg(test)]
+mod test;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frontmatter<'a> {
+    /// The opening fence including the infostring.
+    pub open: Span,
+    pub infostring: Option<&'a str>,
+    /// The byte range of the embedded manifest inside the host file.
+    pub manifest: Range<usize>,
+    /// The closing fence.
+    pub close: Span,
+}
+
+/// Find the frontmatter containing the embedded manifest of a cargo script. Returns `None` if
+/// there is no frontmatter, or if it is invalid.
+pub fn find_frontmatter<'a>(ctx: &mut impl IdeCtx, input: &'a str) -> Option<Frontmatter<'a>> {
+    let mut lines = input.split_inclusive('\n').enumerate().peekable();
+    let mut byte_pos = 0;
+
+    // skip the shebang, but not an inner attribute
+    if let Some((_, l)) = lines.peek() {
+        if l.starts_with("#!") && !l.starts_with("#![") {
+            byte_pos += l.len();
+            lines.next();
+        }
+    }
+
+    // the frontmatter may be preceded by whitespace only lines
+    let (open_line, open_str) = loop {
+        let (i, l) = lines.next()?;
+        if !l.trim().is_empty() {
+            break (i as u32, l);
+        }
+        byte_pos += l.len();
+    };
+    byte_pos += open_str.len();
+
+    let num_dashes = open_str.bytes().take_while(|b| *b == b'-').count();
+    if num_dashes < 3 {
+        return None;
+    }
+    let open_str = open_str.trim_end();
+    let open = Span::new(
+        Pos::new(open_line, 0),
+        Pos::new(open_line, open_str.len() as u32),
+    );
+    let info = open_str[num_dashes..].trim();
+    let infostring = (!info.is_empty()).then_some(info);
+    if let Some(info) = infostring {
+        if info != "cargo" {
+            ctx.error(cargo::Error::new(
+                Box::new([]),
+                FmtStr::empty(),
+                open,
+                cargo::ErrorKind::ScriptUnsupportedInfostring(FmtStr::from_str(info)),
+            ));
+            return None;
+        }
+    }
+
+    let manifest_start = byte_pos;
+    for (i, l) in lines {
+        let fence = l.trim_end();
+        if fence.len() == num_dashes && fence.bytes().all(|b| b == b'-') {
+            let line = i as u32;
+            let close = Span::new(Pos::new(line, 0), Pos::new(line, num_dashes as u32));
+            return Some(Frontmatter {
+                open,
+                infostring,
+                manifest: manifest_start..byte_pos,
+                close,
+            });
+        }
+        byte_pos += l.len();
+    }
+
+    ctx.error(cargo::Error::new(
+        Box::new([]),
+        FmtStr::empty(),
+        open,
+        cargo::ErrorKind::ScriptUnclosedFrontmatter,
+    ));
+    None
+}
@@ -0,0 +1,113 @@
+use bumpalo::Bump;
+use common::diagnostic::Diagnostic;
+use common::Pos;
+use pretty_assertions::assert_eq;
+use toml::TomlCtx;
+
+use super::*;
+use crate::{Error, IdeDiagnostics};
+
+#[test]
+fn frontmatter() {
+    let input = "\
+#!/usr/bin/env cargo
+
+---cargo
+[dependencies]
+---
+
+fn main() {}
+";
+    let mut ctx = IdeDiagnostics::default();
+    let frontmatter = find_frontmatter(&mut ctx, input).unwrap();
+    assert_eq!(Vec::<Error>::new(), ctx.errors);
+    assert_eq!(
+        Frontmatter {
+            open: Span::new(Pos::new(2, 0), Pos::new(2, 8)),
+            infostring: Some("cargo"),
+            manifest: 31..46,
+            close: Span::new(Pos::new(4, 0), Pos::new(4, 3)),
+        },
+        frontmatter
+    );
+    assert_eq!("[dependencies]\n", &input[frontmatter.manifest]);
+}
+
+#[test]
+fn frontmatter_longer_fence() {
+    let input = "-----\n---\n-----\n";
+    let mut ctx = IdeDiagnostics::default();
+    let frontmatter = find_frontmatter(&mut ctx, input).unwrap();
+    assert_eq!(Vec::<Error>::new(), ctx.errors);
+    assert_eq!(None, frontmatter.infostring);
+    assert_eq!("---\n", &input[frontmatter.manifest]);
+}
+
+#[test]
+fn no_frontmatter() {
+    let mut ctx = IdeDiagnostics::default();
+    assert_eq!(
+        None,
+        find_frontmatter(&mut ctx, "#![allow(unused)]\nfn main() {}\n")
+    );
+    assert_eq!(None, find_frontmatter(&mut ctx, "// ---\n"));
+    assert_eq!(Vec::<Error>::new(), ctx.errors);
+}
+
+#[test]
+fn frontmatter_unclosed() {
+    let mut ctx = IdeDiagnostics::default();
+    assert_eq!(None, find_frontmatter(&mut ctx, "---\n[package]\n--\n"));
+    assert_eq!(
+        vec![Error::Cargo(cargo::Error::new(
+            Box::new([]),
+            FmtStr::empty(),
+            Span::new(Pos::new(0, 0), Pos::new(0, 3)),
+            cargo::ErrorKind::ScriptUnclosedFrontmatter,
+        ))],
+        ctx.errors
+    );
+}
+
+#[test]
+fn frontmatter_unsupported_infostring() {
+    let mut ctx = IdeDiagnostics::default();
+    assert_eq!(None, find_frontmatter(&mut ctx, "---toml\n---\n"));
+    assert_eq!(
+        vec![Error::Cargo(cargo::Error::new(
+            Box::new([]),
+            FmtStr::empty(),
+            Span::new(Pos::new(0, 0), Pos::new(0, 7)),
+            cargo::ErrorKind::ScriptUnsupportedInfostring(FmtStr::from_str("toml")),
+        ))],
+        ctx.errors
+    );
+}
+
+#[test]
+fn forbidden_keys() {
+    let input = "\
+---
+[package]
+build = \"build.rs\"
+
+[lib]
+---
+";
+    let mut ctx = IdeDiagnostics::default();
+    let bump = Bump::new();
+    let frontmatter = find_frontmatter(&mut ctx, input).unwrap();
+    let tokens = ctx.lex_embedded(&bump, input, frontmatter.manifest);
+    let asts = ctx.parse(&bump, &tokens);
+    let map = ctx.map(&asts);
+    crate::check_script(&mut ctx, &map);
+
+    let spans: Vec<_> = ctx.errors.iter().map(|e| e.span()).collect();
+    assert_eq!(
+        vec![
+            Span::new(Pos::new(2, 0), Pos::new(2, 18)),
+            Span::new(Pos::new(4, 1), Pos::new(4, 5)),
+        ],
+        spans
+    );
+}
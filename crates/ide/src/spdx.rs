@@ -0,0 +1,470 @@
+//! Parser for SPDX license expressions, as used by `package.license`.
+//!
+//! ```text
+//! expr    = and ("OR" and)*
+//! and     = with ("AND" with)*
+//! with    = primary ("WITH" exception)?
+//! primary = "(" expr ")" | license "+"?
+//! ```
+
+use common::diagnostic::Footer;
+use common::{FmtStr, Pos, Span};
+
+mod ids;
+#[cfg(test)]
+mod test;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expr<'a> {
+    License(License<'a>),
+    /// `<license> WITH <exception>`
+    With(License<'a>, Exception<'a>),
+    And(Box<Expr<'a>>, Box<Expr<'a>>),
+    Or(Box<Expr<'a>>, Box<Expr<'a>>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct License<'a> {
+    /// The identifier without the `+` suffix.
+    pub id: &'a str,
+    /// `<license>+`
+    pub or_later: bool,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Exception<'a> {
+    pub id: &'a str,
+    pub span: Span,
+}
+
+/// The result of parsing an expression. Unknown and deprecated identifiers don't prevent the
+/// expression from being parsed, syntax errors do. The spans are byte offsets relative to
+/// [`Pos::ZERO`].
+#[derive(Debug, Default, PartialEq)]
+pub struct Parsed<'a> {
+    pub expr: Option<Expr<'a>>,
+    pub errors: Vec<Error>,
+    pub warnings: Vec<Warning>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Error {
+    pub span: Span,
+    pub kind: ErrorKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    Empty,
+    InvalidChar(char),
+    ExpectedLicense,
+    ExpectedException,
+    ExpectedOperator,
+    UnclosedParen,
+    UnmatchedParen,
+    UnknownLicense(FmtStr, Option<&'static str>),
+    UnknownException(FmtStr, Option<&'static str>),
+}
+
+impl ErrorKind {
+    pub fn description(&self, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        use ErrorKind::*;
+        match self {
+            Empty => write!(f, "empty license expression"),
+            InvalidChar(c) => write!(f, "invalid character `{c}` in license expression"),
+            ExpectedLicense => write!(f, "expected a license identifier"),
+            ExpectedException => write!(f, "expected a license exception identifier after `WITH`"),
+            ExpectedOperator => write!(
+                f,
+                "expected `AND`, `OR` or `WITH` between license identifiers"
+            ),
+            UnclosedParen => write!(f, "unclosed parenthesis in license expression"),
+            UnmatchedParen => write!(f, "unmatched closing parenthesis in license expression"),
+            UnknownLicense(id, _) => write!(f, "unknown SPDX license identifier `{id}`"),
+            UnknownException(id, _) => {
+                write!(f, "unknown SPDX license exception identifier `{id}`")
+            }
+        }
+    }
+
+    pub fn annotation(&self, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        use ErrorKind::*;
+        match self {
+            Empty => write!(f, "empty"),
+            InvalidChar(_) => write!(f, "invalid character"),
+            ExpectedLicense => write!(f, "expected license"),
+            ExpectedException => write!(f, "expected exception"),
+            ExpectedOperator => write!(f, "expected operator"),
+            UnclosedParen => write!(f, "unclosed"),
+            UnmatchedParen => write!(f, "unmatched"),
+            UnknownLicense(..) => write!(f, "unknown license"),
+            UnknownException(..) => write!(f, "unknown exception"),
+        }
+    }
+
+    pub fn footers(&self) -> Vec<Footer> {
+        use ErrorKind::*;
+        match self {
+            UnknownLicense(_, Some(s)) | UnknownException(_, Some(s)) => {
+                vec![Footer::help(FmtStr::from_string(format!(
+                    "did you mean `{s}`"
+                )))]
+            }
+            UnknownLicense(_, None) => vec![Footer::help(
+                "use `license-file` for licenses that aren't on the SPDX license list",
+            )],
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Warning {
+    pub span: Span,
+    pub kind: WarningKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WarningKind {
+    /// The deprecated identifier and the replacement expression, if there is one.
+    DeprecatedLicense(FmtStr, Option<FmtStr>),
+    DeprecatedException(FmtStr),
+    /// `MIT/Apache-2.0` is tolerated by crates.io, but deprecated.
+    SlashSeparator,
+    /// Operators are case sensitive, but crates.io tolerates lowercase ones.
+    LowercaseOperator(&'static str),
+}
+
+impl WarningKind {
+    pub fn description(&self, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        use WarningKind::*;
+        match self {
+            DeprecatedLicense(id, _) => write!(f, "the license identifier `{id}` is deprecated"),
+            DeprecatedException(id) => {
+                write!(f, "the license exception identifier `{id}` is deprecated")
+            }
+            SlashSeparator => write!(
+                f,
+                "`/` is deprecated as a license separator, use `OR` instead"
+            ),
+            LowercaseOperator(op) => write!(
+                f,
+                "license expression operators are case sensitive, use `{op}` instead"
+            ),
+        }
+    }
+
+    pub fn annotation(&self, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        use WarningKind::*;
+        match self {
+            DeprecatedLicense(..) => write!(f, "deprecated license"),
+            DeprecatedException(_) => write!(f, "deprecated exception"),
+            SlashSeparator => write!(f, "deprecated separator"),
+            LowercaseOperator(_) => write!(f, "lowercase operator"),
+        }
+    }
+
+    pub fn footers(&self) -> Vec<Footer> {
+        use WarningKind::*;
+        match self {
+            DeprecatedLicense(_, Some(replacement)) => {
+                vec![Footer::help(FmtStr::from_string(format!(
+                    "use `{replacement}` instead"
+                )))]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+pub fn parse_expression(input: &str) -> Parsed<'_> {
+    let mut warnings = Vec::new();
+    let tokens = lex(input, &mut warnings);
+    let end = Pos::new(0, input.len() as u32);
+    let mut parser = Parser {
+        tokens,
+        idx: 0,
+        end,
+        errors: Vec::new(),
+        warnings,
+    };
+
+    let expr = parser.parse();
+    Parsed {
+        expr,
+        errors: parser.errors,
+        warnings: parser.warnings,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TokenKind {
+    Ident,
+    And,
+    Or,
+    With,
+    Slash,
+    LParen,
+    RParen,
+    Invalid(char),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Token<'a> {
+    kind: TokenKind,
+    text: &'a str,
+    span: Span,
+}
+
+fn is_id_char(c: char) -> bool {
+    // `:` separates the parts of `DocumentRef-<doc>:LicenseRef-<license>`
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | ':')
+}
+
+fn lex<'a>(input: &'a str, warnings: &mut Vec<Warning>) -> Vec<Token<'a>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let mut kind = match c {
+            c if c.is_whitespace() => continue,
+            '(' => TokenKind::LParen,
+            ')' => TokenKind::RParen,
+            '/' => TokenKind::Slash,
+            c if is_id_char(c) => {
+                while chars.next_if(|(_, c)| is_id_char(*c)).is_some() {}
+                chars.next_if(|(_, c)| *c == '+');
+                TokenKind::Ident
+            }
+            c => TokenKind::Invalid(c),
+        };
+
+        let end = chars.peek().map_or(input.len(), |(i, _)| *i);
+        let text = &input[start..end];
+        let span = Span::new(Pos::new(0, start as u32), Pos::new(0, end as u32));
+
+        if kind == TokenKind::Ident {
+            let op = [
+                (TokenKind::And, "AND"),
+                (TokenKind::Or, "OR"),
+                (TokenKind::With, "WITH"),
+            ]
+            .into_iter()
+            .find(|(_, op)| op.eq_ignore_ascii_case(text));
+            if let Some((op_kind, op)) = op {
+                if text != op {
+                    let kind = WarningKind::LowercaseOperator(op);
+                    warnings.push(Warning { span, kind });
+                }
+                kind = op_kind;
+            }
+        }
+
+        tokens.push(Token { kind, text, span });
+    }
+    tokens
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    idx: usize,
+    /// The end of the input.
+    end: Pos,
+    errors: Vec<Error>,
+    warnings: Vec<Warning>,
+}
+
+impl<'a> Parser<'a> {
+    fn parse(&mut self) -> Option<Expr<'a>> {
+        if self.tokens.is_empty() {
+            let span = Span::new(Pos::ZERO, self.end);
+            self.error(span, ErrorKind::Empty);
+            return None;
+        }
+
+        let res = self.parse_or().and_then(|expr| match self.next() {
+            Some(t) => Err(self.unexpected(t)),
+            None => Ok(expr),
+        });
+        match res {
+            Ok(expr) => Some(expr),
+            Err(e) => {
+                self.errors.push(e);
+                None
+            }
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr<'a>, Error> {
+        let mut lhs = self.parse_and()?;
+        while let Some(t) = self.peek() {
+            match t.kind {
+                TokenKind::Or => (),
+                TokenKind::Slash => {
+                    let kind = WarningKind::SlashSeparator;
+                    self.warnings.push(Warning { span: t.span, kind });
+                }
+                _ => break,
+            }
+            self.idx += 1;
+            let rhs = self.parse_and()?;
+            lhs = Expr::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr<'a>, Error> {
+        let mut lhs = self.parse_with()?;
+        while self.peek().is_some_and(|t| t.kind == TokenKind::And) {
+            self.idx += 1;
+            let rhs = self.parse_with()?;
+            lhs = Expr::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_with(&mut self) -> Result<Expr<'a>, Error> {
+        let Some(t) = self.next() else {
+            return Err(self.error_at_end(ErrorKind::ExpectedLicense));
+        };
+
+        match t.kind {
+            TokenKind::LParen => {
+                let expr = self.parse_or()?;
+                match self.next() {
+                    Some(Token {
+                        kind: TokenKind::RParen,
+                        ..
+                    }) => Ok(expr),
+                    Some(other) => Err(self.unexpected(other)),
+                    None => Err(Error {
+                        span: t.span,
+                        kind: ErrorKind::UnclosedParen,
+                    }),
+                }
+            }
+            TokenKind::Ident => {
+                let license = self.license(t);
+                if self.peek().is_none_or(|t| t.kind != TokenKind::With) {
+                    return Ok(Expr::License(license));
+                }
+
+                self.idx += 1;
+                match self.next() {
+                    Some(
+                        t @ Token {
+                            kind: TokenKind::Ident,
+                            ..
+                        },
+                    ) => Ok(Expr::With(license, self.exception(t))),
+                    Some(t) => Err(Error {
+                        span: t.span,
+                        kind: ErrorKind::ExpectedException,
+                    }),
+                    None => Err(self.error_at_end(ErrorKind::ExpectedException)),
+                }
+            }
+            TokenKind::Invalid(c) => Err(Error {
+                span: t.span,
+                kind: ErrorKind::InvalidChar(c),
+            }),
+            _ => Err(Error {
+                span: t.span,
+                kind: ErrorKind::ExpectedLicense,
+            }),
+        }
+    }
+
+    fn license(&mut self, t: Token<'a>) -> License<'a> {
+        let (id, or_later) = match t.text.strip_suffix('+') {
+            Some(id) => (id, true),
+            None => (t.text, false),
+        };
+        let license = License {
+            id,
+            or_later,
+            span: t.span,
+        };
+
+        if id.starts_with("LicenseRef-") || id.starts_with("DocumentRef-") {
+            return license;
+        }
+        if ids::LICENSES.binary_search(&id).is_ok() {
+            return license;
+        }
+
+        if let Some((_, replacement)) = ids::DEPRECATED_LICENSES.iter().find(|(d, _)| *d == id) {
+            let replacement = match (or_later, replacement) {
+                (true, Some(r)) if r.ends_with("-only") => {
+                    let base = r.strip_suffix("-only").unwrap_or(r);
+                    Some(FmtStr::from_string(format!("{base}-or-later")))
+                }
+                (false, Some(r)) => Some(FmtStr::from_str(r)),
+                _ => None,
+            };
+            let kind = WarningKind::DeprecatedLicense(FmtStr::from_str(t.text), replacement);
+            self.warnings.push(Warning { span: t.span, kind });
+            return license;
+        }
+
+        let suggestion = (ids::LICENSES.iter()).find(|l| l.eq_ignore_ascii_case(id));
+        let kind = ErrorKind::UnknownLicense(FmtStr::from_str(id), suggestion.copied());
+        self.error(t.span, kind);
+        license
+    }
+
+    fn exception(&mut self, t: Token<'a>) -> Exception<'a> {
+        let exception = Exception {
+            id: t.text,
+            span: t.span,
+        };
+
+        if ids::EXCEPTIONS.binary_search(&t.text).is_ok() {
+            return exception;
+        }
+
+        if ids::DEPRECATED_EXCEPTIONS.contains(&t.text) {
+            let kind = WarningKind::DeprecatedException(FmtStr::from_str(t.text));
+            self.warnings.push(Warning { span: t.span, kind });
+            return exception;
+        }
+
+        let suggestion = (ids::EXCEPTIONS.iter()).find(|e| e.eq_ignore_ascii_case(t.text));
+        let kind = ErrorKind::UnknownException(FmtStr::from_str(t.text), suggestion.copied());
+        self.error(t.span, kind);
+        exception
+    }
+
+    fn unexpected(&self, t: Token) -> Error {
+        let kind = match t.kind {
+            TokenKind::Invalid(c) => ErrorKind::InvalidChar(c),
+            TokenKind::RParen => ErrorKind::UnmatchedParen,
+            TokenKind::Ident | TokenKind::LParen => ErrorKind::ExpectedOperator,
+            TokenKind::And | TokenKind::Or | TokenKind::With | TokenKind::Slash => {
+                ErrorKind::ExpectedLicense
+            }
+        };
+        Error { span: t.span, kind }
+    }
+
+    fn error(&mut self, span: Span, kind: ErrorKind) {
+        self.errors.push(Error { span, kind });
+    }
+
+    fn error_at_end(&self, kind: ErrorKind) -> Error {
+        Error {
+            span: Span::new(self.end, self.end),
+            kind,
+        }
+    }
+
+    fn peek(&self) -> Option<Token<'a>> {
+        self.tokens.get(self.idx).copied()
+    }
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        let t = self.peek();
+        self.idx += t.is_some() as usize;
+        t
+    }
+}
@@ -0,0 +1,177 @@
+use pretty_assertions::assert_eq;
+
+use super::*;
+
+fn span(char: u32, len: u32) -> Span {
+    Span::new(Pos::new(0, char), Pos::new(0, char + len))
+}
+
+fn license(id: &str, or_later: bool, span: Span) -> Expr<'_> {
+    Expr::License(License { id, or_later, span })
+}
+
+#[track_caller]
+fn check_errors(input: &str, expected: Vec<(Span, ErrorKind)>) {
+    let parsed = parse_expression(input);
+    let errors: Vec<_> = (parsed.errors.into_iter())
+        .map(|e| (e.span, e.kind))
+        .collect();
+    assert_eq!(expected, errors);
+}
+
+#[track_caller]
+fn check_warnings(input: &str, expected: Vec<(Span, WarningKind)>) {
+    let parsed = parse_expression(input);
+    assert_eq!(Vec::<Error>::new(), parsed.errors);
+    let warnings: Vec<_> = (parsed.warnings.into_iter())
+        .map(|w| (w.span, w.kind))
+        .collect();
+    assert_eq!(expected, warnings);
+}
+
+#[test]
+fn single() {
+    let parsed = parse_expression("MIT");
+    assert_eq!(
+        Parsed {
+            expr: Some(license("MIT", false, span(0, 3))),
+            errors: Vec::new(),
+            warnings: Vec::new(),
+        },
+        parsed
+    );
+}
+
+#[test]
+fn precedence() {
+    let parsed = parse_expression("MIT OR Apache-2.0 AND BSD-3-Clause");
+    let expected = Expr::Or(
+        Box::new(license("MIT", false, span(0, 3))),
+        Box::new(Expr::And(
+            Box::new(license("Apache-2.0", false, span(7, 10))),
+            Box::new(license("BSD-3-Clause", false, span(22, 12))),
+        )),
+    );
+    assert_eq!(Some(expected), parsed.expr);
+
+    let parsed = parse_expression("(MIT OR Apache-2.0) AND Zlib");
+    let expected = Expr::And(
+        Box::new(Expr::Or(
+            Box::new(license("MIT", false, span(1, 3))),
+            Box::new(license("Apache-2.0", false, span(8, 10))),
+        )),
+        Box::new(license("Zlib", false, span(24, 4))),
+    );
+    assert_eq!(Some(expected), parsed.expr);
+}
+
+#[test]
+fn with_and_or_later() {
+    let parsed = parse_expression("Apache-2.0+ WITH LLVM-exception");
+    let expected = Expr::With(
+        License {
+            id: "Apache-2.0",
+            or_later: true,
+            span: span(0, 11),
+        },
+        Exception {
+            id: "LLVM-exception",
+            span: span(17, 14),
+        },
+    );
+    assert_eq!(Some(expected), parsed.expr);
+    assert_eq!(Vec::<Error>::new(), parsed.errors);
+    assert_eq!(Vec::<Warning>::new(), parsed.warnings);
+}
+
+#[test]
+fn license_ref() {
+    let parsed = parse_expression("LicenseRef-Proprietary OR DocumentRef-a:LicenseRef-b");
+    assert_eq!(Vec::<Error>::new(), parsed.errors);
+    assert!(parsed.expr.is_some());
+}
+
+#[test]
+fn unknown() {
+    check_errors(
+        "MIT OR mit-0 OR Foo",
+        vec![
+            (
+                span(7, 5),
+                ErrorKind::UnknownLicense(FmtStr::from_str("mit-0"), Some("MIT-0")),
+            ),
+            (
+                span(16, 3),
+                ErrorKind::UnknownLicense(FmtStr::from_str("Foo"), None),
+            ),
+        ],
+    );
+    check_errors(
+        "GPL-3.0-only WITH Foo-exception",
+        vec![(
+            span(18, 13),
+            ErrorKind::UnknownException(FmtStr::from_str("Foo-exception"), None),
+        )],
+    );
+}
+
+#[test]
+fn deprecated() {
+    check_warnings(
+        "GPL-2.0",
+        vec![(
+            span(0, 7),
+            WarningKind::DeprecatedLicense(
+                FmtStr::from_str("GPL-2.0"),
+                Some(FmtStr::from_str("GPL-2.0-only")),
+            ),
+        )],
+    );
+    check_warnings(
+        "GPL-2.0+",
+        vec![(
+            span(0, 8),
+            WarningKind::DeprecatedLicense(
+                FmtStr::from_str("GPL-2.0+"),
+                Some(FmtStr::from_str("GPL-2.0-or-later")),
+            ),
+        )],
+    );
+    check_warnings(
+        "Nunit",
+        vec![(
+            span(0, 5),
+            WarningKind::DeprecatedLicense(FmtStr::from_str("Nunit"), None),
+        )],
+    );
+}
+
+#[test]
+fn slash_and_lowercase() {
+    check_warnings(
+        "MIT/Apache-2.0",
+        vec![(span(3, 1), WarningKind::SlashSeparator)],
+    );
+    check_warnings(
+        "MIT or Apache-2.0",
+        vec![(span(4, 2), WarningKind::LowercaseOperator("OR"))],
+    );
+}
+
+#[test]
+fn syntax_errors() {
+    check_errors("", vec![(span(0, 0), ErrorKind::Empty)]);
+    check_errors("  ", vec![(span(0, 2), ErrorKind::Empty)]);
+    check_errors(
+        "MIT Apache-2.0",
+        vec![(span(4, 10), ErrorKind::ExpectedOperator)],
+    );
+    check_errors("MIT OR", vec![(span(6, 0), ErrorKind::ExpectedLicense)]);
+    check_errors("AND MIT", vec![(span(0, 3), ErrorKind::ExpectedLicense)]);
+    check_errors("(MIT OR Zlib", vec![(span(0, 1), ErrorKind::UnclosedParen)]);
+    check_errors("MIT)", vec![(span(3, 1), ErrorKind::UnmatchedParen)]);
+    check_errors("MIT WITH", vec![(span(8, 0), ErrorKind::ExpectedException)]);
+    check_errors("MIT, Zlib", vec![(span(3, 1), ErrorKind::InvalidChar(','))]);
+    check_errors("ä OR MIT", vec![(span(0, 2), ErrorKind::InvalidChar('ä'))]);
+    check_errors("MIT OR ä", vec![(span(7, 2), ErrorKind::InvalidChar('ä'))]);
+}
@@ -1,9 +1,17 @@
+use std::cell::RefCell;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::rc::Rc;
+
 use bumpalo::Bump;
 use common::diagnostic::Diagnostic;
 use common::Span;
+use ide::cfg::Platform;
 use ide::{IdeCtx, IdeDiagnostics};
+use nvim_oxi::api::opts::NotifyOpts;
+use nvim_oxi::api::types::LogLevel;
 use nvim_oxi::conversion::ToObject;
-use nvim_oxi::serde::Serializer;
+use nvim_oxi::serde::{Deserializer, Serializer};
 use nvim_oxi::{Dictionary, Function, Object};
 use serde::{Deserialize, Serialize};
 use toml::TomlCtx;
@@ -13,6 +21,8 @@ pub struct VimDiagnostics {
     pub errors: Vec<VimDiagnostic>,
     pub warnings: Vec<VimDiagnostic>,
     pub infos: Vec<VimDiagnostic>,
+    /// Dependencies that aren't used when building for the selected platform.
+    pub inactive: Vec<VimRange>,
 }
 
 impl ToObject for VimDiagnostics {
@@ -36,18 +46,91 @@ impl ToObject for VimDiagnostic {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VimRange {
+    pub lnum: u32,
+    pub end_lnum: u32,
+    pub col: u32,
+    pub end_col: u32,
+}
+
+/// Options that select the platform used to dim inactive dependencies. Without options the
+/// host platform is used.
+#[derive(Clone, Default, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct PlatformOptions {
+    /// A target triple. Without `cfg_file` this has to be one of the built-in targets.
+    pub target: Option<String>,
+    /// A file with the output of `rustc --print cfg`.
+    pub cfg_file: Option<PathBuf>,
+}
+
+thread_local! {
+    /// The platform of the last options, so the built-in targets aren't scanned and the cfg file
+    /// isn't read on every check.
+    static PLATFORM: RefCell<Option<(PlatformOptions, Option<Rc<Platform>>)>> =
+        const { RefCell::new(None) };
+}
+
 #[nvim_oxi::plugin]
 pub fn crates_nvim_lib() -> nvim_oxi::Result<Dictionary> {
-    let check_toml: Function<(), Result<Object, nvim_oxi::Error>> = Function::from_fn(move |()| {
-        let diagnostics = check_toml()?;
-        let object = diagnostics.to_object()?;
-        Ok(object)
-    });
+    let check_toml: Function<Object, Result<Object, nvim_oxi::Error>> =
+        Function::from_fn(move |options: Object| {
+            let options = if options.is_nil() {
+                PlatformOptions::default()
+            } else {
+                PlatformOptions::deserialize(Deserializer::new(options))?
+            };
+            let diagnostics = check_toml(options)?;
+            let object = diagnostics.to_object()?;
+            Ok(object)
+        });
 
     Ok(Dictionary::from_iter([("check_toml", check_toml)]))
 }
 
-fn check_toml() -> Result<VimDiagnostics, nvim_oxi::api::Error> {
+/// The cached platform for `options`. If it can't be resolved, the user is notified once.
+fn platform(options: PlatformOptions) -> Option<Rc<Platform>> {
+    PLATFORM.with_borrow_mut(|cache| {
+        if let Some((cached, platform)) = cache {
+            if *cached == options {
+                return platform.clone();
+            }
+        }
+
+        let platform = match resolve_platform(&options) {
+            Ok(platform) => Some(Rc::new(platform)),
+            Err(msg) => {
+                let msg = format!("crates.nvim: {msg}, inactive dependencies aren't dimmed");
+                _ = nvim_oxi::api::notify(&msg, LogLevel::Warn, &NotifyOpts::default());
+                None
+            }
+        };
+        *cache = Some((options, platform.clone()));
+        platform
+    })
+}
+
+fn resolve_platform(options: &PlatformOptions) -> Result<Platform, String> {
+    match (&options.target, &options.cfg_file) {
+        (target, Some(file)) => {
+            let text = std::fs::read_to_string(file)
+                .map_err(|e| format!("error reading `{}`: {e}", file.display()))?;
+            let triple = target.clone().unwrap_or_default();
+            Platform::from_rustc_cfg(triple, &text)
+                .map_err(|i| format!("invalid cfg on line {} of `{}`", i + 1, file.display()))
+        }
+        (Some(target), None) => Platform::builtin(target).ok_or_else(|| {
+            format!("`{target}` isn't a built-in target, set `cfg_file` to the output of `rustc --print cfg --target {target}`")
+        }),
+        (None, None) => Platform::host().ok_or_else(|| {
+            "the host platform isn't one of the built-in targets, set `target` or `cfg_file`"
+                .to_string()
+        }),
+    }
+}
+
+fn check_toml(options: PlatformOptions) -> Result<VimDiagnostics, nvim_oxi::api::Error> {
     let buf = nvim_oxi::api::get_current_buf();
     let num_lines = buf.line_count()?;
     let raw_lines = buf.get_lines(0..num_lines, true)?;
@@ -63,12 +146,52 @@ fn check_toml() -> Result<VimDiagnostics, nvim_oxi::api::Error> {
         lines.push(str.to_string());
     }
 
+    let path = buf.get_name()?;
+    let is_script = path.extension().is_some_and(|e| e == "rs");
+
     let mut ctx = IdeDiagnostics::default();
     let bump = Bump::new();
-    let tokens = ctx.lex(&bump, &text);
+    let tokens = if is_script {
+        // check the manifest embedded in a cargo script
+        let Some(frontmatter) = ide::script::find_frontmatter(&mut ctx, &text) else {
+            let errors = ctx.errors.iter().map(map_vim_diagnostic).collect();
+            return Ok(VimDiagnostics {
+                errors,
+                ..Default::default()
+            });
+        };
+        ctx.lex_embedded(&bump, &text, frontmatter.manifest)
+    } else {
+        ctx.lex(&bump, &text)
+    };
     let asts = ctx.parse(&bump, &tokens);
     let map = ctx.map(&asts);
-    let _state = ctx.check(&map);
+    let state = if is_script {
+        ctx.check_script(&map)
+    } else {
+        let state = ctx.check(&map);
+        let implicit_lib = path.with_file_name("src").join("lib.rs").is_file();
+        ide::check::lint_version_reqs(&mut ctx, &state, implicit_lib);
+        state
+    };
+
+    let mut inactive = Vec::new();
+    if let Some(platform) = platform(options) {
+        for dep in state.dependencies().iter() {
+            if dep.is_active(&platform) {
+                continue;
+            }
+            for repr in dep.entry.reprs.iter() {
+                let Span { start, end } = repr.repr_span();
+                inactive.push(VimRange {
+                    lnum: start.line,
+                    end_lnum: end.line,
+                    col: start.char,
+                    end_col: end.char,
+                });
+            }
+        }
+    }
 
     let errors = ctx.errors.iter().map(map_vim_diagnostic).collect();
     let warnings = ctx.warnings.iter().map(map_vim_diagnostic).collect();
@@ -78,6 +201,7 @@ fn check_toml() -> Result<VimDiagnostics, nvim_oxi::api::Error> {
         errors,
         warnings,
         infos,
+        inactive,
     };
 
     Ok(diagnostics)
@@ -87,6 +211,9 @@ fn map_vim_diagnostic(d: &impl Diagnostic) -> VimDiagnostic {
     let Span { start, end } = d.span();
     let mut message = String::new();
     _ = d.description(&mut message);
+    for footer in d.footers() {
+        _ = write!(message, "\n{footer}");
+    }
     VimDiagnostic {
         lnum: start.line,
         end_lnum: end.line,
@@ -0,0 +1,118 @@
+//! Parse throughput on the versions and requirements of the `diesel` index file.
+//!
+//! Run with `cargo bench -p crates-semver`, an optional argument filters the benchmarks by name.
+//! Without `--bench`, for example when run by `cargo test --benches`, every benchmark is only
+//! run once to check that it works.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use common::Pos;
+
+const INDEX: &str = include_str!("../../ide/tests/diesel_index.json");
+const WARM_UP: Duration = Duration::from_millis(500);
+const MEASUREMENT: Duration = Duration::from_secs(2);
+const SAMPLES: u32 = 50;
+
+type Bench<'a> = (&'static str, &'a [&'a str], fn(&str));
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let bench_mode = args.iter().any(|a| a == "--bench");
+    let filter = args.iter().find(|a| !a.starts_with("--"));
+
+    let versions = json_strings(INDEX, "\"vers\":\"");
+    let reqs = json_strings(INDEX, "\"req\":\"");
+
+    let benches: [Bench; 3] = [
+        ("parse_version", &versions, |s| {
+            black_box(crates_semver::parse_version(s, Pos::ZERO).unwrap());
+        }),
+        ("parse_version_ref", &versions, |s| {
+            black_box(crates_semver::parse_version_ref(s, Pos::ZERO).unwrap());
+        }),
+        ("parse_requirement", &reqs, |s| {
+            black_box(crates_semver::parse_requirement(s, Pos::ZERO).unwrap());
+        }),
+    ];
+
+    for (name, inputs, f) in benches {
+        if filter.is_some_and(|filter| !name.contains(filter.as_str())) {
+            continue;
+        }
+
+        let run = || inputs.iter().for_each(|s| f(black_box(s)));
+        if !bench_mode {
+            run();
+            println!("{name}: ok");
+            continue;
+        }
+
+        bench(name, inputs, run);
+    }
+}
+
+/// Run `f` repeatedly for a fixed amount of time and report the time per iteration and the
+/// throughput, similar to criterion.
+fn bench(name: &str, inputs: &[&str], mut f: impl FnMut()) {
+    let start = Instant::now();
+    let mut warm_up_iters = 0u64;
+    while start.elapsed() < WARM_UP {
+        f();
+        warm_up_iters += 1;
+    }
+
+    let iter_time = WARM_UP.as_secs_f64() / warm_up_iters as f64;
+    let sample_time = MEASUREMENT.as_secs_f64() / SAMPLES as f64;
+    let iters_per_sample = ((sample_time / iter_time) as u64).max(1);
+
+    let mut samples = Vec::with_capacity(SAMPLES as usize);
+    for _ in 0..SAMPLES {
+        let start = Instant::now();
+        for _ in 0..iters_per_sample {
+            f();
+        }
+        samples.push(start.elapsed().as_secs_f64() / iters_per_sample as f64);
+    }
+    samples.sort_by(f64::total_cmp);
+
+    let bytes: usize = inputs.iter().map(|s| s.len()).sum();
+    let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+    let (low, high) = (samples[0], samples[samples.len() - 1]);
+    println!(
+        "{name:<20} time: [{} {} {}]",
+        fmt_time(low),
+        fmt_time(mean),
+        fmt_time(high)
+    );
+    println!(
+        "{:<20} thrpt: {:.1} MiB/s, {:.0} elements/s ({} elements)",
+        "",
+        bytes as f64 / mean / (1024.0 * 1024.0),
+        inputs.len() as f64 / mean,
+        inputs.len(),
+    );
+}
+
+fn fmt_time(secs: f64) -> String {
+    match secs {
+        s if s < 1e-6 => format!("{:.2} ns", s * 1e9),
+        s if s < 1e-3 => format!("{:.2} µs", s * 1e6),
+        s => format!("{:.2} ms", s * 1e3),
+    }
+}
+
+/// All string values following `key` in the json lines, neither contains escape sequences.
+fn json_strings<'a>(index: &'a str, key: &str) -> Vec<&'a str> {
+    let mut strings = Vec::new();
+    for line in index.lines() {
+        let mut rest = line;
+        while let Some(start) = rest.find(key) {
+            rest = &rest[start + key.len()..];
+            let end = rest.find('"').expect("unterminated string");
+            strings.push(&rest[..end]);
+            rest = &rest[end..];
+        }
+    }
+    strings
+}
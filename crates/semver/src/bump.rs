@@ -0,0 +1,83 @@
+use std::fmt::Write as _;
+
+use crate::{eval, CompVersion, Comparator, Op, Version, VersionReq};
+
+#[cfg(test)]
+mod test;
+
+pub(crate) fn bump_requirement(req: &VersionReq, target: &Version) -> String {
+    let mut out = String::new();
+    let mut pos = 0;
+    for cmp in req.comparators.iter() {
+        push_spaces(&mut out, cmp.op_offset.char as usize - pos);
+        out.push_str(cmp.op.as_str());
+        let op_end = cmp.op_offset.char as usize + cmp.op.as_str().len();
+        push_spaces(&mut out, cmp.version_offset.char as usize - op_end);
+
+        let version = bump_comparator(cmp, target);
+        _ = write!(out, "{}", version.as_ref().unwrap_or(&cmp.version));
+        let old_len = cmp.version.to_string().len();
+        pos = cmp.version_offset.char as usize + old_len;
+
+        if let Some(comma) = cmp.comma {
+            push_spaces(&mut out, comma.char as usize - pos);
+            out.push(',');
+            pos = comma.char as usize + 1;
+        }
+    }
+    push_spaces(&mut out, (req.len as usize).saturating_sub(pos));
+    out
+}
+
+/// The new version of the comparator, or `None` if it should be kept.
+fn bump_comparator(cmp: &Comparator, target: &Version) -> Option<CompVersion> {
+    match cmp.op {
+        Op::Wl if matches!(cmp.version, CompVersion::Wl(_)) => None,
+        Op::Eq | Op::Wl | Op::Ge | Op::Tl | Op::Cr | Op::Bl => {
+            Some(with_precision(&cmp.version, target))
+        }
+        // Lower bounds excluding the target can't be bumped to it without changing the operator.
+        Op::Gt => None,
+        Op::Lt | Op::Le if eval::matches_impl(cmp, target) => None,
+        Op::Le => Some(with_precision(&cmp.version, target)),
+        Op::Lt => Some(match cmp.version {
+            CompVersion::Major(_, wl) if target.pre.is_empty() => {
+                CompVersion::Major(target.major.saturating_add(1), wl)
+            }
+            CompVersion::Minor(_, _, wl) if target.pre.is_empty() => {
+                CompVersion::Minor(target.major, target.minor.saturating_add(1), wl)
+            }
+            _ => CompVersion::Patch(
+                target.major,
+                target.minor,
+                target.patch.saturating_add(1),
+                None,
+            ),
+        }),
+    }
+}
+
+/// `target` truncated to the precision of `version`. Prereleases always use the full version,
+/// otherwise they wouldn't be matched.
+fn with_precision(version: &CompVersion, target: &Version) -> CompVersion {
+    let Version {
+        major,
+        minor,
+        patch,
+        pre,
+        ..
+    } = target;
+    match version {
+        CompVersion::Wl(wl) => CompVersion::Wl(*wl),
+        _ if !pre.is_empty() => CompVersion::Pre(*major, *minor, *patch, pre.clone(), None),
+        CompVersion::Major(_, wl) => CompVersion::Major(*major, *wl),
+        CompVersion::Minor(_, _, wl) => CompVersion::Minor(*major, *minor, *wl),
+        CompVersion::Patch(..) | CompVersion::Pre(..) => {
+            CompVersion::Patch(*major, *minor, *patch, None)
+        }
+    }
+}
+
+fn push_spaces(out: &mut String, n: usize) {
+    out.extend(std::iter::repeat_n(' ', n));
+}
@@ -0,0 +1,62 @@
+use pretty_assertions::assert_eq;
+
+use crate::test::{req, version};
+
+#[track_caller]
+fn check(input: &str, target: &str, expected: &str) {
+    let r = req(input);
+    let target = version(target);
+    let bumped = r.bump_to(&target);
+    assert_eq!(expected, bumped);
+    assert!(
+        req(&bumped).matches(&target),
+        "`{bumped}` doesn't match {target}"
+    );
+}
+
+#[test]
+fn keep_precision() {
+    check("^1.2", "1.4.3", "^1.4");
+    check("1.2.3", "1.4.0", "1.4.0");
+    check("~0.3", "0.5.1", "~0.5");
+    check("1", "2.3.4", "2");
+    check("=1.2.3", "1.2.7", "=1.2.7");
+    check(">=0.9", "1.0.0", ">=1.0");
+}
+
+#[test]
+fn keep_wildcards() {
+    check("1.*", "3.1.0", "3.*");
+    check("1.x.X", "3.1.0", "3.x.X");
+    check("1.2.x", "1.4.1", "1.4.x");
+    check("*", "3.1.0", "*");
+}
+
+#[test]
+fn keep_whitespace_and_commas() {
+    check(" >= 1.2 ,  < 2 ", "2.1.0", " >= 2.1 ,  < 3 ");
+    check(">=1.2.3,<1.5", "1.7.2", ">=1.7.2,<1.8");
+    check(">=1.2, <=1.4", "1.6.0", ">=1.6, <=1.6");
+}
+
+#[test]
+fn keep_satisfied_upper_bounds() {
+    check(">=1.2, <3", "2.1.0", ">=2.1, <3");
+    check(">1.0, <3", "2.1.0", ">1.0, <3");
+}
+
+#[test]
+fn prerelease() {
+    check("^1.2", "2.0.0-beta.1", "^2.0.0-beta.1");
+    check("1.2.3-alpha", "1.2.3", "1.2.3");
+    check("1.2.3+build", "1.3.0", "1.3.0");
+}
+
+#[test]
+fn needs_bump() {
+    assert!(!req("^1.2").needs_bump(&version("1.4.0")));
+    assert!(req("^1.2").needs_bump(&version("2.0.0")));
+    assert!(req("~0.3").needs_bump(&version("0.4.0")));
+    assert!(req("1.2").needs_bump(&version("1.3.0-beta")));
+    assert!(!req("*").needs_bump(&version("5.0.0")));
+}
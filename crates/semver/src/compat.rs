@@ -0,0 +1,89 @@
+//! Classify version changes using cargo's compatibility rules.
+
+use crate::{
+    eval, CompVersion, Comparator, Offset, Op, StableRange, Triple, Version, VersionReq, VersionSet,
+};
+
+#[cfg(test)]
+mod test;
+
+/// The kind of change between two versions. The leftmost non-zero component signals breaking
+/// changes, so `0.x` minor and `0.0.x` patch releases are breaking, like in caret requirements.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CompatClass {
+    /// The versions only differ in build metadata.
+    Equal,
+    /// A compatible change of the patch version or prerelease.
+    Patch,
+    /// A compatible change of the minor version.
+    Minor,
+    /// An incompatible change.
+    Breaking,
+}
+
+impl Version {
+    /// Classify the change between this version and `other`, the order of the versions doesn't
+    /// matter. Prereleases are compatible with other versions if a caret requirement on the
+    /// lower version would match the higher version, ignoring cargo's special casing that
+    /// prereleases are only matched if the requirement mentions them.
+    pub fn compat_class(&self, other: &Version) -> CompatClass {
+        let (lower, higher) = match self <= other {
+            true => (self, other),
+            false => (other, self),
+        };
+
+        if Triple::of(lower) == Triple::of(higher) && lower.pre == higher.pre {
+            return CompatClass::Equal;
+        }
+
+        let caret = Comparator {
+            op_offset: Offset::new(0),
+            op: Op::Cr,
+            version_offset: Offset::new(1),
+            version: comp_version(lower),
+            comma: None,
+        };
+        if !eval::matches_caret(&caret, higher) {
+            return CompatClass::Breaking;
+        }
+
+        match (lower.major, lower.minor) == (higher.major, higher.minor) {
+            true => CompatClass::Patch,
+            false => CompatClass::Minor,
+        }
+    }
+}
+
+impl VersionReq {
+    /// The range of stable versions that are compatible with the lowest version matched by this
+    /// requirement, `None` if it doesn't match any version. This ignores upper bounds, so for
+    /// example `>=1.2, <1.4` results in `1.2.0..2.0.0`. If the requirement has no upper bound,
+    /// like `>=1.2` or `*`, all newer versions are matched and the range is unbounded.
+    pub fn compatible_range(&self) -> Option<StableRange> {
+        let set = self.to_set();
+        let start = min_triple(&set)?;
+        let unbounded = set.stable_ranges().last().is_some_and(|r| r.end.is_none());
+        let end = match unbounded {
+            true => None,
+            false => start.caret_end(),
+        };
+        Some(StableRange::new(start, end))
+    }
+}
+
+/// The smallest `major.minor.patch` matched by `set`, including prereleases.
+fn min_triple(set: &VersionSet) -> Option<Triple> {
+    let stable = set.stable_ranges().first().map(|r| r.start);
+    let pre = set.pre_ranges().first().map(|r| r.triple);
+    match (stable, pre) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+fn comp_version(v: &Version) -> CompVersion {
+    match v.pre.is_empty() {
+        true => CompVersion::Patch(v.major, v.minor, v.patch, None),
+        false => CompVersion::Pre(v.major, v.minor, v.patch, v.pre.clone(), None),
+    }
+}
@@ -0,0 +1,107 @@
+use pretty_assertions::assert_eq;
+
+use super::*;
+use crate::test::{req, version};
+use CompatClass::*;
+
+#[track_caller]
+fn check(a: &str, b: &str, expected: CompatClass) {
+    let (a, b) = (version(a), version(b));
+    assert_eq!(expected, a.compat_class(&b), "{a} -> {b}");
+    assert_eq!(expected, b.compat_class(&a), "{b} -> {a}");
+}
+
+#[test]
+fn stable() {
+    check("1.2.3", "1.2.3", Equal);
+    check("1.2.3", "1.2.3+build", Equal);
+    check("1.2.3", "1.2.4", Patch);
+    check("1.2.3", "1.3.0", Minor);
+    check("1.2.3", "1.9.9", Minor);
+    check("1.2.3", "2.0.0", Breaking);
+    check("1.0.0", "3.1.4", Breaking);
+}
+
+#[test]
+fn zero_major() {
+    check("0.2.3", "0.2.3", Equal);
+    check("0.2.3", "0.2.4", Patch);
+    check("0.2.3", "0.2.99", Patch);
+    check("0.2.3", "0.3.0", Breaking);
+    check("0.2.3", "1.0.0", Breaking);
+    check("0.1.0", "0.2.0", Breaking);
+}
+
+#[test]
+fn zero_major_zero_minor() {
+    check("0.0.3", "0.0.3", Equal);
+    check("0.0.3", "0.0.4", Breaking);
+    check("0.0.0", "0.0.1", Breaking);
+    check("0.0.3", "0.1.0", Breaking);
+    check("0.0.3", "1.0.0", Breaking);
+}
+
+#[test]
+fn prerelease() {
+    check("1.2.3-alpha", "1.2.3-alpha+build", Equal);
+    check("1.2.3-alpha", "1.2.3-beta", Patch);
+    check("1.2.3-alpha", "1.2.3", Patch);
+    check("1.2.3", "1.2.4-alpha", Patch);
+    check("1.2.3-alpha", "1.3.0", Minor);
+    check("1.2.3", "1.3.0-alpha", Minor);
+    check("2.0.0-alpha", "2.0.0", Patch);
+    check("2.0.0-alpha", "2.1.0", Minor);
+    check("1.9.0", "2.0.0-alpha", Breaking);
+
+    check("0.2.0-alpha", "0.2.0", Patch);
+    check("0.2.0", "0.2.1-alpha", Patch);
+    check("0.2.0", "0.3.0-alpha", Breaking);
+
+    check("0.0.3-alpha", "0.0.3-beta", Patch);
+    check("0.0.3-alpha", "0.0.3", Patch);
+    check("0.0.3", "0.0.4-alpha", Breaking);
+}
+
+/// `compat_class` agrees with caret requirements for all stable versions in a small grid.
+#[test]
+fn agrees_with_caret_requirements() {
+    let mut versions = Vec::new();
+    for major in 0..3 {
+        for minor in 0..3 {
+            for patch in 0..3 {
+                versions.push(version(&format!("{major}.{minor}.{patch}")));
+            }
+        }
+    }
+
+    for a in versions.iter() {
+        let caret = req(&format!("^{a}"));
+        for b in versions.iter().filter(|b| a <= *b) {
+            let compatible = a.compat_class(b) != Breaking;
+            assert_eq!(caret.matches(b), compatible, "{a} -> {b}");
+        }
+    }
+}
+
+#[test]
+fn compatible_range() {
+    #[track_caller]
+    fn check(r: &str, start: (u32, u32, u32), end: Option<(u32, u32, u32)>) {
+        let t = |(major, minor, patch)| Triple::new(major, minor, patch);
+        let expected = StableRange::new(t(start), end.map(t));
+        assert_eq!(Some(expected), req(r).compatible_range(), "`{r}`");
+    }
+
+    check("1.2.3", (1, 2, 3), Some((2, 0, 0)));
+    check("=1.2.3", (1, 2, 3), Some((2, 0, 0)));
+    check(">=1.2, <1.4", (1, 2, 0), Some((2, 0, 0)));
+    check("~0.2", (0, 2, 0), Some((0, 3, 0)));
+    check("0.0.3", (0, 0, 3), Some((0, 0, 4)));
+    check("^0.0", (0, 0, 0), Some((0, 0, 1)));
+    check(">0.9", (0, 10, 0), None);
+    check("^1.0.0-alpha", (1, 0, 0), Some((2, 0, 0)));
+    check("*", (0, 0, 0), None);
+    check(">=1.2", (1, 2, 0), None);
+
+    assert_eq!(None, req("<0.0.0").compatible_range());
+}
@@ -0,0 +1,89 @@
+//! Completion of partially typed version requirements.
+
+use std::collections::HashSet;
+use std::ops::Range;
+
+use crate::{AvailableVersion, Version};
+
+#[cfg(test)]
+mod test;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Completion {
+    /// The text that replaces [`Self::range`], including the operator.
+    pub text: String,
+    /// The byte range of the comparator that is replaced.
+    pub range: Range<u32>,
+    /// Lower is better, completions are returned sorted by rank.
+    pub rank: u32,
+}
+
+/// Complete the comparator at `cursor`, a byte offset into `input`. Only the text before the
+/// cursor is used to filter the versions, but the completions replace the whole comparator.
+///
+/// If no version has been typed yet, only the latest version is offered in several precisions,
+/// otherwise all versions starting with the typed text are offered, newest first. Yanked versions
+/// are never offered and prereleases only if the typed text contains a `-`.
+pub fn complete_requirement<V: AvailableVersion>(
+    input: &str,
+    cursor: u32,
+    versions: &[V],
+) -> Vec<Completion> {
+    let cursor = (cursor as usize).min(input.len());
+    if !input.is_char_boundary(cursor) {
+        return Vec::new();
+    }
+
+    let segment_start = input[..cursor].rfind(',').map_or(0, |i| i + 1);
+    let segment_end = input[cursor..]
+        .find(',')
+        .map_or(input.len(), |i| cursor + i);
+    let start = cursor - input[segment_start..cursor].trim_start_matches(' ').len();
+    let end = cursor + input[cursor..segment_end].trim_end_matches(' ').len();
+
+    let typed = &input[start..cursor];
+    let op_len = typed.len()
+        - typed
+            .trim_start_matches(['=', '<', '>', '^', '~', ' '])
+            .len();
+    let (op, typed_version) = typed.split_at(op_len);
+
+    let prerelease = typed_version.contains('-');
+    let mut candidates = (versions.iter())
+        .filter(|v| !v.is_yanked() && (prerelease || v.version().pre.is_empty()))
+        .map(|v| v.version())
+        .collect::<Vec<_>>();
+    candidates.sort_unstable_by(|a, b| b.cmp(a));
+    if typed_version.is_empty() {
+        candidates.truncate(1);
+    }
+
+    let mut seen = HashSet::new();
+    let mut completions = Vec::new();
+    for v in candidates {
+        for form in forms(v) {
+            if !form.starts_with(typed_version) || !seen.insert(form.clone()) {
+                continue;
+            }
+            completions.push(Completion {
+                text: format!("{op}{form}"),
+                range: start as u32..end as u32,
+                rank: completions.len() as u32,
+            });
+        }
+    }
+    completions
+}
+
+/// The version in different precisions, in the order they are offered.
+fn forms(v: &Version) -> [String; 3] {
+    let full = match v.pre.is_empty() {
+        true => format!("{}.{}.{}", v.major, v.minor, v.patch),
+        false => format!("{}.{}.{}-{}", v.major, v.minor, v.patch, v.pre.as_str()),
+    };
+    [
+        format!("{}.{}", v.major, v.minor),
+        full,
+        format!("{}", v.major),
+    ]
+}
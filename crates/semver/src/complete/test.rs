@@ -0,0 +1,85 @@
+use pretty_assertions::assert_eq;
+
+use super::*;
+use crate::test::version;
+
+fn versions() -> Vec<Version> {
+    ["0.9.3", "1.3.0", "1.3.1", "1.4.0", "1.4.2", "2.0.0-rc.1"]
+        .map(version)
+        .to_vec()
+}
+
+#[track_caller]
+fn check(input: &str, cursor: u32, range: Range<u32>, expected: &[&str]) {
+    let completions = complete_requirement(input, cursor, &versions());
+    let texts: Vec<_> = completions.iter().map(|c| c.text.as_str()).collect();
+    assert_eq!(expected, texts, "`{input}`");
+    for (i, c) in completions.iter().enumerate() {
+        assert_eq!(range, c.range, "`{input}`");
+        assert_eq!(i as u32, c.rank, "`{input}`");
+    }
+}
+
+#[test]
+fn empty() {
+    check("", 0, 0..0, &["1.4", "1.4.2", "1"]);
+    check("  ", 1, 1..1, &["1.4", "1.4.2", "1"]);
+    check("^", 1, 0..1, &["^1.4", "^1.4.2", "^1"]);
+    check(">= ", 3, 0..3, &[">= 1.4", ">= 1.4.2", ">= 1"]);
+}
+
+#[test]
+fn partial() {
+    check(
+        "^1.",
+        3,
+        0..3,
+        &["^1.4", "^1.4.2", "^1.4.0", "^1.3", "^1.3.1", "^1.3.0"],
+    );
+    check("1.4", 3, 0..3, &["1.4", "1.4.2", "1.4.0"]);
+    check("1.4.", 4, 0..4, &["1.4.2", "1.4.0"]);
+    check("~0", 2, 0..2, &["~0.9", "~0.9.3", "~0"]);
+    check("3", 1, 0..1, &[]);
+}
+
+#[test]
+fn prerelease() {
+    check("2.0.0-", 6, 0..6, &["2.0.0-rc.1"]);
+}
+
+#[test]
+fn text_after_cursor() {
+    check(
+        "^1.4.0",
+        3,
+        0..6,
+        &["^1.4", "^1.4.2", "^1.4.0", "^1.3", "^1.3.1", "^1.3.0"],
+    );
+}
+
+#[test]
+fn comparator_list() {
+    check(">=1.3, <", 8, 7..8, &["<1.4", "<1.4.2", "<1"]);
+    check(">=1.3,<1.4", 10, 6..10, &["<1.4", "<1.4.2", "<1.4.0"]);
+    check(">=1.3, <2, ", 11, 11..11, &["1.4", "1.4.2", "1"]);
+    check(">=1.3 , <2", 5, 0..5, &[">=1.3", ">=1.3.1", ">=1.3.0"]);
+}
+
+#[test]
+fn yanked() {
+    struct Yanked(Version);
+    impl AvailableVersion for Yanked {
+        fn version(&self) -> &Version {
+            &self.0
+        }
+
+        fn is_yanked(&self) -> bool {
+            self.0.minor == 4
+        }
+    }
+
+    let versions = versions().into_iter().map(Yanked).collect::<Vec<_>>();
+    let completions = complete_requirement("", 0, &versions);
+    let texts: Vec<_> = completions.iter().map(|c| c.text.as_str()).collect();
+    assert_eq!(vec!["1.3", "1.3.1", "1"], texts);
+}
@@ -0,0 +1,166 @@
+//! A vector of comparators that stores up to [`INLINE_CAP`] comparators inline, which covers
+//! almost all requirements seen in practice, like `1.2` or `>=1.2, <1.5`.
+
+use std::mem::MaybeUninit;
+use std::ops::{Deref, DerefMut};
+
+use crate::Comparator;
+
+#[cfg(test)]
+mod test;
+
+pub const INLINE_CAP: usize = 2;
+
+pub struct CompVec {
+    repr: Repr,
+}
+
+enum Repr {
+    /// The first `len` items are initialized.
+    Inline {
+        len: usize,
+        items: [MaybeUninit<Comparator>; INLINE_CAP],
+    },
+    Heap(Vec<Comparator>),
+}
+
+impl CompVec {
+    pub const fn new() -> Self {
+        let repr = Repr::Inline {
+            len: 0,
+            items: [const { MaybeUninit::uninit() }; INLINE_CAP],
+        };
+        Self { repr }
+    }
+
+    pub fn is_inline(&self) -> bool {
+        matches!(self.repr, Repr::Inline { .. })
+    }
+
+    pub fn push(&mut self, comparator: Comparator) {
+        match &mut self.repr {
+            Repr::Inline { len, items } if *len < INLINE_CAP => {
+                items[*len].write(comparator);
+                *len += 1;
+            }
+            Repr::Inline { len, items } => {
+                let mut vec = Vec::with_capacity(2 * INLINE_CAP);
+                // Set the length to zero before moving the items out, so they aren't dropped
+                // twice if anything below panics.
+                *len = 0;
+                for item in items.iter() {
+                    // SAFETY: the inline storage is full, so all items are initialized, and
+                    // they are only read once since the length was set to zero.
+                    vec.push(unsafe { item.assume_init_read() });
+                }
+                vec.push(comparator);
+                self.repr = Repr::Heap(vec);
+            }
+            Repr::Heap(vec) => vec.push(comparator),
+        }
+    }
+
+    pub fn as_slice(&self) -> &[Comparator] {
+        match &self.repr {
+            Repr::Inline { len, items } => {
+                // SAFETY: the first `len` items are initialized, and `MaybeUninit<T>` has the
+                // same layout as `T`.
+                unsafe { std::slice::from_raw_parts(items.as_ptr() as *const Comparator, *len) }
+            }
+            Repr::Heap(vec) => vec,
+        }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [Comparator] {
+        match &mut self.repr {
+            Repr::Inline { len, items } => {
+                // SAFETY: see `as_slice`
+                unsafe {
+                    std::slice::from_raw_parts_mut(items.as_mut_ptr() as *mut Comparator, *len)
+                }
+            }
+            Repr::Heap(vec) => vec,
+        }
+    }
+}
+
+impl Default for CompVec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for CompVec {
+    fn drop(&mut self) {
+        if let Repr::Inline { len, items } = &mut self.repr {
+            for item in items[..*len].iter_mut() {
+                // SAFETY: the first `len` items are initialized.
+                unsafe { item.assume_init_drop() };
+            }
+        }
+    }
+}
+
+impl Deref for CompVec {
+    type Target = [Comparator];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl DerefMut for CompVec {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl Clone for CompVec {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl std::fmt::Debug for CompVec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl PartialEq for CompVec {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for CompVec {}
+
+impl FromIterator<Comparator> for CompVec {
+    fn from_iter<T: IntoIterator<Item = Comparator>>(iter: T) -> Self {
+        let mut vec = Self::new();
+        for c in iter {
+            vec.push(c);
+        }
+        vec
+    }
+}
+
+impl From<Vec<Comparator>> for CompVec {
+    fn from(vec: Vec<Comparator>) -> Self {
+        match vec.len() {
+            0..=INLINE_CAP => vec.into_iter().collect(),
+            _ => Self {
+                repr: Repr::Heap(vec),
+            },
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a CompVec {
+    type Item = &'a Comparator;
+    type IntoIter = std::slice::Iter<'a, Comparator>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,48 @@
+use pretty_assertions::assert_eq;
+
+use super::*;
+use crate::test::req;
+
+fn comparators(n: usize) -> Vec<Comparator> {
+    if n == 0 {
+        return Vec::new();
+    }
+    let input = (1..=n)
+        .map(|i| format!(">={i}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    req(&input).comparators.to_vec()
+}
+
+#[test]
+fn inline() {
+    for n in 0..=INLINE_CAP {
+        let vec = comparators(n).into_iter().collect::<CompVec>();
+        assert!(vec.is_inline());
+        assert_eq!(comparators(n), vec.as_slice());
+    }
+}
+
+#[test]
+fn spill_to_heap() {
+    for n in INLINE_CAP + 1..10 {
+        let vec = comparators(n).into_iter().collect::<CompVec>();
+        assert!(!vec.is_inline());
+        assert_eq!(comparators(n), vec.as_slice());
+        assert_eq!(vec, vec.clone());
+    }
+}
+
+#[test]
+fn from_vec() {
+    assert!(CompVec::from(comparators(2)).is_inline());
+    assert!(!CompVec::from(comparators(3)).is_inline());
+    assert_eq!(comparators(3), CompVec::from(comparators(3)).as_slice());
+}
+
+#[test]
+fn parsed_requirement_is_inline() {
+    assert!(req("^1.2.3-alpha.1").comparators.is_inline());
+    assert!(req(">=1.2, <1.5").comparators.is_inline());
+    assert!(!req(">=1.2, <1.5, <1.4").comparators.is_inline());
+}
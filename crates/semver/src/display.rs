@@ -1,4 +1,7 @@
-use crate::{BuildMetadata, CompVersion, Comparator, Op, Prerelease, Version, VersionReq, WlChar};
+use crate::{
+    BuildMetadata, CompVersion, Comparator, PartialVersion, Prerelease, Version, VersionRef,
+    VersionReq, WlChar,
+};
 use std::fmt::Write;
 
 struct Wrapper<'a, 'b> {
@@ -83,17 +86,7 @@ fn fmt_comparator(f: &mut Wrapper<'_, '_>, comparator: &Comparator) -> std::fmt:
 
     f.pos = op_offset.char as usize;
 
-    match op {
-        Op::Eq => write!(f, "=")?,
-        Op::Lt => write!(f, "<")?,
-        Op::Le => write!(f, "<=")?,
-        Op::Gt => write!(f, ">")?,
-        Op::Ge => write!(f, ">=")?,
-        Op::Cr => write!(f, "^")?,
-        Op::Tl => write!(f, "~")?,
-        Op::Wl => (),
-        Op::Bl => (),
-    }
+    f.write_str(op.as_str())?;
 
     let num_spaces = version_offset.char as usize - f.pos;
     write!(f, "{:num_spaces$}", "")?;
@@ -135,6 +128,32 @@ fn fmt_comp_version(f: &mut Wrapper<'_, '_>, version: &CompVersion) -> std::fmt:
     }
 }
 
+impl std::fmt::Display for VersionRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major(), self.minor(), self.patch())?;
+        if !self.pre().is_empty() {
+            write!(f, "-{}", self.pre())?;
+        }
+        if !self.meta().is_empty() {
+            write!(f, "+{}", self.meta())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for PartialVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.major)?;
+        if let Some(minor) = self.minor {
+            write!(f, ".{minor}")?;
+        }
+        if let Some(patch) = self.patch {
+            write!(f, ".{patch}")?;
+        }
+        Ok(())
+    }
+}
+
 impl std::fmt::Display for Version {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let Self {
@@ -1,27 +1,87 @@
-use common::diagnostic::{Diagnostic, DiagnosticHint, Severity};
+use common::diagnostic::{Diagnostic, DiagnosticHint, Footer, Severity};
 use common::{FmtChar, FmtStr, Pos, Span};
 
 use crate::{parse, IdentField, NumField, WlChar};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Error {
-    TrailingCharacters(FmtStr, Option<IdentField>, Pos),
+    TrailingCharacters(FmtStr, Option<IdentField>, Span),
     MissingField(NumField, Pos),
-    LeadingZeroNum(NumField, Pos),
-    InvalidIntChar(FmtChar, NumField, Pos),
-    IntOverflow(NumField, Pos, u32),
-    ExpectedDot(FmtChar, NumField, Pos),
+    LeadingZeroNum(NumField, Span),
+    InvalidIntChar(FmtChar, NumField, Span),
+    IntOverflow(NumField, Span),
+    ExpectedDot(FmtChar, NumField, Span),
     MissingDot(NumField, Pos),
     EmptyIdentifier(IdentField, Pos),
     EmptyIdentifierSegment(IdentField, Pos),
-    LeadingZeroSegment(IdentField, Pos),
-    InvalidOp(FmtChar, Pos),
+    LeadingZeroSegment(IdentField, Span),
+    InvalidOp(FmtChar, Span),
     MissingComma(Pos),
-    TrailingComma(Pos),
-    WildcardNotTheSoleComparator(WlChar, Pos),
-    ExcessiveComparators(Pos, u32),
+    TrailingComma(Span),
+    WildcardNotTheSoleComparator(WlChar, Span),
+    ExcessiveComparators(Span),
     EmptyVersionReq(Pos),
-    UnexpectedAfterWildcard(FmtChar, NumField, Pos),
+    UnexpectedAfterWildcard(FmtChar, NumField, Span),
+    /// A hyphen range as used by npm, for example `1.2 - 1.4`, with the cargo equivalent.
+    HyphenRange(FmtStr, Span),
+    /// Alternatives separated by `||` as used by npm. The cargo equivalent is only known if the
+    /// alternatives form a single range.
+    LogicalOr(Option<FmtStr>, Span),
+    /// A `v` prefix as used by git tags, for example `v1.2.3`, with the version without it.
+    VersionPrefix(FmtStr, Span),
+    /// A version with more than three components as used by python, for example `1.2.3.4`,
+    /// with the first three components.
+    ExtraVersionComponent(FmtStr, Span),
+    /// The `==` operator as used by python.
+    DoubleEquals(Span),
+    /// A prerelease or build metadata in a [`crate::PartialVersion`].
+    UnexpectedIdentifier(IdentField, Span),
+}
+
+impl Error {
+    /// Maps all spans and positions of this error using `f`. Positions are mapped as empty
+    /// spans. This is useful when the parsed text doesn't directly correspond to the source,
+    /// for example when a version requirement is contained in a string with escape sequences.
+    pub fn map_spans(&mut self, mut f: impl FnMut(Span) -> Span) {
+        let mut map_pos = |p: &mut Pos| *p = f(Span::pos(*p)).start;
+        match self {
+            Error::TrailingCharacters(_, _, s) => *s = f(*s),
+            Error::MissingField(_, p) => map_pos(p),
+            Error::LeadingZeroNum(_, s) => *s = f(*s),
+            Error::InvalidIntChar(_, _, s) => *s = f(*s),
+            Error::IntOverflow(_, s) => *s = f(*s),
+            Error::ExpectedDot(_, _, s) => *s = f(*s),
+            Error::MissingDot(_, p) => map_pos(p),
+            Error::EmptyIdentifier(_, p) => map_pos(p),
+            Error::EmptyIdentifierSegment(_, p) => map_pos(p),
+            Error::LeadingZeroSegment(_, s) => *s = f(*s),
+            Error::InvalidOp(_, s) => *s = f(*s),
+            Error::MissingComma(p) => map_pos(p),
+            Error::TrailingComma(s) => *s = f(*s),
+            Error::WildcardNotTheSoleComparator(_, s) => *s = f(*s),
+            Error::ExcessiveComparators(s) => *s = f(*s),
+            Error::EmptyVersionReq(p) => map_pos(p),
+            Error::UnexpectedAfterWildcard(_, _, s) => *s = f(*s),
+            Error::HyphenRange(_, s) => *s = f(*s),
+            Error::LogicalOr(_, s) => *s = f(*s),
+            Error::VersionPrefix(_, s) => *s = f(*s),
+            Error::ExtraVersionComponent(_, s) => *s = f(*s),
+            Error::DoubleEquals(s) => *s = f(*s),
+            Error::UnexpectedIdentifier(_, s) => *s = f(*s),
+        }
+    }
+
+    /// The text that should replace the text inside [`Diagnostic::span`], if there is a fix.
+    pub fn fix(&self) -> Option<FmtStr> {
+        match self {
+            Error::HyphenRange(r, _) => Some(r.clone()),
+            Error::LogicalOr(r, _) => r.clone(),
+            Error::VersionPrefix(r, _) => Some(r.clone()),
+            Error::ExtraVersionComponent(r, _) => Some(r.clone()),
+            Error::DoubleEquals(_) => Some(FmtStr::from_str("=")),
+            _ => None,
+        }
+    }
 }
 
 impl Diagnostic for Error {
@@ -31,23 +91,29 @@ impl Diagnostic for Error {
 
     fn span(&self) -> Span {
         match self {
-            Error::TrailingCharacters(s, _, p) => Span::from_pos_len(*p, s.len() as u32),
+            Error::TrailingCharacters(_, _, s) => *s,
             Error::MissingField(_, p) => Span::pos(*p),
-            Error::LeadingZeroNum(_, p) => Span::ascii_char(*p),
-            Error::InvalidIntChar(c, _, p) => Span::from_pos_len(*p, c.len_utf8() as u32),
-            Error::IntOverflow(_, p, l) => Span::from_pos_len(*p, *l),
-            Error::ExpectedDot(c, _, p) => Span::from_pos_len(*p, c.len_utf8() as u32),
+            Error::LeadingZeroNum(_, s) => *s,
+            Error::InvalidIntChar(_, _, s) => *s,
+            Error::IntOverflow(_, s) => *s,
+            Error::ExpectedDot(_, _, s) => *s,
             Error::MissingDot(_, p) => Span::pos(*p),
             Error::EmptyIdentifier(_, p) => Span::pos(*p),
             Error::EmptyIdentifierSegment(_, p) => Span::pos(*p),
-            Error::LeadingZeroSegment(_, p) => Span::ascii_char(*p),
-            Error::InvalidOp(c, p) => Span::from_pos_len(*p, c.len_utf8() as u32),
+            Error::LeadingZeroSegment(_, s) => *s,
+            Error::InvalidOp(_, s) => *s,
             Error::MissingComma(p) => Span::pos(*p),
-            Error::TrailingComma(p) => Span::ascii_char(*p),
-            Error::WildcardNotTheSoleComparator(_, p) => Span::ascii_char(*p),
-            Error::ExcessiveComparators(p, l) => Span::from_pos_len(*p, *l),
+            Error::TrailingComma(s) => *s,
+            Error::WildcardNotTheSoleComparator(_, s) => *s,
+            Error::ExcessiveComparators(s) => *s,
             Error::EmptyVersionReq(p) => Span::pos(*p),
-            Error::UnexpectedAfterWildcard(c, _, p) => Span::from_pos_len(*p, c.len_utf8() as u32),
+            Error::UnexpectedAfterWildcard(_, _, s) => *s,
+            Error::HyphenRange(_, s) => *s,
+            Error::LogicalOr(_, s) => *s,
+            Error::VersionPrefix(_, s) => *s,
+            Error::ExtraVersionComponent(_, s) => *s,
+            Error::DoubleEquals(s) => *s,
+            Error::UnexpectedIdentifier(_, s) => *s,
         }
     }
 
@@ -60,7 +126,7 @@ impl Diagnostic for Error {
             Error::MissingField(p, _) => write!(f, "missing {p}"),
             Error::LeadingZeroNum(p, _) => write!(f, "invalid leading zero in {p}"),
             Error::InvalidIntChar(c, p, _) => write!(f, "invalid character `{c}` zero in {p}"),
-            Error::IntOverflow(p, _, _) => write!(f, "integer overflow in {p}"),
+            Error::IntOverflow(p, _) => write!(f, "integer overflow in {p}"),
             Error::ExpectedDot(c, p, _) => write!(f, "expected dot after {p}, found `{c}`"),
             Error::MissingDot(p, _) => write!(f, "missing dot after {p}"),
             Error::EmptyIdentifier(p, _) => write!(f, "emtpy {p}"),
@@ -70,9 +136,17 @@ impl Diagnostic for Error {
             Error::MissingComma(_) => write!(f, "missing comma"),
             Error::TrailingComma(_) => write!(f, "invalid trailing comma"),
             Error::WildcardNotTheSoleComparator(wl, _) => write!(f, "wildcard `{wl}` must be the only comparator"),
-            Error::ExcessiveComparators(_, _) => write!(f, "excessive number of comparators, the maximum allowed number is {}", parse::MAX_COMPARATORS),
+            Error::ExcessiveComparators(_) => write!(f, "excessive number of comparators, the maximum allowed number is {}", parse::MAX_COMPARATORS),
             Error::EmptyVersionReq(_) => write!(f, "empty version requirement"),
             Error::UnexpectedAfterWildcard(c, p, _) => write!(f, "unexpected character `{c}` after wildcard {p}"),
+            Error::HyphenRange(r, _) => write!(f, "hyphen ranges aren't supported, use `{r}` instead"),
+            Error::LogicalOr(Some(r), _) => write!(f, "`||` isn't supported, use `{r}` instead"),
+            Error::LogicalOr(None, _) => write!(f, "`||` isn't supported, a version requirement can't have alternatives"),
+            Error::VersionPrefix(r, _) => write!(f, "versions can't have a `v` prefix, use `{r}` instead"),
+            Error::ExtraVersionComponent(r, _) => write!(f, "versions can only have three components, use `{r}` instead"),
+            Error::DoubleEquals(_) => write!(f, "invalid operator `==`, use `=` for an exact version"),
+            Error::UnexpectedIdentifier(IdentField::Prerelease, _) => write!(f, "unexpected pre-release identifier, expected a version like `1.70`"),
+            Error::UnexpectedIdentifier(IdentField::BuildMetadata, _) => write!(f, "unexpected build-metadata, expected a version like `1.70`"),
         }
     }
 
@@ -85,7 +159,7 @@ impl Diagnostic for Error {
             Error::MissingField(p, _) => write!(f, "missing {p}"),
             Error::LeadingZeroNum(_, _) => write!(f, "invalid leading zero"),
             Error::InvalidIntChar(_, _, _) => write!(f, "invalid character"),
-            Error::IntOverflow(_, _, _) => write!(f, "integer overflow"),
+            Error::IntOverflow(_, _) => write!(f, "integer overflow"),
             Error::ExpectedDot(_, _, _) => write!(f, "expected dot"),
             Error::MissingDot(_, _) => write!(f, "missing dot"),
             Error::EmptyIdentifier(p, _) => write!(f, "emtpy {p}"),
@@ -97,15 +171,92 @@ impl Diagnostic for Error {
             Error::WildcardNotTheSoleComparator(_, _) => {
                 write!(f, "wildcard must be the only comparator")
             }
-            Error::ExcessiveComparators(_, _) => write!(f, "excessive number of comparators"),
+            Error::ExcessiveComparators(_) => write!(f, "excessive number of comparators"),
             Error::EmptyVersionReq(_) => write!(f, "empty version requirement"),
             Error::UnexpectedAfterWildcard(_, _, _) => write!(f, "unexpected character"),
+            Error::HyphenRange(_, _) => write!(f, "hyphen range"),
+            Error::LogicalOr(_, _) => write!(f, "alternatives"),
+            Error::VersionPrefix(_, _) => write!(f, "`v` prefix"),
+            Error::ExtraVersionComponent(_, _) => write!(f, "more than three components"),
+            Error::DoubleEquals(_) => write!(f, "invalid operator"),
+            Error::UnexpectedIdentifier(IdentField::Prerelease, _) => {
+                write!(f, "unexpected pre-release identifier")
+            }
+            Error::UnexpectedIdentifier(IdentField::BuildMetadata, _) => {
+                write!(f, "unexpected build-metadata")
+            }
+        }
+    }
+
+    fn footers(&self) -> Vec<Footer> {
+        match self.fix() {
+            Some(fix) => vec![Footer::help(replace_with(&fix))],
+            None => Vec::new(),
         }
     }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub enum Warning {}
+pub enum Warning {
+    /// A comparator that doesn't change the set of matched versions. The optional span points
+    /// to a comparator that is at least as strict.
+    RedundantComparator(FmtStr, Span, Option<Span>),
+    /// Two comparators that can't be satisfied at the same time, the second span points to the
+    /// earlier comparator.
+    ContradictoryComparators(FmtStr, FmtStr, Span, Span),
+    /// A requirement that doesn't match any version, but no two comparators contradict each
+    /// other on their own.
+    UnsatisfiableRequirement(Span),
+    /// A requirement that can be written in a simpler form.
+    SimplifiableRequirement(FmtStr, Span),
+    /// A `*` requirement, which crates.io rejects.
+    WildcardRequirement(Span),
+    /// A requirement without an upper bound, with an optional fix.
+    UnboundedRequirement(Span, Option<FmtStr>),
+    /// Build metadata, which is ignored when matching versions. The span only covers the
+    /// metadata including the `+`.
+    BuildMetadataIgnored(FmtStr, Span),
+    /// A requirement that only matches pre-releases, with an optional fix.
+    PrereleaseOnlyRequirement(Span, Option<FmtStr>),
+}
+
+impl Warning {
+    /// Maps all spans of this warning using `f`, see [`Error::map_spans`].
+    pub fn map_spans(&mut self, mut f: impl FnMut(Span) -> Span) {
+        match self {
+            Warning::RedundantComparator(_, s, h) => {
+                *s = f(*s);
+                if let Some(h) = h {
+                    *h = f(*h);
+                }
+            }
+            Warning::ContradictoryComparators(_, _, s, h) => {
+                *s = f(*s);
+                *h = f(*h);
+            }
+            Warning::UnsatisfiableRequirement(s) => *s = f(*s),
+            Warning::SimplifiableRequirement(_, s) => *s = f(*s),
+            Warning::WildcardRequirement(s) => *s = f(*s),
+            Warning::UnboundedRequirement(s, _) => *s = f(*s),
+            Warning::BuildMetadataIgnored(_, s) => *s = f(*s),
+            Warning::PrereleaseOnlyRequirement(s, _) => *s = f(*s),
+        }
+    }
+
+    /// The text that should replace the text inside [`Diagnostic::span`], if there is a fix.
+    pub fn fix(&self) -> Option<FmtStr> {
+        match self {
+            Warning::RedundantComparator(_, _, _) => None,
+            Warning::ContradictoryComparators(_, _, _, _) => None,
+            Warning::UnsatisfiableRequirement(_) => None,
+            Warning::SimplifiableRequirement(s, _) => Some(s.clone()),
+            Warning::WildcardRequirement(_) => None,
+            Warning::UnboundedRequirement(_, fix) => fix.clone(),
+            Warning::BuildMetadataIgnored(_, _) => Some(FmtStr::empty()),
+            Warning::PrereleaseOnlyRequirement(_, fix) => fix.clone(),
+        }
+    }
+}
 
 impl Diagnostic for Warning {
     type Hint = Hint;
@@ -113,20 +264,121 @@ impl Diagnostic for Warning {
     const SEVERITY: Severity = Severity::Warning;
 
     fn span(&self) -> Span {
-        unreachable!()
+        match self {
+            Warning::RedundantComparator(_, s, _) => *s,
+            Warning::ContradictoryComparators(_, _, s, _) => *s,
+            Warning::UnsatisfiableRequirement(s) => *s,
+            Warning::SimplifiableRequirement(_, s) => *s,
+            Warning::WildcardRequirement(s) => *s,
+            Warning::UnboundedRequirement(s, _) => *s,
+            Warning::BuildMetadataIgnored(_, s) => *s,
+            Warning::PrereleaseOnlyRequirement(s, _) => *s,
+        }
+    }
+
+    fn description(&self, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        match self {
+            Warning::RedundantComparator(c, _, _) => write!(
+                f,
+                "redundant comparator `{c}`, it doesn't change the matched versions"
+            ),
+            Warning::ContradictoryComparators(a, b, _, _) => write!(
+                f,
+                "comparators `{a}` and `{b}` contradict each other, no version can satisfy both"
+            ),
+            Warning::UnsatisfiableRequirement(_) => {
+                write!(f, "version requirement can never be satisfied")
+            }
+            Warning::SimplifiableRequirement(s, _) => {
+                write!(f, "version requirement can be simplified to `{s}`")
+            }
+            Warning::WildcardRequirement(_) => write!(
+                f,
+                "wildcard requirement `*` matches every version, crates.io doesn't accept it"
+            ),
+            Warning::UnboundedRequirement(_, _) => write!(
+                f,
+                "version requirement has no upper bound, future breaking releases will match"
+            ),
+            Warning::BuildMetadataIgnored(m, _) => write!(
+                f,
+                "build metadata `+{m}` is ignored when matching versions"
+            ),
+            Warning::PrereleaseOnlyRequirement(_, _) => write!(
+                f,
+                "version requirement only matches pre-releases, it will never match a stable release"
+            ),
+        }
+    }
+
+    fn annotation(&self, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        match self {
+            Warning::RedundantComparator(_, _, _) => write!(f, "redundant"),
+            Warning::ContradictoryComparators(_, _, _, _) => write!(f, "contradiction"),
+            Warning::UnsatisfiableRequirement(_) => write!(f, "never satisfied"),
+            Warning::SimplifiableRequirement(s, _) => write!(f, "can be simplified to `{s}`"),
+            Warning::WildcardRequirement(_) => write!(f, "matches every version"),
+            Warning::UnboundedRequirement(_, _) => write!(f, "no upper bound"),
+            Warning::BuildMetadataIgnored(_, _) => write!(f, "ignored"),
+            Warning::PrereleaseOnlyRequirement(_, _) => write!(f, "only matches pre-releases"),
+        }
     }
 
-    fn description(&self, _f: &mut impl std::fmt::Write) -> std::fmt::Result {
-        unreachable!()
+    fn footers(&self) -> Vec<Footer> {
+        match self {
+            Warning::WildcardRequirement(_) => vec![Footer::help(
+                "specify the oldest compatible version, for example `^1.2`",
+            )],
+            Warning::BuildMetadataIgnored(_, _) => {
+                vec![Footer::help("remove the build metadata")]
+            }
+            Warning::UnboundedRequirement(_, Some(fix))
+            | Warning::PrereleaseOnlyRequirement(_, Some(fix)) => {
+                vec![Footer::help(replace_with(fix))]
+            }
+            _ => Vec::new(),
+        }
     }
 
-    fn annotation(&self, _f: &mut impl std::fmt::Write) -> std::fmt::Result {
-        unreachable!()
+    fn hint(&self) -> Option<Self::Hint> {
+        match self {
+            Warning::RedundantComparator(_, _, h) => h.map(Hint::ImpliedBy),
+            Warning::ContradictoryComparators(_, _, _, h) => Some(Hint::Contradicts(*h)),
+            Warning::UnsatisfiableRequirement(_) => None,
+            Warning::SimplifiableRequirement(_, _) => None,
+            Warning::WildcardRequirement(_) => None,
+            Warning::UnboundedRequirement(_, _) => None,
+            Warning::BuildMetadataIgnored(_, _) => None,
+            Warning::PrereleaseOnlyRequirement(_, _) => None,
+        }
     }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub enum Info {}
+pub enum Info {
+    /// An exact `=` requirement in a library, with the requirement that should be used instead.
+    ExactVersionInLibrary(Span, FmtStr),
+    /// A caret requirement on a `0.0.x` version, which only matches that exact version.
+    CaretZeroZeroIsExact(FmtStr, Span),
+}
+
+impl Info {
+    /// Maps all spans of this info using `f`, see [`Error::map_spans`].
+    pub fn map_spans(&mut self, mut f: impl FnMut(Span) -> Span) {
+        match self {
+            Info::ExactVersionInLibrary(s, _) => *s = f(*s),
+            Info::CaretZeroZeroIsExact(_, s) => *s = f(*s),
+        }
+    }
+
+    /// The text that should replace the text inside [`Diagnostic::span`], if there is a fix.
+    pub fn fix(&self) -> Option<FmtStr> {
+        match self {
+            Info::ExactVersionInLibrary(_, fix) => Some(fix.clone()),
+            Info::CaretZeroZeroIsExact(_, _) => None,
+        }
+    }
+}
 
 impl Diagnostic for Info {
     type Hint = Hint;
@@ -134,28 +386,65 @@ impl Diagnostic for Info {
     const SEVERITY: Severity = Severity::Info;
 
     fn span(&self) -> Span {
-        unreachable!()
+        match self {
+            Info::ExactVersionInLibrary(s, _) => *s,
+            Info::CaretZeroZeroIsExact(_, s) => *s,
+        }
     }
 
-    fn description(&self, _f: &mut impl std::fmt::Write) -> std::fmt::Result {
-        unreachable!()
+    fn description(&self, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        match self {
+            Info::ExactVersionInLibrary(_, _) => write!(
+                f,
+                "exact version requirement in a library, this can cause resolution conflicts for dependents"
+            ),
+            Info::CaretZeroZeroIsExact(v, _) => write!(
+                f,
+                "caret requirement on `{v}` only matches exactly this version"
+            ),
+        }
     }
 
-    fn annotation(&self, _f: &mut impl std::fmt::Write) -> std::fmt::Result {
-        unreachable!()
+    fn annotation(&self, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        match self {
+            Info::ExactVersionInLibrary(_, _) => write!(f, "exact version"),
+            Info::CaretZeroZeroIsExact(_, _) => write!(f, "acts as an exact version"),
+        }
     }
+
+    fn footers(&self) -> Vec<Footer> {
+        match self {
+            Info::ExactVersionInLibrary(_, fix) => vec![Footer::help(replace_with(fix))],
+            Info::CaretZeroZeroIsExact(_, _) => vec![Footer::note(
+                "for `0.0.x` versions every patch release is considered a breaking change",
+            )],
+        }
+    }
+}
+
+fn replace_with(fix: &FmtStr) -> FmtStr {
+    FmtStr::from_string(format!("replace with `{fix}`"))
 }
 
 #[derive(Debug, PartialEq, Eq)]
-pub enum Hint {}
+pub enum Hint {
+    ImpliedBy(Span),
+    Contradicts(Span),
+}
 
 impl DiagnosticHint for Hint {
     fn span(&self) -> Span {
-        unreachable!()
+        match self {
+            Hint::ImpliedBy(s) => *s,
+            Hint::Contradicts(s) => *s,
+        }
     }
 
-    fn annotation(&self, _f: &mut impl std::fmt::Write) -> std::fmt::Result {
-        unreachable!()
+    fn annotation(&self, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        match self {
+            Hint::ImpliedBy(_) => write!(f, "already implied by this comparator"),
+            Hint::Contradicts(_) => write!(f, "contradicts this comparator"),
+        }
     }
 }
 
@@ -29,7 +29,7 @@ pub(crate) fn matches_comparator(cmp: &Comparator, ver: &Version) -> bool {
     matches_impl(cmp, ver) && (ver.pre.is_empty() || pre_is_compatible(cmp, ver))
 }
 
-fn matches_impl(cmp: &Comparator, ver: &Version) -> bool {
+pub(crate) fn matches_impl(cmp: &Comparator, ver: &Version) -> bool {
     match cmp.op {
         Op::Eq | Op::Wl => matches_exact(cmp, ver),
         Op::Gt => matches_greater(cmp, ver),
@@ -145,7 +145,7 @@ fn matches_tilde(cmp: &Comparator, ver: &Version) -> bool {
     &ver.pre >= cmp.version.pre()
 }
 
-fn matches_caret(cmp: &Comparator, ver: &Version) -> bool {
+pub(crate) fn matches_caret(cmp: &Comparator, ver: &Version) -> bool {
     let Some(major) = cmp.version.major() else {
         unreachable!("^* is not a valid requirement")
     };
@@ -1,16 +1,34 @@
 use std::cmp::Ordering;
 
 use common::{Ctx, Diagnostics, Pos};
+pub use compat::CompatClass;
+pub use complete::{complete_requirement, Completion};
+pub use compvec::CompVec;
 pub use error::{Error, Hint, Info, Warning};
+pub use lint::{lint_requirement, LintOptions};
+pub use normalize::check_requirement;
 pub use parse::*;
+pub use range::{PreRange, StableRange, Triple, VersionSet};
+pub use select::{
+    highest_match, latest_compatible, latest_incompatible, lowest_match, AvailableVersion,
+    SelectOptions,
+};
 
 use crate::inlinestr::InlineStr;
 
+mod bump;
+mod compat;
+mod complete;
+mod compvec;
 mod display;
 mod error;
 mod eval;
 mod inlinestr;
+mod lint;
+mod normalize;
 mod parse;
+mod range;
+mod select;
 #[cfg(feature = "serde")]
 mod serde;
 #[cfg(test)]
@@ -63,21 +81,33 @@ pub enum IdentField {
 pub struct VersionReq {
     pub pos: Pos,
     pub len: u32,
-    pub comparators: Vec<Comparator>,
+    pub comparators: CompVec,
 }
 
 impl VersionReq {
-    pub fn new(pos: Pos, len: u32, comparators: Vec<Comparator>) -> Self {
+    pub fn new(pos: Pos, len: u32, comparators: impl Into<CompVec>) -> Self {
         Self {
             pos,
             len,
-            comparators,
+            comparators: comparators.into(),
         }
     }
 
     pub fn matches(&self, version: &Version) -> bool {
         eval::matches_requirement(self, version)
     }
+
+    /// Rewrite this requirement so its minimum is `version`, while keeping the operators,
+    /// precision, wildcard characters and whitespace. Upper bounds that would exclude `version`
+    /// are raised, other comparators are kept as is.
+    pub fn bump_to(&self, version: &Version) -> String {
+        bump::bump_requirement(self, version)
+    }
+
+    /// Whether the requirement has to be bumped to allow `version`.
+    pub fn needs_bump(&self, version: &Version) -> bool {
+        !self.matches(version)
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -172,6 +202,22 @@ impl CompVersion {
     }
 }
 
+impl Op {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Op::Eq => "=",
+            Op::Lt => "<",
+            Op::Le => "<=",
+            Op::Gt => ">",
+            Op::Ge => ">=",
+            Op::Cr => "^",
+            Op::Tl => "~",
+            Op::Wl => "",
+            Op::Bl => "",
+        }
+    }
+}
+
 /// Wildcard character
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum WlChar {
@@ -202,6 +248,199 @@ pub struct Version {
     pub meta: BuildMetadata,
 }
 
+/// A [`Version`] that borrows its prerelease and build metadata, see [`parse_version_ref`]. The
+/// fields are private, since the prerelease and build metadata have to be validated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VersionRef<'a> {
+    major: u32,
+    minor: u32,
+    patch: u32,
+    pre: &'a str,
+    meta: &'a str,
+}
+
+impl<'a> VersionRef<'a> {
+    pub fn major(&self) -> u32 {
+        self.major
+    }
+
+    pub fn minor(&self) -> u32 {
+        self.minor
+    }
+
+    pub fn patch(&self) -> u32 {
+        self.patch
+    }
+
+    /// Empty if there is no prerelease.
+    pub fn pre(&self) -> &'a str {
+        self.pre
+    }
+
+    /// Empty if there is no build metadata.
+    pub fn meta(&self) -> &'a str {
+        self.meta
+    }
+
+    pub fn to_version(&self) -> Version {
+        // SAFETY: the prerelease and build metadata have been validated by the parser, and only
+        // contain `[0-9a-zA-Z-.]` characters.
+        let (pre, meta) = unsafe {
+            let pre = InlineStr::new_unchecked(self.pre);
+            let meta = InlineStr::new_unchecked(self.meta);
+            (pre, meta)
+        };
+        Version {
+            major: self.major,
+            minor: self.minor,
+            patch: self.patch,
+            pre: Prerelease { str: pre },
+            meta: BuildMetadata { str: meta },
+        }
+    }
+}
+
+/// A version with an optional minor and patch version and neither a prerelease nor build
+/// metadata, as used by `package.rust-version`. Missing components are treated as `0` when
+/// comparing with a [`Version`], so `1.70` is equal to `1.70.0`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PartialVersion {
+    pub major: u32,
+    pub minor: Option<u32>,
+    /// This is only `Some` if [`Self::minor`] is.
+    pub patch: Option<u32>,
+}
+
+impl PartialVersion {
+    pub fn new(major: u32, minor: Option<u32>, patch: Option<u32>) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// The full version, with missing components set to `0`.
+    pub fn to_version(&self) -> Version {
+        Version {
+            major: self.major,
+            minor: self.minor.unwrap_or(0),
+            patch: self.patch.unwrap_or(0),
+            pre: Prerelease::EMPTY,
+            meta: BuildMetadata::EMPTY,
+        }
+    }
+
+    fn precision(&self) -> u8 {
+        self.minor.is_some() as u8 + self.patch.is_some() as u8
+    }
+}
+
+impl From<&Version> for PartialVersion {
+    fn from(v: &Version) -> Self {
+        Self::new(v.major, Some(v.minor), Some(v.patch))
+    }
+}
+
+impl PartialOrd for PartialVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PartialVersion {
+    /// Missing components are treated as `0`, if the versions are otherwise equal the less
+    /// precise one is ordered first.
+    fn cmp(&self, other: &Self) -> Ordering {
+        let triple = |v: &Self| (v.major, v.minor.unwrap_or(0), v.patch.unwrap_or(0));
+        (triple(self).cmp(&triple(other))).then(self.precision().cmp(&other.precision()))
+    }
+}
+
+impl PartialEq<Version> for PartialVersion {
+    fn eq(&self, other: &Version) -> bool {
+        self.to_version() == *other
+    }
+}
+
+impl PartialOrd<Version> for PartialVersion {
+    fn partial_cmp(&self, other: &Version) -> Option<Ordering> {
+        Some(self.to_version().cmp(other))
+    }
+}
+
+impl PartialEq<PartialVersion> for Version {
+    fn eq(&self, other: &PartialVersion) -> bool {
+        other == self
+    }
+}
+
+impl PartialOrd<PartialVersion> for Version {
+    fn partial_cmp(&self, other: &PartialVersion) -> Option<Ordering> {
+        other.partial_cmp(self).map(Ordering::reverse)
+    }
+}
+
+impl Version {
+    /// Increment the major version and reset the minor and patch version. A prerelease of a new
+    /// major version, like `2.0.0-alpha`, is released as `2.0.0` instead. Returns `None` on
+    /// overflow.
+    #[must_use]
+    pub fn checked_bump_major(&self) -> Option<Version> {
+        let major = if self.pre.is_empty() || self.minor != 0 || self.patch != 0 {
+            self.major.checked_add(1)?
+        } else {
+            self.major
+        };
+        Some(Version::release(major, 0, 0))
+    }
+
+    /// Increment the minor version and reset the patch version. A prerelease of a new minor
+    /// version, like `1.3.0-alpha`, is released as `1.3.0` instead. Returns `None` on overflow.
+    #[must_use]
+    pub fn checked_bump_minor(&self) -> Option<Version> {
+        let minor = if self.pre.is_empty() || self.patch != 0 {
+            self.minor.checked_add(1)?
+        } else {
+            self.minor
+        };
+        Some(Version::release(self.major, minor, 0))
+    }
+
+    /// Increment the patch version. A prerelease, like `1.2.3-alpha`, is released as `1.2.3`
+    /// instead. Returns `None` on overflow.
+    #[must_use]
+    pub fn checked_bump_patch(&self) -> Option<Version> {
+        let patch = if self.pre.is_empty() {
+            self.patch.checked_add(1)?
+        } else {
+            self.patch
+        };
+        Some(Version::release(self.major, self.minor, patch))
+    }
+
+    fn release(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            pre: Prerelease::EMPTY,
+            meta: BuildMetadata::EMPTY,
+        }
+    }
+
+    /// Set the prerelease and clear the build metadata, see [`parse_prerelease`].
+    pub fn set_pre(&mut self, pre: Prerelease) {
+        self.pre = pre;
+        self.meta = BuildMetadata::EMPTY;
+    }
+
+    /// Clear the prerelease and build metadata.
+    pub fn clear_pre(&mut self) {
+        self.set_pre(Prerelease::EMPTY);
+    }
+}
+
 static EMPTY_PRERELEASE: Prerelease = Prerelease::EMPTY;
 
 #[derive(Clone, PartialEq, Eq)]
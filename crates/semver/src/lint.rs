@@ -0,0 +1,102 @@
+//! Lints for requirement forms that are valid, but probably don't do what was intended.
+
+use common::{FmtStr, Span};
+
+use crate::normalize::{comparator_span, requirement_span};
+use crate::{CompVersion, Comparator, Info, Op, SemverCtx, VersionReq, Warning};
+
+#[cfg(test)]
+mod test;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LintOptions {
+    /// Whether the requirement is part of a library, exact version requirements in libraries
+    /// easily lead to resolution conflicts for dependents.
+    pub library: bool,
+}
+
+/// Lint a requirement, the spans are relative to [`VersionReq::pos`]. The fix suggestions of the
+/// diagnostics replace the text inside their span.
+pub fn lint_requirement(ctx: &mut impl SemverCtx, req: &VersionReq, options: LintOptions) {
+    let set = req.to_set();
+
+    for cmp in req.comparators.iter() {
+        if let CompVersion::Wl(_) = cmp.version {
+            ctx.warn(Warning::WildcardRequirement(comparator_span(req, cmp)));
+        }
+
+        if let Some(meta) = build_metadata(&cmp.version) {
+            let version_span = comparator_span(req, cmp);
+            let meta_len = 1 + meta.len() as u32;
+            let span = Span::new(version_span.end.minus(meta_len), version_span.end);
+            ctx.warn(Warning::BuildMetadataIgnored(FmtStr::from_str(meta), span));
+        }
+
+        match (cmp.op, &cmp.version) {
+            (Op::Eq, CompVersion::Patch(..) | CompVersion::Pre(..)) if options.library => {
+                let span = comparator_span(req, cmp);
+                let fix = FmtStr::from_string(without_meta(&cmp.version).to_string());
+                ctx.info(Info::ExactVersionInLibrary(span, fix));
+            }
+            (
+                Op::Cr | Op::Bl,
+                CompVersion::Patch(0, 0, patch, _) | CompVersion::Pre(0, 0, patch, _, _),
+            ) => {
+                let span = comparator_span(req, cmp);
+                let version = FmtStr::from_string(format!("0.0.{patch}"));
+                ctx.info(Info::CaretZeroZeroIsExact(version, span));
+            }
+            _ => (),
+        }
+    }
+
+    if set.is_empty() {
+        return;
+    }
+
+    let unbounded = (set.stable_ranges().last()).is_some_and(|r| r.end.is_none());
+    let has_wildcard = (req.comparators.iter()).any(|c| matches!(c.version, CompVersion::Wl(_)));
+    if unbounded && !has_wildcard {
+        let fix = match req.comparators.as_slice() {
+            [cmp @ Comparator { op: Op::Ge, .. }] => Some(FmtStr::from_string(format!(
+                "^{}",
+                without_meta(&cmp.version)
+            ))),
+            _ => None,
+        };
+        ctx.warn(Warning::UnboundedRequirement(requirement_span(req), fix));
+    }
+
+    if set.stable_ranges().is_empty() {
+        let fix = match req.comparators.as_slice() {
+            [cmp @ Comparator { op: Op::Eq, .. }] => Some(FmtStr::from_string(format!(
+                "^{}",
+                without_meta(&cmp.version)
+            ))),
+            _ => None,
+        };
+        ctx.warn(Warning::PrereleaseOnlyRequirement(
+            requirement_span(req),
+            fix,
+        ));
+    }
+}
+
+fn build_metadata(version: &CompVersion) -> Option<&str> {
+    match version {
+        CompVersion::Patch(_, _, _, Some(meta)) | CompVersion::Pre(_, _, _, _, Some(meta)) => {
+            Some(meta.as_str())
+        }
+        _ => None,
+    }
+}
+
+fn without_meta(version: &CompVersion) -> CompVersion {
+    match version.clone() {
+        CompVersion::Patch(major, minor, patch, _) => CompVersion::Patch(major, minor, patch, None),
+        CompVersion::Pre(major, minor, patch, pre, _) => {
+            CompVersion::Pre(major, minor, patch, pre, None)
+        }
+        v => v,
+    }
+}
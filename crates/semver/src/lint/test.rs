@@ -0,0 +1,119 @@
+use common::{Diagnostics, FmtStr, Pos, Span};
+use pretty_assertions::assert_eq;
+
+use super::*;
+use crate::test::req;
+use crate::Error;
+
+fn span(start: u32, end: u32) -> Span {
+    Span::new(Pos::new(0, start), Pos::new(0, end))
+}
+
+fn lint(input: &str, library: bool) -> (Vec<Warning>, Vec<Info>) {
+    let mut ctx = Diagnostics::<Error, Warning, Info>::default();
+    lint_requirement(&mut ctx, &req(input), LintOptions { library });
+    assert_eq!(Vec::<Error>::new(), ctx.errors);
+    (ctx.warnings, ctx.infos)
+}
+
+#[test]
+fn wildcard() {
+    let (warnings, infos) = lint("*", false);
+    assert_eq!(vec![Warning::WildcardRequirement(span(0, 1))], warnings);
+    assert_eq!(Vec::<Info>::new(), infos);
+}
+
+#[test]
+fn unbounded() {
+    let (warnings, _) = lint(">=1.2", false);
+    assert_eq!(
+        vec![Warning::UnboundedRequirement(
+            span(0, 5),
+            Some(FmtStr::from_str("^1.2"))
+        )],
+        warnings
+    );
+
+    let (warnings, _) = lint(">1.2, >=1.0", false);
+    assert_eq!(
+        vec![Warning::UnboundedRequirement(span(0, 11), None)],
+        warnings
+    );
+}
+
+#[test]
+fn exact_version_in_library() {
+    let (warnings, infos) = lint("=1.2.3", true);
+    assert_eq!(Vec::<Warning>::new(), warnings);
+    assert_eq!(
+        vec![Info::ExactVersionInLibrary(
+            span(0, 6),
+            FmtStr::from_str("1.2.3")
+        )],
+        infos
+    );
+
+    let (_, infos) = lint("=1.2.3", false);
+    assert_eq!(Vec::<Info>::new(), infos);
+
+    let (_, infos) = lint("=1.2", true);
+    assert_eq!(Vec::<Info>::new(), infos);
+}
+
+#[test]
+fn build_metadata() {
+    let (warnings, _) = lint("^1.2.3+build.5", false);
+    assert_eq!(
+        vec![Warning::BuildMetadataIgnored(
+            FmtStr::from_str("build.5"),
+            span(6, 14)
+        )],
+        warnings
+    );
+    assert_eq!(Some(FmtStr::empty()), warnings[0].fix());
+}
+
+#[test]
+fn prerelease_only() {
+    let (warnings, _) = lint("=1.0.0-alpha.1", false);
+    assert_eq!(
+        vec![Warning::PrereleaseOnlyRequirement(
+            span(0, 14),
+            Some(FmtStr::from_str("^1.0.0-alpha.1"))
+        )],
+        warnings
+    );
+
+    let (warnings, _) = lint(">=1.0.0-alpha, <1.0.0", false);
+    assert_eq!(
+        vec![Warning::PrereleaseOnlyRequirement(span(0, 21), None)],
+        warnings
+    );
+}
+
+#[test]
+fn caret_zero_zero() {
+    for (input, end) in [("0.0.3", 5), ("^0.0.3", 6)] {
+        let (warnings, infos) = lint(input, false);
+        assert_eq!(Vec::<Warning>::new(), warnings);
+        assert_eq!(
+            vec![Info::CaretZeroZeroIsExact(
+                FmtStr::from_str("0.0.3"),
+                span(0, end)
+            )],
+            infos
+        );
+    }
+
+    let (_, infos) = lint("^0.0", false);
+    assert_eq!(Vec::<Info>::new(), infos);
+}
+
+#[test]
+fn no_lints() {
+    for r in ["1.2.3", "^0.3", "~1.2", ">=1.2, <1.7", "1.*", "<0.0.0"] {
+        let (warnings, infos) = lint(r, true);
+        assert_eq!(Vec::<Warning>::new(), warnings, "`{r}`");
+        assert_eq!(Vec::<Info>::new(), infos, "`{r}`");
+    }
+}
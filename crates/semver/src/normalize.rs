@@ -0,0 +1,198 @@
+//! Find redundant and contradictory comparators, and the simplest equivalent form of a
+//! requirement.
+
+use common::{FmtStr, Pos, Span};
+
+use crate::{
+    parse_requirement, CompVec, CompVersion, Comparator, Op, StableRange, VersionReq, VersionSet,
+    Warning,
+};
+
+#[cfg(test)]
+mod test;
+
+/// Check a requirement for comparators that are redundant or contradict each other, and whether
+/// it can be written in a simpler form. The spans are relative to [`VersionReq::pos`].
+pub fn check_requirement(req: &VersionReq) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+    let set = req.to_set();
+
+    if set.is_empty() {
+        let cmps = &req.comparators;
+        for (i, b) in cmps.iter().enumerate() {
+            for a in cmps[..i].iter() {
+                if sub_req(req, [a, b]).is_empty() {
+                    let other = comparator_span(req, a);
+                    let warning = Warning::ContradictoryComparators(
+                        comparator_str(a),
+                        comparator_str(b),
+                        comparator_span(req, b),
+                        other,
+                    );
+                    warnings.push(warning);
+                    return warnings;
+                }
+            }
+        }
+        warnings.push(Warning::UnsatisfiableRequirement(requirement_span(req)));
+        return warnings;
+    }
+
+    let kept = non_redundant(req);
+    for (i, cmp) in req.comparators.iter().enumerate() {
+        if kept.contains(&i) {
+            continue;
+        }
+
+        // A single comparator that is at least as strict.
+        let cmp_req = sub_req(req, [cmp]);
+        let implied_by = (kept.iter())
+            .map(|j| &req.comparators[*j])
+            .find(|other| sub_req(req, [*other]).is_subset_of(&cmp_req))
+            .map(|other| comparator_span(req, other));
+        let span = comparator_span(req, cmp);
+        warnings.push(Warning::RedundantComparator(
+            comparator_str(cmp),
+            span,
+            implied_by,
+        ));
+    }
+
+    let simplified = req.simplify();
+    let num_simplified = simplified.split(',').count();
+    let has_wildcard = req.comparators.iter().any(|c| has_wildcard(&c.version));
+    if num_simplified < kept.len() || has_wildcard && simplified != req_str(req) {
+        let span = requirement_span(req);
+        let simplified = FmtStr::from_string(simplified);
+        warnings.push(Warning::SimplifiableRequirement(simplified, span));
+    }
+
+    warnings
+}
+
+impl VersionReq {
+    /// The simplest requirement that matches the same versions, for example `>=1.2, <2` is
+    /// simplified to `^1.2`. Blank requirements are used instead of `^`, if this requirement
+    /// contains any blank or wildcard comparators.
+    pub fn simplify(&self) -> String {
+        let caret = match (self.comparators.iter()).any(|c| matches!(c.op, Op::Bl | Op::Wl)) {
+            true => "",
+            false => "^",
+        };
+        if let Some(simplified) = simplify_set(&self.to_set(), caret) {
+            return simplified;
+        }
+
+        let kept = non_redundant(self);
+        let strs = kept.iter().map(|i| comparator_text(&self.comparators[*i]));
+        strs.collect::<Vec<_>>().join(", ")
+    }
+}
+
+/// The simplest requirement matching exactly `set`, if `set` is a single range of stable versions.
+pub(crate) fn simplify_set(set: &VersionSet, caret: &str) -> Option<String> {
+    let ([range], []) = (set.stable_ranges(), set.pre_ranges()) else {
+        return None;
+    };
+    candidates(*range, caret).into_iter().find(|candidate| {
+        let req = parse_requirement(candidate, Pos::ZERO);
+        req.is_ok_and(|req| req.to_set() == *set)
+    })
+}
+
+/// Possible simple forms of a range, shortest first.
+fn candidates(range: StableRange, caret: &str) -> Vec<String> {
+    let s = range.start;
+    let (major, minor, patch) = (s.major, s.minor, s.patch);
+    let start = match (minor, patch) {
+        (0, 0) => format!("{major}"),
+        (_, 0) => format!("{major}.{minor}"),
+        _ => format!("{major}.{minor}.{patch}"),
+    };
+
+    let Some(e) = range.end else {
+        if s == crate::Triple::ZERO {
+            return vec!["*".into()];
+        }
+        return vec![format!(">={start}")];
+    };
+    let end = match (e.minor, e.patch) {
+        (0, 0) => format!("{}", e.major),
+        (_, 0) => format!("{}.{}", e.major, e.minor),
+        _ => format!("{}.{}.{}", e.major, e.minor, e.patch),
+    };
+
+    let mut candidates = Vec::new();
+    if patch == 0 {
+        if minor == 0 {
+            candidates.push(format!("{caret}{major}"));
+        }
+        candidates.push(format!("{caret}{major}.{minor}"));
+        candidates.push(format!("~{major}.{minor}"));
+    }
+    candidates.push(format!("{caret}{major}.{minor}.{patch}"));
+    candidates.push(format!("~{major}.{minor}.{patch}"));
+    candidates.push(format!("={major}.{minor}.{patch}"));
+    if s == crate::Triple::ZERO {
+        candidates.push(format!("<{end}"));
+    }
+    candidates.push(format!(">={start}, <{end}"));
+    candidates
+}
+
+/// Indices of the comparators that are needed, redundant ones are removed from left to right.
+fn non_redundant(req: &VersionReq) -> Vec<usize> {
+    let set = req.to_set();
+    let mut kept = (0..req.comparators.len()).collect::<Vec<_>>();
+    let mut i = 0;
+    while i < kept.len() {
+        let others = kept.iter().filter(|j| **j != kept[i]);
+        let without = sub_req(req, others.map(|j| &req.comparators[*j]));
+        if !without.comparators.is_empty() && without.to_set() == set {
+            kept.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+    kept
+}
+
+fn sub_req<'a>(req: &VersionReq, cmps: impl IntoIterator<Item = &'a Comparator>) -> VersionReq {
+    let comparators = cmps.into_iter().cloned().collect::<CompVec>();
+    VersionReq::new(req.pos, req.len, comparators)
+}
+
+fn has_wildcard(version: &CompVersion) -> bool {
+    matches!(
+        version,
+        CompVersion::Major(_, Some(_)) | CompVersion::Minor(_, _, Some(_))
+    )
+}
+
+pub(crate) fn comparator_text(cmp: &Comparator) -> String {
+    format!("{}{}", cmp.op.as_str(), cmp.version)
+}
+
+fn comparator_str(cmp: &Comparator) -> FmtStr {
+    FmtStr::from_string(comparator_text(cmp))
+}
+
+fn req_str(req: &VersionReq) -> String {
+    let strs = req.comparators.iter().map(comparator_text);
+    strs.collect::<Vec<_>>().join(", ")
+}
+
+pub(crate) fn comparator_span(req: &VersionReq, cmp: &Comparator) -> Span {
+    let version_len = cmp.version.to_string().len() as u32;
+    let start = req.pos.plus(cmp.op_offset.char);
+    let end = req.pos.plus(cmp.version_offset.char + version_len);
+    Span::new(start, end)
+}
+
+pub(crate) fn requirement_span(req: &VersionReq) -> Span {
+    match req.comparators.as_slice() {
+        [first, .., last] => Span::across(comparator_span(req, first), comparator_span(req, last)),
+        [cmp] => comparator_span(req, cmp),
+        [] => Span::pos(req.pos),
+    }
+}
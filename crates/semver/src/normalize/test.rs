@@ -0,0 +1,127 @@
+use common::{FmtStr, Pos, Span};
+use pretty_assertions::assert_eq;
+
+use super::*;
+use crate::test::req;
+
+fn span(start: u32, end: u32) -> Span {
+    Span::new(Pos::new(0, start), Pos::new(0, end))
+}
+
+#[test]
+fn simplify() {
+    assert_eq!("^1", req(">=1.0, <2.0").simplify());
+    assert_eq!("^1.2", req("^1.0.0, >=1.2").simplify());
+    assert_eq!("1", req("1.*").simplify());
+    assert_eq!("~1.2", req("1.2.*").simplify());
+    assert_eq!("^0.3", req(">=0.3.0, <0.4").simplify());
+    assert_eq!("=1.2.3", req(">=1.2.3, <=1.2.3").simplify());
+    assert_eq!("*", req(">=0.0.0").simplify());
+    assert_eq!(">=1.4", req(">=1.2, >=1.4").simplify());
+    assert_eq!(">=1.2, <1.7", req(">=1.2, <1.7").simplify());
+    assert_eq!("<1.7", req("<1.7").simplify());
+    assert_eq!("1.2.3", req("1.2.3").simplify());
+}
+
+#[test]
+fn simplify_keeps_prereleases() {
+    assert_eq!(
+        ">=1.0.0-alpha, <1.0.0-beta",
+        req(">=1.0.0-alpha, <1.0.0-beta").simplify()
+    );
+    assert_eq!(
+        ">=1.0.0-alpha, <2",
+        req(">=1.0.0-alpha, >=0.5, <2").simplify()
+    );
+}
+
+#[test]
+fn redundant_comparator() {
+    let warnings = check_requirement(&req(">=1.2, >=1.4"));
+    assert_eq!(
+        vec![Warning::RedundantComparator(
+            FmtStr::from_str(">=1.2"),
+            span(0, 5),
+            Some(span(7, 12)),
+        )],
+        warnings
+    );
+
+    let warnings = check_requirement(&req("^1.2, <3"));
+    assert_eq!(
+        vec![Warning::RedundantComparator(
+            FmtStr::from_str("<3"),
+            span(6, 8),
+            Some(span(0, 4)),
+        )],
+        warnings
+    );
+}
+
+#[test]
+fn duplicate_comparator_is_reported_once() {
+    let warnings = check_requirement(&req("1.2, 1.2"));
+    assert_eq!(
+        vec![Warning::RedundantComparator(
+            FmtStr::from_str("1.2"),
+            span(0, 3),
+            Some(span(5, 8)),
+        )],
+        warnings
+    );
+}
+
+#[test]
+fn contradictory_comparators() {
+    let warnings = check_requirement(&req(">=2, <1"));
+    assert_eq!(
+        vec![Warning::ContradictoryComparators(
+            FmtStr::from_str(">=2"),
+            FmtStr::from_str("<1"),
+            span(5, 7),
+            span(0, 3),
+        )],
+        warnings
+    );
+
+    let warnings = check_requirement(&req("<0.0.0"));
+    assert_eq!(
+        vec![Warning::UnsatisfiableRequirement(span(0, 6))],
+        warnings
+    );
+}
+
+#[test]
+fn simplifiable_requirement() {
+    let warnings = check_requirement(&req(">=1.0, <2.0"));
+    assert_eq!(
+        vec![Warning::SimplifiableRequirement(
+            FmtStr::from_str("^1"),
+            span(0, 11)
+        )],
+        warnings
+    );
+
+    let warnings = check_requirement(&req("1.*"));
+    assert_eq!(
+        vec![Warning::SimplifiableRequirement(
+            FmtStr::from_str("1"),
+            span(0, 3)
+        )],
+        warnings
+    );
+}
+
+#[test]
+fn no_warnings() {
+    for r in [
+        "1.2.3",
+        "^0.3",
+        "~1.2",
+        ">=1.2, <1.7",
+        "*",
+        ">=1.0.0-alpha, <1.0.0",
+    ] {
+        assert_eq!(Vec::<Warning>::new(), check_requirement(&req(r)), "`{r}`");
+    }
+}
@@ -1,9 +1,9 @@
-use common::{FmtChar, FmtStr, Pos};
+use common::{FmtChar, FmtStr, Pos, Span};
 
 use crate::inlinestr::InlineStr;
 use crate::{
-    BuildMetadata, CompVersion, Comparator, Error, IdentField, NumField, Offset, Op, Prerelease,
-    Version, VersionReq, WlChar,
+    normalize, BuildMetadata, CompVec, CompVersion, Comparator, Error, IdentField, NumField,
+    Offset, Op, PartialVersion, Prerelease, Version, VersionRef, VersionReq, VersionSet, WlChar,
 };
 
 #[cfg(test)]
@@ -50,14 +50,13 @@ impl<'a> CharIter<'a> {
 }
 
 pub fn parse_requirement(input: &str, pos: Pos) -> Result<VersionReq, Error> {
+    if input.contains("||") {
+        return Err(logical_or(input, pos));
+    }
+
     let mut chars = CharIter::new(pos, input);
-    let num_commas = input.as_bytes().iter().filter(|b| **b == b',').count();
-    // If there is no comma, there are probably no comparators or one. If it's only one the push
-    // won't have to reallocate anyway, but if there are 0 we saved an allocation. For 1 or more
-    // commas this might allocate space for one extra item, but will never have to reallocate.
-    let capacity = num_commas + (num_commas != 0) as usize;
-    let capacity = capacity.max(MAX_COMPARATORS);
-    let mut comparators = Vec::with_capacity(capacity);
+    // Requirements with up to `compvec::INLINE_CAP` comparators don't allocate.
+    let mut comparators = CompVec::new();
     let mut last_comma = None;
 
     loop {
@@ -70,13 +69,17 @@ pub fn parse_requirement(input: &str, pos: Pos) -> Result<VersionReq, Error> {
         if comparators.len() >= MAX_COMPARATORS {
             let pos = chars.pos();
             let len = chars.remainder().len() as u32;
-            return Err(Error::ExcessiveComparators(pos, len));
+            return Err(Error::ExcessiveComparators(Span::from_pos_len(pos, len)));
         }
 
         let op_offset = chars.offset();
         let mut op = match b {
             // Blank version requirement, equivalent to caret
             b'0'..=b'9' => Op::Bl,
+            // Handled as part of the version
+            b'v' | b'V' if matches!(chars.remainder().as_bytes().get(1), Some(b'0'..=b'9')) => {
+                Op::Bl
+            }
             b'*' | b'x' | b'X' => {
                 let wl = match b {
                     b'*' => WlChar::Star,
@@ -86,8 +89,8 @@ pub fn parse_requirement(input: &str, pos: Pos) -> Result<VersionReq, Error> {
                 };
 
                 if !comparators.is_empty() {
-                    let pos = pos.plus(op_offset.char);
-                    return Err(Error::WildcardNotTheSoleComparator(wl, pos));
+                    let span = Span::ascii_char(pos.plus(op_offset.char));
+                    return Err(Error::WildcardNotTheSoleComparator(wl, span));
                 }
 
                 chars.next_byte();
@@ -96,8 +99,8 @@ pub fn parse_requirement(input: &str, pos: Pos) -> Result<VersionReq, Error> {
                     Some(b',' | b' ') => (),
                     Some(_) => {
                         let c = FmtChar(chars.peek_char().unwrap());
-                        let pos = chars.pos();
-                        return Err(Error::UnexpectedAfterWildcard(c, NumField::Major, pos));
+                        let span = Span::from_pos_len(chars.pos(), c.len_utf8() as u32);
+                        return Err(Error::UnexpectedAfterWildcard(c, NumField::Major, span));
                     }
                     None => (),
                 }
@@ -110,12 +113,12 @@ pub fn parse_requirement(input: &str, pos: Pos) -> Result<VersionReq, Error> {
                         chars.next_byte();
                         eat_whitespace(&mut chars);
                         if chars.peek_byte().is_none() {
-                            return Err(Error::TrailingComma(pos));
+                            return Err(Error::TrailingComma(Span::ascii_char(pos)));
                         }
                     }
 
-                    let pos = pos.plus(op_offset.char);
-                    return Err(Error::WildcardNotTheSoleComparator(wl, pos));
+                    let span = Span::ascii_char(pos.plus(op_offset.char));
+                    return Err(Error::WildcardNotTheSoleComparator(wl, span));
                 }
 
                 last_comma = expect_comma_or_end(&mut chars)?;
@@ -133,6 +136,10 @@ pub fn parse_requirement(input: &str, pos: Pos) -> Result<VersionReq, Error> {
             }
             b'=' => {
                 chars.next_byte();
+                if chars.peek_byte() == Some(b'=') {
+                    let span = Span::from_pos_len(chars.pos().minus(1), 2);
+                    return Err(Error::DoubleEquals(span));
+                }
                 Op::Eq
             }
             b'<' => {
@@ -163,16 +170,30 @@ pub fn parse_requirement(input: &str, pos: Pos) -> Result<VersionReq, Error> {
             }
             _ => {
                 let char = FmtChar(chars.peek_char().unwrap());
-                let pos = chars.pos();
-                return Err(Error::InvalidOp(char, pos));
+                let span = Span::from_pos_len(chars.pos(), char.len_utf8() as u32);
+                return Err(Error::InvalidOp(char, span));
             }
         };
 
         eat_whitespace(&mut chars);
+        version_prefix(&mut chars, &mut op)?;
         let version_offset = chars.offset();
         let version = comp_version(&mut chars, &mut op)?;
+        let version_end = chars.idx;
 
         eat_whitespace(&mut chars);
+        if op == Op::Bl && chars.peek_byte() == Some(b'-') {
+            chars.next_byte();
+            eat_whitespace(&mut chars);
+            let upper_start = chars.idx;
+            comp_version(&mut chars, &mut Op::Bl)?;
+
+            let lower = &input[version_offset.char as usize..version_end];
+            let upper = &input[upper_start..chars.idx];
+            let replacement = FmtStr::from_string(format!(">={lower}, <={upper}"));
+            let span = Span::new(pos.plus(op_offset.char), chars.pos());
+            return Err(Error::HyphenRange(replacement, span));
+        }
         last_comma = expect_comma_or_end(&mut chars)?;
 
         let comparator = Comparator {
@@ -187,8 +208,8 @@ pub fn parse_requirement(input: &str, pos: Pos) -> Result<VersionReq, Error> {
 
     if let Some(offset) = last_comma {
         // TODO: store error and continue
-        let pos = pos.plus(offset.char);
-        return Err(Error::TrailingComma(pos));
+        let span = Span::ascii_char(pos.plus(offset.char));
+        return Err(Error::TrailingComma(span));
     }
 
     if comparators.is_empty() {
@@ -203,7 +224,47 @@ pub fn parse_requirement(input: &str, pos: Pos) -> Result<VersionReq, Error> {
     })
 }
 
+/// Alternatives separated by `||`, as used by npm. If the alternatives form a single range, the
+/// equivalent cargo requirement is suggested.
+fn logical_or(input: &str, pos: Pos) -> Error {
+    let start = input.len() - input.trim_start_matches(' ').len();
+    let end = input.trim_end_matches(' ').len();
+    let span = Span::new(pos.plus(start as u32), pos.plus(end as u32));
+
+    let mut set = VersionSet::empty();
+    let mut offset = 0;
+    for alternative in input.split("||") {
+        let alt_pos = pos.plus(offset as u32);
+        offset += alternative.len() + 2;
+        match parse_requirement(alternative, alt_pos) {
+            Ok(req) => set = set.union(&req.to_set()),
+            Err(_) => return Error::LogicalOr(None, span),
+        }
+    }
+
+    let replacement = normalize::simplify_set(&set, "^").map(FmtStr::from_string);
+    Error::LogicalOr(replacement, span)
+}
+
+/// A `v` prefix, as used by git tags.
+fn version_prefix(chars: &mut CharIter, op: &mut Op) -> Result<(), Error> {
+    let bytes = chars.remainder().as_bytes();
+    let (Some(b'v' | b'V'), Some(b'0'..=b'9')) = (bytes.first(), bytes.get(1)) else {
+        return Ok(());
+    };
+
+    let start = chars.pos();
+    chars.next_byte();
+    let version_start = chars.idx;
+    comp_version(chars, op)?;
+
+    let version = FmtStr::from_str(&chars.str[version_start..chars.idx]);
+    let span = Span::new(start, chars.pos());
+    Err(Error::VersionPrefix(version, span))
+}
+
 fn comp_version(chars: &mut CharIter, op: &mut Op) -> Result<CompVersion, Error> {
+    let start = chars.pos();
     let major = parse_int(chars, NumField::Major)?;
     if !eat_dot(chars) {
         return Ok(CompVersion::Major(major, None));
@@ -225,7 +286,7 @@ fn comp_version(chars: &mut CharIter, op: &mut Op) -> Result<CompVersion, Error>
             Some(c) => Err(Error::UnexpectedAfterWildcard(
                 FmtChar(c),
                 NumField::Minor,
-                pos,
+                Span::from_pos_len(pos, c.len_utf8() as u32),
             )),
             None => Err(Error::MissingField(NumField::Patch, pos)),
         };
@@ -243,6 +304,16 @@ fn comp_version(chars: &mut CharIter, op: &mut Op) -> Result<CompVersion, Error>
     }
     let patch = parse_int(chars, NumField::Patch)?;
 
+    // More than three components, as used by python.
+    if let [b'.', b'0'..=b'9', ..] = chars.remainder().as_bytes() {
+        while let Some(b'.' | b'0'..=b'9') = chars.peek_byte() {
+            chars.next_byte();
+        }
+        let replacement = FmtStr::from_string(format!("{major}.{minor}.{patch}"));
+        let span = Span::new(start, chars.pos());
+        return Err(Error::ExtraVersionComponent(replacement, span));
+    }
+
     let mut pre = None;
     let mut meta = None;
     if eat_hyphen(chars) {
@@ -263,6 +334,12 @@ fn comp_version(chars: &mut CharIter, op: &mut Op) -> Result<CompVersion, Error>
 }
 
 pub fn parse_version(input: &str, pos: Pos) -> Result<Version, Error> {
+    parse_version_ref(input, pos).map(|v| v.to_version())
+}
+
+/// Parse a version without allocating, the prerelease and build metadata are borrowed from
+/// `input`.
+pub fn parse_version_ref(input: &str, pos: Pos) -> Result<VersionRef<'_>, Error> {
     let mut chars = CharIter::new(pos, input);
 
     eat_whitespace(&mut chars);
@@ -273,27 +350,21 @@ pub fn parse_version(input: &str, pos: Pos) -> Result<Version, Error> {
     expect_dot(&mut chars, NumField::Minor)?;
     let patch = parse_int(&mut chars, NumField::Patch)?;
 
-    let pre = if eat_hyphen(&mut chars) {
-        let ident = parse_ident(&mut chars, IdentField::Prerelease)?;
-        let str = unsafe { InlineStr::new_unchecked(ident) };
-        Prerelease { str }
-    } else {
-        Prerelease::EMPTY
-    };
+    let mut pre = "";
+    if eat_hyphen(&mut chars) {
+        pre = parse_ident(&mut chars, IdentField::Prerelease)?;
+    }
 
-    let meta = if eat_plus(&mut chars) {
-        let ident = parse_ident(&mut chars, IdentField::BuildMetadata)?;
-        let str = unsafe { InlineStr::new_unchecked(ident) };
-        BuildMetadata { str }
-    } else {
-        BuildMetadata::EMPTY
-    };
+    let mut meta = "";
+    if eat_plus(&mut chars) {
+        meta = parse_ident(&mut chars, IdentField::BuildMetadata)?;
+    }
 
     eat_whitespace(&mut chars);
 
     if chars.peek_byte().is_some() {
         let trailing = FmtStr::from_str(chars.remainder().trim_end_matches(' '));
-        let pos = chars.pos();
+        let span = Span::from_pos_len(chars.pos(), trailing.len() as u32);
         let field = if !meta.is_empty() {
             Some(IdentField::BuildMetadata)
         } else if pre.is_empty() {
@@ -301,10 +372,10 @@ pub fn parse_version(input: &str, pos: Pos) -> Result<Version, Error> {
         } else {
             None
         };
-        return Err(Error::TrailingCharacters(trailing, field, pos));
+        return Err(Error::TrailingCharacters(trailing, field, span));
     }
 
-    Ok(Version {
+    Ok(VersionRef {
         major,
         minor,
         patch,
@@ -313,6 +384,65 @@ pub fn parse_version(input: &str, pos: Pos) -> Result<Version, Error> {
     })
 }
 
+pub fn parse_partial_version(input: &str, pos: Pos) -> Result<PartialVersion, Error> {
+    let mut chars = CharIter::new(pos, input);
+
+    eat_whitespace(&mut chars);
+
+    let major = parse_int(&mut chars, NumField::Major)?;
+    let mut minor = None;
+    let mut patch = None;
+    if eat_dot(&mut chars) {
+        minor = Some(parse_int(&mut chars, NumField::Minor)?);
+        if eat_dot(&mut chars) {
+            patch = Some(parse_int(&mut chars, NumField::Patch)?);
+        }
+    }
+
+    let field = match chars.peek_byte() {
+        Some(b'-') => Some(IdentField::Prerelease),
+        Some(b'+') => Some(IdentField::BuildMetadata),
+        _ => None,
+    };
+    if let Some(field) = field {
+        let len = chars.remainder().trim_end_matches(' ').len() as u32;
+        let span = Span::from_pos_len(chars.pos(), len);
+        return Err(Error::UnexpectedIdentifier(field, span));
+    }
+
+    eat_whitespace(&mut chars);
+
+    if chars.peek_byte().is_some() {
+        let trailing = FmtStr::from_str(chars.remainder().trim_end_matches(' '));
+        let span = Span::from_pos_len(chars.pos(), trailing.len() as u32);
+        return Err(Error::TrailingCharacters(trailing, None, span));
+    }
+
+    Ok(PartialVersion {
+        major,
+        minor,
+        patch,
+    })
+}
+
+pub fn parse_prerelease(input: &str, pos: Pos) -> Result<Prerelease, Error> {
+    let mut chars = CharIter::new(pos, input);
+    let ident = parse_ident(&mut chars, IdentField::Prerelease)?;
+
+    if chars.peek_byte().is_some() {
+        let trailing = FmtStr::from_str(chars.remainder());
+        let span = Span::from_pos_len(chars.pos(), trailing.len() as u32);
+        return Err(Error::TrailingCharacters(
+            trailing,
+            Some(IdentField::Prerelease),
+            span,
+        ));
+    }
+
+    let str = unsafe { InlineStr::new_unchecked(ident) };
+    Ok(Prerelease { str })
+}
+
 fn parse_int(chars: &mut CharIter, field: NumField) -> Result<u32, Error> {
     let start_idx = chars.idx as u32;
 
@@ -325,8 +455,8 @@ fn parse_int(chars: &mut CharIter, field: NumField) -> Result<u32, Error> {
             chars.next_byte();
             return match chars.peek_byte() {
                 Some(b'0'..=b'9') => {
-                    let pos = chars.pos().minus(1);
-                    Err(Error::LeadingZeroNum(field, pos))
+                    let span = Span::ascii_char(chars.pos().minus(1));
+                    Err(Error::LeadingZeroNum(field, span))
                 }
                 _ => Ok(0),
             };
@@ -336,9 +466,9 @@ fn parse_int(chars: &mut CharIter, field: NumField) -> Result<u32, Error> {
             c as u32 - b'0' as u32
         }
         _ => {
-            let pos = chars.pos();
             let char = FmtChar(chars.peek_char().unwrap());
-            return Err(Error::InvalidIntChar(char, field, pos));
+            let span = Span::from_pos_len(chars.pos(), char.len_utf8() as u32);
+            return Err(Error::InvalidIntChar(char, field, span));
         }
     };
 
@@ -372,7 +502,7 @@ fn parse_int(chars: &mut CharIter, field: NumField) -> Result<u32, Error> {
         State::Overflow => {
             let pos = chars.start.plus(start_idx);
             let len = chars.idx as u32 - start_idx;
-            Err(Error::IntOverflow(field, pos, len))
+            Err(Error::IntOverflow(field, Span::from_pos_len(pos, len)))
         }
     }
 }
@@ -405,8 +535,8 @@ fn parse_ident<'a>(chars: &mut CharIter<'a>, field: IdentField) -> Result<&'a st
                     && !segment_has_nondigit
                     && chars.str[segment_start..].starts_with('0')
                 {
-                    let pos = chars.start.plus(segment_start as u32);
-                    return Err(Error::LeadingZeroSegment(field, pos));
+                    let span = Span::ascii_char(chars.start.plus(segment_start as u32));
+                    return Err(Error::LeadingZeroSegment(field, span));
                 }
 
                 if boundary == Some(b'.') {
@@ -431,8 +561,8 @@ fn expect_dot(chars: &mut CharIter, field: NumField) -> Result<(), Error> {
         }
         Some(_) => {
             let char = FmtChar(chars.peek_char().unwrap());
-            let pos = chars.pos();
-            Err(Error::ExpectedDot(char, field, pos))
+            let span = Span::from_pos_len(chars.pos(), char.len_utf8() as u32);
+            Err(Error::ExpectedDot(char, field, span))
         }
         None => {
             let pos = chars.pos();
@@ -7,6 +7,10 @@ fn pos(char: u32) -> Pos {
     Pos { line: 0, char }
 }
 
+fn span(char: u32, len: u32) -> Span {
+    Span::from_pos_len(pos(char), len)
+}
+
 #[track_caller]
 fn check_version(input: &str, expected: Version) {
     let version = parse_version(input, Pos::ZERO).unwrap();
@@ -115,22 +119,22 @@ fn version_missing_num() {
     check_version_error("1", Error::MissingDot(NumField::Major, pos(1)));
     check_version_error(
         "1 ",
-        Error::ExpectedDot(' '.into(), NumField::Major, pos(1)),
+        Error::ExpectedDot(' '.into(), NumField::Major, span(1, 1)),
     );
     check_version_error("1.", Error::MissingField(NumField::Minor, pos(2)));
     check_version_error("1.0", Error::MissingDot(NumField::Minor, pos(3)));
     check_version_error(
         "1.0 ",
-        Error::ExpectedDot(' '.into(), NumField::Minor, pos(3)),
+        Error::ExpectedDot(' '.into(), NumField::Minor, span(3, 1)),
     );
     check_version_error("1.0.", Error::MissingField(NumField::Patch, pos(4)));
 }
 
 #[test]
 fn version_invalid_num() {
-    check_version_error("01.2.3", Error::LeadingZeroNum(NumField::Major, pos(0)));
-    check_version_error("1.02.3", Error::LeadingZeroNum(NumField::Minor, pos(2)));
-    check_version_error("1.2.03", Error::LeadingZeroNum(NumField::Patch, pos(4)));
+    check_version_error("01.2.3", Error::LeadingZeroNum(NumField::Major, span(0, 1)));
+    check_version_error("1.02.3", Error::LeadingZeroNum(NumField::Minor, span(2, 1)));
+    check_version_error("1.2.03", Error::LeadingZeroNum(NumField::Patch, span(4, 1)));
 }
 
 #[test]
@@ -163,7 +167,7 @@ fn check_req(input: &str, expected_comparators: Vec<Comparator>) {
     let expected = VersionReq {
         pos: Pos::ZERO,
         len: input.len() as u32,
-        comparators: expected_comparators,
+        comparators: expected_comparators.into(),
     };
     assert_eq!(expected, req);
 }
@@ -306,30 +310,38 @@ fn req_wildcard_invalid_after_operator() {
     let ops = ["=", "<", "<=", ">", ">=", "^", "~"];
     for op in ops {
         let req = format!("{op}*");
-        let offset = pos(op.len() as u32);
+        let span = span(op.len() as u32, 1);
         check_req_error(
             &req,
-            Error::InvalidIntChar('*'.into(), NumField::Major, offset),
+            Error::InvalidIntChar('*'.into(), NumField::Major, span),
         );
     }
 }
 
 #[test]
 fn req_trailing_comma() {
-    check_req_error("> 0.1.0,", Error::TrailingComma(pos(7)));
-    check_req_error("> 0.3.0, ,", Error::InvalidOp(','.into(), pos(9)));
+    check_req_error("> 0.1.0,", Error::TrailingComma(span(7, 1)));
+    check_req_error("> 0.3.0, ,", Error::InvalidOp(','.into(), span(9, 1)));
 }
 
 #[test]
-fn req_invalid_separator() {
-    check_req_error("1.2.3 - 2.3.4", Error::MissingComma(pos(6)));
+fn req_hyphen_range() {
+    check_req_error(
+        "1.2.3 - 2.3.4",
+        Error::HyphenRange(FmtStr::from_str(">=1.2.3, <=2.3.4"), span(0, 13)),
+    );
+    check_req_error(
+        " 1.2-1.4 ",
+        Error::HyphenRange(FmtStr::from_str(">=1.2, <=1.4"), span(1, 7)),
+    );
+    check_req_error(">=1.2 - 1.4", Error::MissingComma(pos(6)));
 }
 
 #[test]
 fn req_excessive_comparators() {
     check_req_error(
         ">1, >2, >3, >4, >5, >6, >7, >8, >9, >10, >11, >12, >13, >14, >15, >16, >17, >18, >19, >20, >21, >22, >23, >24, >25, >26, >27, >28, >29, >30, >31, >32, >33",
-        Error::ExcessiveComparators(pos(151), 3),
+        Error::ExcessiveComparators(span(151, 3)),
     );
 }
 
@@ -344,22 +356,65 @@ fn req_empty() {
 }
 
 #[test]
-fn req_invalid_logical_or_separator() {
-    check_req_error("=1.2.3 || =2.3.4", Error::MissingComma(pos(7)));
-    check_req_error("1.1 || =1.2.3", Error::MissingComma(pos(4)));
-    check_req_error("6.* || 8.* || >= 10.*", Error::MissingComma(pos(4)));
+fn req_logical_or() {
+    check_req_error("=1.2.3 || =2.3.4", Error::LogicalOr(None, span(0, 16)));
+    check_req_error(
+        "1.1 || =1.2.3",
+        Error::LogicalOr(Some(FmtStr::from_str("^1.1")), span(0, 13)),
+    );
+    check_req_error(
+        " ~1.2 || ~1.3",
+        Error::LogicalOr(Some(FmtStr::from_str(">=1.2, <1.4")), span(1, 12)),
+    );
+    check_req_error("6.* || 8.* || >= 10.*", Error::LogicalOr(None, span(0, 21)));
+    check_req_error(">=1.0 || <0.5", Error::LogicalOr(None, span(0, 13)));
+    check_req_error("1.0 || a", Error::LogicalOr(None, span(0, 8)));
+}
+
+#[test]
+fn req_version_prefix() {
+    check_req_error(
+        "v1.2.3",
+        Error::VersionPrefix(FmtStr::from_str("1.2.3"), span(0, 6)),
+    );
+    check_req_error(
+        ">= V1.2",
+        Error::VersionPrefix(FmtStr::from_str("1.2"), span(3, 4)),
+    );
+    check_req_error(
+        "^v",
+        Error::InvalidIntChar('v'.into(), NumField::Major, span(1, 1)),
+    );
+}
+
+#[test]
+fn req_extra_version_component() {
+    check_req_error(
+        "1.2.3.4",
+        Error::ExtraVersionComponent(FmtStr::from_str("1.2.3"), span(0, 7)),
+    );
+    check_req_error(
+        "~1.2.3.4.5, <2",
+        Error::ExtraVersionComponent(FmtStr::from_str("1.2.3"), span(1, 9)),
+    );
+}
+
+#[test]
+fn req_double_equals() {
+    check_req_error("==1.0", Error::DoubleEquals(span(0, 2)));
+    check_req_error(">=1, == 1.0", Error::DoubleEquals(span(5, 2)));
 }
 
 #[test]
 fn req_invalid_char() {
-    check_req_error("\0", Error::InvalidOp('\0'.into(), pos(0)));
+    check_req_error("\0", Error::InvalidOp('\0'.into(), span(0, 1)));
 }
 
 #[test]
 fn req_duplicate_operator() {
     check_req_error(
         ">= >= 0.0.2",
-        Error::InvalidIntChar('>'.into(), NumField::Major, pos(3)),
+        Error::InvalidIntChar('>'.into(), NumField::Major, span(3, 1)),
     );
 }
 
@@ -367,13 +422,13 @@ fn req_duplicate_operator() {
 fn req_too_long_operator() {
     check_req_error(
         ">== 0.0.2",
-        Error::InvalidIntChar('='.into(), NumField::Major, pos(2)),
+        Error::InvalidIntChar('='.into(), NumField::Major, span(2, 1)),
     );
 }
 
 #[test]
 fn req_non_numeric_major_version() {
-    check_req_error("a.0.0", Error::InvalidOp('a'.into(), pos(0)));
+    check_req_error("a.0.0", Error::InvalidOp('a'.into(), span(0, 1)));
 }
 
 #[test]
@@ -398,7 +453,7 @@ fn req_missing_version() {
 fn req_prerelease_leading_zero() {
     check_req_error(
         "1.2.3-01",
-        Error::LeadingZeroSegment(IdentField::Prerelease, pos(6)),
+        Error::LeadingZeroSegment(IdentField::Prerelease, span(6, 1)),
     );
 }
 
@@ -429,15 +484,15 @@ fn req_invalid_char_in_buildmetadata() {
 fn req_digit_after_wildcard() {
     check_req_error(
         "*.1",
-        Error::UnexpectedAfterWildcard('.'.into(), NumField::Major, pos(1)),
+        Error::UnexpectedAfterWildcard('.'.into(), NumField::Major, span(1, 1)),
     );
     check_req_error(
         "1.*.1",
-        Error::UnexpectedAfterWildcard('1'.into(), NumField::Minor, pos(4)),
+        Error::UnexpectedAfterWildcard('1'.into(), NumField::Minor, span(4, 1)),
     );
     check_req_error(
         ">=1.*.1",
-        Error::UnexpectedAfterWildcard('1'.into(), NumField::Minor, pos(6)),
+        Error::UnexpectedAfterWildcard('1'.into(), NumField::Minor, span(6, 1)),
     );
 }
 
@@ -445,15 +500,15 @@ fn req_digit_after_wildcard() {
 fn req_wildcard_and_another() {
     check_req_error(
         "*, 0.20.0-any",
-        Error::WildcardNotTheSoleComparator(WlChar::Star, pos(0)),
+        Error::WildcardNotTheSoleComparator(WlChar::Star, span(0, 1)),
     );
     check_req_error(
         "0.20.0-any, *",
-        Error::WildcardNotTheSoleComparator(WlChar::Star, pos(12)),
+        Error::WildcardNotTheSoleComparator(WlChar::Star, span(12, 1)),
     );
     check_req_error(
         "0.20.0-any, *, 1.0",
-        Error::WildcardNotTheSoleComparator(WlChar::Star, pos(12)),
+        Error::WildcardNotTheSoleComparator(WlChar::Star, span(12, 1)),
     );
 }
 
@@ -475,3 +530,65 @@ fn req_leading_digit_in_pre_and_build() {
         req(&format!("{} 1.2.3-1a-1a+1a-1a-1a", op));
     }
 }
+
+#[test]
+fn partial_version() {
+    let check = |input, expected: PartialVersion| {
+        let version = parse_partial_version(input, Pos::ZERO).unwrap();
+        assert_eq!(expected, version);
+        assert_eq!(input.trim(), version.to_string());
+    };
+    check("1", PartialVersion::new(1, None, None));
+    check("1.70", PartialVersion::new(1, Some(70), None));
+    check(" 1.70.2 ", PartialVersion::new(1, Some(70), Some(2)));
+}
+
+#[test]
+fn partial_version_error() {
+    let check = |input, expected: Error| {
+        let error = parse_partial_version(input, Pos::ZERO).unwrap_err();
+        assert_eq!(expected, error);
+    };
+    check("", Error::MissingField(NumField::Major, pos(0)));
+    check("1.", Error::MissingField(NumField::Minor, pos(2)));
+    check("1.70.", Error::MissingField(NumField::Patch, pos(5)));
+    check("1.07", Error::LeadingZeroNum(NumField::Minor, span(2, 1)));
+    check(
+        "1.70-beta",
+        Error::UnexpectedIdentifier(IdentField::Prerelease, span(4, 5)),
+    );
+    check(
+        "1.70.0+build ",
+        Error::UnexpectedIdentifier(IdentField::BuildMetadata, span(6, 6)),
+    );
+    check(
+        "1.70.0.1",
+        Error::TrailingCharacters(FmtStr::from_str(".1"), None, span(6, 2)),
+    );
+    check(
+        "1.70 x",
+        Error::TrailingCharacters(FmtStr::from_str("x"), None, span(5, 1)),
+    );
+}
+
+#[test]
+fn version_ref() {
+    for input in [
+        "1.2.3",
+        "1.2.3-alpha.1",
+        "1.2.3+build.5",
+        " 1.2.3-rc.1+a-very-long-build ",
+    ] {
+        let v = parse_version_ref(input, Pos::ZERO).unwrap();
+        assert_eq!(parse_version(input, Pos::ZERO).unwrap(), v.to_version());
+        assert_eq!(input.trim(), v.to_string());
+    }
+
+    let input = "1.2.3-beta.2+exp.sha.5114f85";
+    let v = parse_version_ref(input, Pos::ZERO).unwrap();
+    assert_eq!((1, 2, 3), (v.major(), v.minor(), v.patch()));
+    assert_eq!(input[6..12].as_ptr(), v.pre().as_ptr());
+    assert_eq!(input[13..].as_ptr(), v.meta().as_ptr());
+
+    check_version_error("1.2", Error::MissingDot(NumField::Minor, pos(3)));
+}
@@ -0,0 +1,459 @@
+//! Set algebra on version requirements.
+//!
+//! A [`VersionReq`] is converted into a [`VersionSet`], a normalized set of intervals, which
+//! follows the same semantics as [`VersionReq::matches`]. Stable versions and prereleases are
+//! tracked separately, because a prerelease is only matched if one of the comparators refers to
+//! the same `major.minor.patch` and has a prerelease itself.
+
+use std::cmp::Ordering;
+use std::ops::Bound;
+
+use crate::{eval, CompVersion, Comparator, Op, Prerelease, Version, VersionReq};
+
+#[cfg(test)]
+mod test;
+
+/// The `major.minor.patch` part of a version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Triple {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl Triple {
+    pub const ZERO: Self = Self::new(0, 0, 0);
+
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    pub fn of(version: &Version) -> Self {
+        Self::new(version.major, version.minor, version.patch)
+    }
+
+    /// The smallest triple with a greater major version, `None` on overflow.
+    pub fn next_major(self) -> Option<Self> {
+        let major = self.major.checked_add(1)?;
+        Some(Self::new(major, 0, 0))
+    }
+
+    /// The smallest triple with a greater minor version, `None` on overflow.
+    pub fn next_minor(self) -> Option<Self> {
+        match self.minor.checked_add(1) {
+            Some(minor) => Some(Self::new(self.major, minor, 0)),
+            None => self.next_major(),
+        }
+    }
+
+    /// The smallest triple with a greater patch version, `None` on overflow.
+    pub fn next_patch(self) -> Option<Self> {
+        match self.patch.checked_add(1) {
+            Some(patch) => Some(Self::new(self.major, self.minor, patch)),
+            None => self.next_minor(),
+        }
+    }
+
+    /// The end of the range of versions compatible with this one, following cargo's caret rules.
+    /// The leftmost non-zero component is the one that signals breaking changes, so `0.x` minor
+    /// and `0.0.x` patch releases are breaking.
+    pub fn caret_end(self) -> Option<Self> {
+        match (self.major, self.minor) {
+            (0, 0) => self.next_patch(),
+            (0, _) => self.next_minor(),
+            _ => self.next_major(),
+        }
+    }
+}
+
+impl std::fmt::Display for Triple {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// A half-open range `start..end` of stable versions. If `end` is `None` the range is unbounded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StableRange {
+    pub start: Triple,
+    pub end: Option<Triple>,
+}
+
+impl StableRange {
+    pub const FULL: Self = Self::new(Triple::ZERO, None);
+
+    pub const fn new(start: Triple, end: Option<Triple>) -> Self {
+        Self { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end.is_some_and(|end| self.start >= end)
+    }
+
+    pub fn contains(&self, triple: Triple) -> bool {
+        self.start <= triple && self.end.is_none_or(|end| triple < end)
+    }
+
+    fn intersect(&self, other: &Self) -> Self {
+        let start = self.start.max(other.start);
+        let end = match (self.end, other.end) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        Self { start, end }
+    }
+}
+
+/// A range of prereleases of a single `major.minor.patch` version. An unbounded start includes
+/// all prereleases below `end`, an unbounded end all prereleases above `start`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PreRange {
+    pub triple: Triple,
+    pub start: Bound<Prerelease>,
+    pub end: Bound<Prerelease>,
+}
+
+impl PreRange {
+    pub fn new(triple: Triple, start: Bound<Prerelease>, end: Bound<Prerelease>) -> Self {
+        Self { triple, start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match (&self.start, &self.end) {
+            (Bound::Unbounded, Bound::Excluded(e)) => is_min_pre(e),
+            (Bound::Unbounded, _) | (_, Bound::Unbounded) => false,
+            (Bound::Included(s), Bound::Included(e)) => s > e,
+            (Bound::Included(s) | Bound::Excluded(s), Bound::Included(e) | Bound::Excluded(e)) => {
+                s >= e
+            }
+        }
+    }
+
+    /// Whether the prerelease is inside this range, the triple isn't checked.
+    pub fn contains(&self, pre: &Prerelease) -> bool {
+        let after_start = match &self.start {
+            Bound::Included(s) => pre >= s,
+            Bound::Excluded(s) => pre > s,
+            Bound::Unbounded => true,
+        };
+        let before_end = match &self.end {
+            Bound::Included(e) => pre <= e,
+            Bound::Excluded(e) => pre < e,
+            Bound::Unbounded => true,
+        };
+        after_start && before_end
+    }
+
+    fn intersect(&self, other: &Self) -> Self {
+        let start = match cmp_start(&self.start, &other.start) {
+            Ordering::Less => other.start.clone(),
+            _ => self.start.clone(),
+        };
+        let end = match cmp_end(&self.end, &other.end) {
+            Ordering::Greater => other.end.clone(),
+            _ => self.end.clone(),
+        };
+        Self::new(self.triple, start, end)
+    }
+}
+
+/// A normalized set of versions. The ranges are sorted and neither overlap nor touch each other,
+/// so two sets containing the same versions compare equal.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VersionSet {
+    stable: Vec<StableRange>,
+    pre: Vec<PreRange>,
+}
+
+impl VersionSet {
+    /// A set containing no versions at all.
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// A set containing all stable versions, but no prereleases.
+    pub fn stable() -> Self {
+        Self {
+            stable: vec![StableRange::FULL],
+            pre: Vec::new(),
+        }
+    }
+
+    pub fn from_ranges(stable: Vec<StableRange>, pre: Vec<PreRange>) -> Self {
+        let mut set = Self { stable, pre };
+        set.normalize();
+        set
+    }
+
+    pub fn from_req(req: &VersionReq) -> Self {
+        let mut stable = StableRange::FULL;
+        for cmp in req.comparators.iter() {
+            match stable_range(cmp) {
+                Some(r) => stable = stable.intersect(&r),
+                None => stable = StableRange::new(Triple::ZERO, Some(Triple::ZERO)),
+            }
+        }
+
+        // Prereleases are only matched if a comparator with the same triple has a prerelease.
+        let mut pre = Vec::<PreRange>::new();
+        for cmp in req.comparators.iter() {
+            let CompVersion::Pre(major, minor, patch, probe, _) = &cmp.version else {
+                continue;
+            };
+            let triple = Triple::new(*major, *minor, *patch);
+            if pre.iter().any(|r| r.triple == triple) {
+                continue;
+            }
+
+            let full = PreRange::new(triple, Bound::Unbounded, Bound::Unbounded);
+            let range = (req.comparators.iter())
+                .map(|c| pre_range(c, triple, probe))
+                .try_fold(full, |acc, r| r.map(|r| acc.intersect(&r)));
+            pre.extend(range);
+        }
+
+        Self::from_ranges(vec![stable], pre)
+    }
+
+    pub fn stable_ranges(&self) -> &[StableRange] {
+        &self.stable
+    }
+
+    pub fn pre_ranges(&self) -> &[PreRange] {
+        &self.pre
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stable.is_empty() && self.pre.is_empty()
+    }
+
+    pub fn contains(&self, version: &Version) -> bool {
+        let triple = Triple::of(version);
+        if version.pre.is_empty() {
+            self.stable.iter().any(|r| r.contains(triple))
+        } else {
+            (self.pre.iter()).any(|r| r.triple == triple && r.contains(&version.pre))
+        }
+    }
+
+    pub fn intersect(&self, other: &Self) -> Self {
+        let mut stable = Vec::new();
+        for a in self.stable.iter() {
+            for b in other.stable.iter() {
+                stable.push(a.intersect(b));
+            }
+        }
+        let mut pre = Vec::new();
+        for a in self.pre.iter() {
+            for b in other.pre.iter().filter(|b| b.triple == a.triple) {
+                pre.push(a.intersect(b));
+            }
+        }
+        Self::from_ranges(stable, pre)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let stable = self.stable.iter().chain(other.stable.iter()).copied();
+        let pre = self.pre.iter().chain(other.pre.iter()).cloned();
+        Self::from_ranges(stable.collect(), pre.collect())
+    }
+
+    pub fn is_subset_of(&self, other: &Self) -> bool {
+        &self.intersect(other) == self
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        !self.intersect(other).is_empty()
+    }
+
+    fn normalize(&mut self) {
+        self.stable.retain(|r| !r.is_empty());
+        self.stable.sort_by_key(|r| r.start);
+        let mut stable: Vec<StableRange> = Vec::with_capacity(self.stable.len());
+        for r in self.stable.drain(..) {
+            if let Some(prev) = stable.last_mut() {
+                if prev.end.is_none_or(|end| r.start <= end) {
+                    prev.end = match (prev.end, r.end) {
+                        (Some(a), Some(b)) => Some(a.max(b)),
+                        _ => None,
+                    };
+                    continue;
+                }
+            }
+            stable.push(r);
+        }
+        self.stable = stable;
+
+        self.pre.retain(|r| !r.is_empty());
+        self.pre
+            .sort_by(|a, b| (a.triple.cmp(&b.triple)).then_with(|| cmp_start(&a.start, &b.start)));
+        let mut pre: Vec<PreRange> = Vec::with_capacity(self.pre.len());
+        for r in self.pre.drain(..) {
+            if let Some(prev) = pre.last_mut() {
+                if prev.triple == r.triple && touches(&prev.end, &r.start) {
+                    if cmp_end(&r.end, &prev.end) == Ordering::Greater {
+                        prev.end = r.end;
+                    }
+                    continue;
+                }
+            }
+            pre.push(r);
+        }
+        self.pre = pre;
+    }
+}
+
+impl VersionReq {
+    /// Convert this requirement into the set of versions it matches.
+    pub fn to_set(&self) -> VersionSet {
+        VersionSet::from_req(self)
+    }
+
+    /// The set of versions matched by both requirements.
+    pub fn intersect(&self, other: &VersionReq) -> VersionSet {
+        self.to_set().intersect(&other.to_set())
+    }
+
+    /// The set of versions matched by either requirement.
+    pub fn union(&self, other: &VersionReq) -> VersionSet {
+        self.to_set().union(&other.to_set())
+    }
+
+    /// Whether all versions matched by this requirement are also matched by `other`.
+    pub fn is_subset_of(&self, other: &VersionReq) -> bool {
+        self.to_set().is_subset_of(&other.to_set())
+    }
+
+    /// Whether this requirement can never be satisfied.
+    pub fn is_empty(&self) -> bool {
+        self.to_set().is_empty()
+    }
+
+    /// Whether there is a version matched by both requirements.
+    pub fn overlaps(&self, other: &VersionReq) -> bool {
+        self.to_set().overlaps(&other.to_set())
+    }
+}
+
+/// The range of stable versions matched by a comparator, `None` if it doesn't match any.
+fn stable_range(cmp: &Comparator) -> Option<StableRange> {
+    let range = |start, end| Some(StableRange::new(start, end));
+    let below = |end| Some(StableRange::new(Triple::ZERO, end));
+
+    let (major, minor, patch, has_pre) = match cmp.version {
+        CompVersion::Wl(_) => return Some(StableRange::FULL),
+        CompVersion::Major(major, _) => (major, None, None, false),
+        CompVersion::Minor(major, minor, _) => (major, Some(minor), None, false),
+        CompVersion::Patch(major, minor, patch, _) => (major, Some(minor), Some(patch), false),
+        CompVersion::Pre(major, minor, patch, _, _) => (major, Some(minor), Some(patch), true),
+    };
+    let start = Triple::new(major, minor.unwrap_or(0), patch.unwrap_or(0));
+    // The smallest triple that isn't matched by the given fields.
+    let next = match (minor, patch) {
+        (None, _) => start.next_major(),
+        (Some(_), None) => start.next_minor(),
+        (Some(_), Some(_)) => start.next_patch(),
+    };
+
+    match cmp.op {
+        Op::Eq | Op::Wl if has_pre => None,
+        Op::Eq | Op::Wl => range(start, next),
+        // A stable version is greater than a prerelease of the same triple.
+        Op::Gt if has_pre => range(start, None),
+        Op::Gt => range(next?, None),
+        Op::Ge => range(start, None),
+        Op::Lt => below(Some(start)),
+        Op::Le if has_pre => below(Some(start)),
+        Op::Le => below(next),
+        Op::Tl => match minor {
+            None => range(start, start.next_major()),
+            Some(_) => range(start, start.next_minor()),
+        },
+        Op::Cr | Op::Bl => match (major, minor) {
+            (_, None) => range(start, start.next_major()),
+            (0, Some(_)) if patch.is_none() || start.minor > 0 => range(start, start.next_minor()),
+            (0, Some(_)) => range(start, start.next_patch()),
+            (_, Some(_)) => range(start, start.next_major()),
+        },
+    }
+}
+
+/// The range of prereleases of `triple` matched by a comparator, `None` if it doesn't match any.
+/// `probe` is an arbitrary prerelease, which is used for comparators that match either all
+/// or none of the prereleases.
+fn pre_range(cmp: &Comparator, triple: Triple, probe: &Prerelease) -> Option<PreRange> {
+    let range = |start, end| Some(PreRange::new(triple, start, end));
+
+    if let CompVersion::Pre(major, minor, patch, pre, _) = &cmp.version {
+        if Triple::new(*major, *minor, *patch) == triple {
+            let pre = pre.clone();
+            return match cmp.op {
+                Op::Eq | Op::Wl => range(Bound::Included(pre.clone()), Bound::Included(pre)),
+                Op::Gt => range(Bound::Excluded(pre), Bound::Unbounded),
+                Op::Ge | Op::Tl | Op::Cr | Op::Bl => range(Bound::Included(pre), Bound::Unbounded),
+                Op::Lt => range(Bound::Unbounded, Bound::Excluded(pre)),
+                Op::Le => range(Bound::Unbounded, Bound::Included(pre)),
+            };
+        }
+    }
+
+    // The result doesn't depend on the prerelease itself.
+    let version = Version {
+        major: triple.major,
+        minor: triple.minor,
+        patch: triple.patch,
+        pre: probe.clone(),
+        meta: crate::BuildMetadata::EMPTY,
+    };
+    if eval::matches_impl(cmp, &version) {
+        range(Bound::Unbounded, Bound::Unbounded)
+    } else {
+        None
+    }
+}
+
+/// `0` is the lowest possible prerelease.
+fn is_min_pre(pre: &Prerelease) -> bool {
+    pre.as_str() == "0"
+}
+
+fn cmp_start(a: &Bound<Prerelease>, b: &Bound<Prerelease>) -> Ordering {
+    match (a, b) {
+        (Bound::Unbounded, Bound::Unbounded) => Ordering::Equal,
+        (Bound::Unbounded, _) => Ordering::Less,
+        (_, Bound::Unbounded) => Ordering::Greater,
+        (Bound::Included(a), Bound::Included(b)) | (Bound::Excluded(a), Bound::Excluded(b)) => {
+            a.cmp(b)
+        }
+        (Bound::Included(a), Bound::Excluded(b)) => a.cmp(b).then(Ordering::Less),
+        (Bound::Excluded(a), Bound::Included(b)) => a.cmp(b).then(Ordering::Greater),
+    }
+}
+
+fn cmp_end(a: &Bound<Prerelease>, b: &Bound<Prerelease>) -> Ordering {
+    match (a, b) {
+        (Bound::Unbounded, Bound::Unbounded) => Ordering::Equal,
+        (Bound::Unbounded, _) => Ordering::Greater,
+        (_, Bound::Unbounded) => Ordering::Less,
+        (Bound::Included(a), Bound::Included(b)) | (Bound::Excluded(a), Bound::Excluded(b)) => {
+            a.cmp(b)
+        }
+        (Bound::Included(a), Bound::Excluded(b)) => a.cmp(b).then(Ordering::Greater),
+        (Bound::Excluded(a), Bound::Included(b)) => a.cmp(b).then(Ordering::Less),
+    }
+}
+
+/// Whether a range ending at `end` overlaps or touches a range starting at `start`.
+fn touches(end: &Bound<Prerelease>, start: &Bound<Prerelease>) -> bool {
+    match (end, start) {
+        (Bound::Unbounded, _) | (_, Bound::Unbounded) => true,
+        (Bound::Excluded(e), Bound::Excluded(s)) => s < e,
+        (Bound::Included(e) | Bound::Excluded(e), Bound::Included(s) | Bound::Excluded(s)) => {
+            s <= e
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use pretty_assertions::assert_eq;
+
+use super::*;
+use crate::test::{req, version};
+
+fn stable(start: (u32, u32, u32), end: Option<(u32, u32, u32)>) -> StableRange {
+    let triple = |(major, minor, patch)| Triple::new(major, minor, patch);
+    StableRange::new(triple(start), end.map(triple))
+}
+
+#[test]
+fn caret_ranges() {
+    let set = req("1.2.3").to_set();
+    assert_eq!(&[stable((1, 2, 3), Some((2, 0, 0)))], set.stable_ranges());
+    assert!(set.pre_ranges().is_empty());
+
+    let set = req("0.2.3").to_set();
+    assert_eq!(&[stable((0, 2, 3), Some((0, 3, 0)))], set.stable_ranges());
+
+    let set = req("0.0.3").to_set();
+    assert_eq!(&[stable((0, 0, 3), Some((0, 0, 4)))], set.stable_ranges());
+
+    let set = req("0.0").to_set();
+    assert_eq!(&[stable((0, 0, 0), Some((0, 1, 0)))], set.stable_ranges());
+}
+
+#[test]
+fn wildcard_is_all_stable_versions() {
+    assert_eq!(VersionSet::stable(), req("*").to_set());
+    assert_eq!(VersionSet::stable(), req(">=0.0.0").to_set());
+}
+
+#[test]
+fn prerelease_ranges() {
+    let set = req(">=1.0.0-alpha, <1.0.0-beta").to_set();
+    assert!(set.stable_ranges().is_empty());
+    let [range] = set.pre_ranges() else {
+        panic!("expected one range, found {:?}", set.pre_ranges());
+    };
+    assert_eq!(Triple::new(1, 0, 0), range.triple);
+    assert_eq!(Bound::Included(version("0.0.0-alpha").pre), range.start);
+    assert_eq!(Bound::Excluded(version("0.0.0-beta").pre), range.end);
+}
+
+#[test]
+fn contradictions() {
+    assert!(req(">=2, <1").is_empty());
+    assert!(req("=1.0.0-alpha, =1.0.0-beta").is_empty());
+    assert!(req("<1.0.0-0, >=1").is_empty());
+    assert!(req("<0.0.0").is_empty());
+    assert!(!req(">=1.0.0-alpha, <1.0.0").is_empty());
+}
+
+#[test]
+fn overlaps_and_subsets() {
+    assert!(req("1.2").overlaps(&req("1.5")));
+    assert!(!req("1.2").overlaps(&req("2")));
+    assert!(!req("0.2").overlaps(&req("0.3")));
+    assert!(req("~1.2.3").is_subset_of(&req("1")));
+    assert!(!req("1").is_subset_of(&req("~1.2.3")));
+    assert!(req("=1.0.0-beta").is_subset_of(&req(">=1.0.0-alpha")));
+    assert!(!req("=1.0.0-beta").is_subset_of(&req(">=1.0.0")));
+}
+
+#[test]
+fn union_merges_adjacent_ranges() {
+    let set = req("~1.2").union(&req("~1.3"));
+    assert_eq!(&[stable((1, 2, 0), Some((1, 4, 0)))], set.stable_ranges());
+
+    let set = req("1").union(&req("3"));
+    assert_eq!(
+        &[
+            stable((1, 0, 0), Some((2, 0, 0))),
+            stable((3, 0, 0), Some((4, 0, 0)))
+        ],
+        set.stable_ranges()
+    );
+}
+
+#[test]
+fn overflow() {
+    let max = u32::MAX;
+    let set = req(&format!(">{max}.{max}.{max}")).to_set();
+    assert!(set.is_empty());
+
+    let set = req(&format!("^{max}")).to_set();
+    assert_eq!(&[stable((max, 0, 0), None)], set.stable_ranges());
+}
+
+/// A small xorshift generator, so the property tests are deterministic.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.below(items.len())]
+    }
+}
+
+const PRERELEASES: [&str; 5] = ["0", "alpha", "alpha.1", "beta", "rc.1"];
+
+fn random_req(rng: &mut Rng) -> VersionReq {
+    loop {
+        let num = 1 + rng.below(3);
+        let mut str = String::new();
+        for i in 0..num {
+            if i > 0 {
+                str.push_str(", ");
+            }
+            str.push_str(rng.pick(&["", "=", "<", "<=", ">", ">=", "^", "~"]));
+            let major = rng.below(3);
+            let minor = rng.below(3);
+            let patch = rng.below(3);
+            match rng.below(6) {
+                0 => str.push_str(&format!("{major}")),
+                1 => str.push_str(&format!("{major}.{minor}")),
+                2 => str.push_str(&format!("{major}.*")),
+                3 => str.push_str(&format!("{major}.{minor}.{patch}")),
+                _ => {
+                    let pre = rng.pick(&PRERELEASES);
+                    str.push_str(&format!("{major}.{minor}.{patch}-{pre}"));
+                }
+            }
+        }
+        if let Ok(req) = crate::parse_requirement(&str, common::Pos::ZERO) {
+            return req;
+        }
+    }
+}
+
+fn all_versions() -> Vec<Version> {
+    let mut versions = Vec::new();
+    for major in 0..4 {
+        for minor in 0..4 {
+            for patch in 0..4 {
+                versions.push(version(&format!("{major}.{minor}.{patch}")));
+                for pre in PRERELEASES.iter().chain(&["alpha.0", "beta.2", "zzz"]) {
+                    versions.push(version(&format!("{major}.{minor}.{patch}-{pre}")));
+                }
+            }
+        }
+    }
+    versions
+}
+
+#[test]
+fn property_set_matches_requirement() {
+    let versions = all_versions();
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    for _ in 0..500 {
+        let r = random_req(&mut rng);
+        let set = r.to_set();
+        for v in versions.iter() {
+            assert_eq!(r.matches(v), set.contains(v), "`{r}` with `{v:?}`");
+        }
+    }
+}
+
+#[test]
+fn property_algebra_matches_requirements() {
+    let versions = all_versions();
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+    for _ in 0..500 {
+        let a = random_req(&mut rng);
+        let b = random_req(&mut rng);
+        let intersection = a.intersect(&b);
+        let union = a.union(&b);
+
+        let mut any_both = false;
+        let mut a_without_b = false;
+        for v in versions.iter() {
+            let (in_a, in_b) = (a.matches(v), b.matches(v));
+            assert_eq!(
+                in_a && in_b,
+                intersection.contains(v),
+                "`{a}` ∩ `{b}` {v:?}"
+            );
+            assert_eq!(in_a || in_b, union.contains(v), "`{a}` ∪ `{b}` {v:?}");
+            any_both |= in_a && in_b;
+            a_without_b |= in_a && !in_b;
+        }
+
+        if any_both {
+            assert!(a.overlaps(&b), "`{a}` should overlap `{b}`");
+            assert!(!intersection.is_empty());
+        }
+        if !a.overlaps(&b) {
+            assert!(intersection.is_empty());
+        }
+        if a_without_b {
+            assert!(!a.is_subset_of(&b), "`{a}` shouldn't be a subset of `{b}`");
+        }
+        if a.is_subset_of(&b) {
+            assert_eq!(a.to_set(), intersection);
+        }
+        if a.is_empty() {
+            assert!(versions.iter().all(|v| !a.matches(v)), "`{a}` isn't empty");
+        }
+        assert!(a.intersect(&a).is_subset_of(&a.to_set()));
+        assert_eq!(union, b.union(&a));
+        assert_eq!(intersection, b.intersect(&a));
+    }
+}
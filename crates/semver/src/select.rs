@@ -0,0 +1,84 @@
+//! Select versions from the list of published versions of a crate, the way cargo would.
+
+use crate::{Triple, Version, VersionReq};
+
+#[cfg(test)]
+mod test;
+
+/// A published version that can be selected.
+pub trait AvailableVersion {
+    fn version(&self) -> &Version;
+
+    fn is_yanked(&self) -> bool {
+        false
+    }
+}
+
+impl AvailableVersion for Version {
+    fn version(&self) -> &Version {
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SelectOptions {
+    /// Whether yanked versions can be selected, cargo only does this if they are already locked.
+    pub yanked: bool,
+    /// Whether prereleases can be selected. Even if this is `true`, prereleases still have to be
+    /// matched by the requirement.
+    pub prerelease: bool,
+}
+
+impl SelectOptions {
+    fn allows(&self, v: &impl AvailableVersion) -> bool {
+        (self.yanked || !v.is_yanked()) && (self.prerelease || v.version().pre.is_empty())
+    }
+}
+
+/// The highest version matching `req`, which is what cargo selects when resolving.
+pub fn highest_match<'a, V: AvailableVersion>(
+    versions: &'a [V],
+    req: &VersionReq,
+    options: SelectOptions,
+) -> Option<&'a V> {
+    (versions.iter())
+        .filter(|v| options.allows(*v) && req.matches(v.version()))
+        .max_by(|a, b| a.version().cmp(b.version()))
+}
+
+/// The lowest version matching `req`, which is what cargo selects with `-Z minimal-versions`.
+pub fn lowest_match<'a, V: AvailableVersion>(
+    versions: &'a [V],
+    req: &VersionReq,
+    options: SelectOptions,
+) -> Option<&'a V> {
+    (versions.iter())
+        .filter(|v| options.allows(*v) && req.matches(v.version()))
+        .min_by(|a, b| a.version().cmp(b.version()))
+}
+
+/// The highest version that is compatible with the minimum of `req`, using cargo's caret rules.
+/// This ignores upper bounds of `req`, so the version might require the requirement to be bumped.
+pub fn latest_compatible<'a, V: AvailableVersion>(
+    versions: &'a [V],
+    req: &VersionReq,
+    options: SelectOptions,
+) -> Option<&'a V> {
+    let range = req.compatible_range()?;
+    (versions.iter())
+        .filter(|v| options.allows(*v) && range.contains(Triple::of(v.version())))
+        .max_by(|a, b| a.version().cmp(b.version()))
+}
+
+/// The highest version that is newer than all versions compatible with the minimum of `req`, so
+/// it contains breaking changes. `None` if there is no such version.
+pub fn latest_incompatible<'a, V: AvailableVersion>(
+    versions: &'a [V],
+    req: &VersionReq,
+    options: SelectOptions,
+) -> Option<&'a V> {
+    let end = req.compatible_range()?.end?;
+    (versions.iter())
+        .filter(|v| options.allows(*v) && Triple::of(v.version()) >= end)
+        .max_by(|a, b| a.version().cmp(b.version()))
+}
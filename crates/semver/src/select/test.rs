@@ -0,0 +1,126 @@
+use pretty_assertions::assert_eq;
+
+use super::*;
+use crate::test::{req, version};
+
+struct Published {
+    version: Version,
+    yanked: bool,
+}
+
+impl AvailableVersion for Published {
+    fn version(&self) -> &Version {
+        &self.version
+    }
+
+    fn is_yanked(&self) -> bool {
+        self.yanked
+    }
+}
+
+fn published(versions: &[&str]) -> Vec<Published> {
+    (versions.iter())
+        .map(|v| Published {
+            version: version(v.trim_end_matches(" (yanked)")),
+            yanked: v.ends_with(" (yanked)"),
+        })
+        .collect()
+}
+
+const VERSIONS: &[&str] = &[
+    "0.9.0",
+    "1.0.0",
+    "1.2.0",
+    "1.3.0-beta.1",
+    "1.3.0 (yanked)",
+    "1.2.5",
+    "2.0.0-alpha",
+    "2.0.0",
+    "2.1.0",
+    "3.0.0-rc.1",
+];
+
+const DEFAULT: SelectOptions = SelectOptions {
+    yanked: false,
+    prerelease: false,
+};
+const YANKED: SelectOptions = SelectOptions {
+    yanked: true,
+    prerelease: false,
+};
+const PRERELEASE: SelectOptions = SelectOptions {
+    yanked: false,
+    prerelease: true,
+};
+
+#[track_caller]
+fn check(
+    f: for<'a> fn(&'a [Published], &VersionReq, SelectOptions) -> Option<&'a Published>,
+    r: &str,
+    options: SelectOptions,
+    expected: Option<&str>,
+) {
+    let versions = published(VERSIONS);
+    let selected = f(&versions, &req(r), options).map(|v| v.version.to_string());
+    assert_eq!(expected, selected.as_deref(), "`{r}` {options:?}");
+}
+
+#[test]
+fn highest() {
+    check(highest_match, "^1.0", DEFAULT, Some("1.2.5"));
+    check(highest_match, "^1.0", YANKED, Some("1.3.0"));
+    check(
+        highest_match,
+        "^1.3.0-beta.1",
+        PRERELEASE,
+        Some("1.3.0-beta.1"),
+    );
+    check(highest_match, "^1.3.0-beta.1", YANKED, Some("1.3.0"));
+    check(highest_match, "~1.2", DEFAULT, Some("1.2.5"));
+    check(highest_match, "*", DEFAULT, Some("2.1.0"));
+    check(highest_match, "*", PRERELEASE, Some("2.1.0"));
+    check(highest_match, "^4", DEFAULT, None);
+}
+
+#[test]
+fn lowest() {
+    check(lowest_match, "^1.0", DEFAULT, Some("1.0.0"));
+    check(lowest_match, ">=1.2.1", DEFAULT, Some("1.2.5"));
+    check(lowest_match, ">=2.0.0-alpha", DEFAULT, Some("2.0.0"));
+    check(
+        lowest_match,
+        ">=2.0.0-alpha",
+        PRERELEASE,
+        Some("2.0.0-alpha"),
+    );
+    check(lowest_match, "<0.5", DEFAULT, None);
+}
+
+#[test]
+fn compatible() {
+    check(latest_compatible, "=1.0.0", DEFAULT, Some("1.2.5"));
+    check(latest_compatible, ">=1.0, <1.1", DEFAULT, Some("1.2.5"));
+    check(latest_compatible, "^0.9", DEFAULT, Some("0.9.0"));
+    check(latest_compatible, "^2.0.0-alpha", PRERELEASE, Some("2.1.0"));
+    check(latest_compatible, "^3.0.0-rc.1", DEFAULT, None);
+    check(
+        latest_compatible,
+        "^3.0.0-rc.1",
+        PRERELEASE,
+        Some("3.0.0-rc.1"),
+    );
+    check(latest_compatible, "<0.0.0", DEFAULT, None);
+    check(latest_compatible, "*", DEFAULT, Some("2.1.0"));
+    check(latest_compatible, ">=1.2", DEFAULT, Some("2.1.0"));
+}
+
+#[test]
+fn incompatible() {
+    check(latest_incompatible, "^1.0", DEFAULT, Some("2.1.0"));
+    check(latest_incompatible, "^1.0", PRERELEASE, Some("3.0.0-rc.1"));
+    check(latest_incompatible, "^0.9", DEFAULT, Some("2.1.0"));
+    check(latest_incompatible, "^2", DEFAULT, None);
+    // The requirement already matches all newer versions.
+    check(latest_incompatible, "*", DEFAULT, None);
+    check(latest_incompatible, ">=1.2", PRERELEASE, None);
+}
@@ -2,7 +2,10 @@ use common::diagnostic::Diagnostic as _;
 use common::Pos;
 use serde::de::Visitor;
 
-use crate::{parse_requirement, parse_version, Version, VersionReq};
+use crate::{
+    parse_partial_version, parse_requirement, parse_version, PartialVersion, Version, VersionRef,
+    VersionReq,
+};
 
 pub struct SerdeError(crate::Error);
 
@@ -38,6 +41,38 @@ impl<'de> Visitor<'de> for VersionVisitor {
     }
 }
 
+impl serde::Serialize for VersionRef<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl serde::Serialize for PartialVersion {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for PartialVersion {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(PartialVersionVisitor)
+    }
+}
+
+struct PartialVersionVisitor;
+
+impl<'de> Visitor<'de> for PartialVersionVisitor {
+    type Value = PartialVersion;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("A partial semver version")
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        parse_partial_version(v, Pos::ZERO).map_err(|e| E::custom(SerdeError(e)))
+    }
+}
+
 impl serde::Serialize for VersionReq {
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         serializer.collect_str(self)
@@ -1,6 +1,9 @@
 use common::Pos;
 
-use crate::{parse_requirement, parse_version, Version, VersionReq};
+use crate::{
+    parse_partial_version, parse_prerelease, parse_requirement, parse_version, PartialVersion,
+    Version, VersionReq,
+};
 
 #[track_caller]
 pub fn version(str: &str) -> Version {
@@ -246,3 +249,81 @@ fn pre() {
     let r = &req("=2.1.1-really.0");
     assert_match_all(r, &["2.1.1-really.0"]);
 }
+
+#[test]
+fn bump_version() {
+    #[track_caller]
+    fn check(v: &str, f: fn(&Version) -> Option<Version>, expected: &str) {
+        let bumped = f(&version(v)).map(|b| b.to_string());
+        assert_eq!(Some(expected), bumped.as_deref());
+    }
+
+    #[track_caller]
+    fn check_overflow(v: &str, f: fn(&Version) -> Option<Version>) {
+        let v = version(v);
+        assert_eq!(None, f(&v));
+    }
+
+    check("1.2.3", Version::checked_bump_major, "2.0.0");
+    check("1.2.3", Version::checked_bump_minor, "1.3.0");
+    check("1.2.3", Version::checked_bump_patch, "1.2.4");
+    check("1.2.3+build", Version::checked_bump_patch, "1.2.4");
+
+    check("2.0.0-alpha", Version::checked_bump_major, "2.0.0");
+    check("2.1.0-alpha", Version::checked_bump_major, "3.0.0");
+    check("1.3.0-alpha", Version::checked_bump_minor, "1.3.0");
+    check("1.3.1-alpha", Version::checked_bump_minor, "1.4.0");
+    check("1.2.3-alpha", Version::checked_bump_patch, "1.2.3");
+
+    check_overflow("4294967295.0.0", Version::checked_bump_major);
+    check_overflow("1.4294967295.1-alpha", Version::checked_bump_minor);
+    check_overflow("1.2.4294967295", Version::checked_bump_patch);
+    check(
+        "4294967295.0.0-alpha",
+        Version::checked_bump_major,
+        "4294967295.0.0",
+    );
+}
+
+#[test]
+fn bump_version_overflow() {
+    // The bumped version is only available through the returned `Option`, so an overflow has to
+    // be handled, instead of silently keeping the old version.
+    let v = version("1.2.4294967295");
+    let bumped: Option<Version> = v.checked_bump_patch();
+    assert_eq!(None, bumped);
+    assert_eq!(version("1.2.4294967295"), v);
+
+    let v = version("1.2.4294967295-alpha");
+    assert_eq!(Some(version("1.2.4294967295")), v.checked_bump_patch());
+}
+
+#[test]
+fn set_prerelease() {
+    let mut v = version("1.2.3+build");
+    v.set_pre(parse_prerelease("rc.1", Pos::ZERO).unwrap());
+    assert_eq!("1.2.3-rc.1", v.to_string());
+    v.clear_pre();
+    assert_eq!("1.2.3", v.to_string());
+
+    assert!(parse_prerelease("rc.01", Pos::ZERO).is_err());
+    assert!(parse_prerelease("rc+1", Pos::ZERO).is_err());
+    assert!(parse_prerelease("", Pos::ZERO).is_err());
+}
+
+#[test]
+fn partial_version_ordering() {
+    let partial = |str| parse_partial_version(str, Pos::ZERO).unwrap();
+
+    assert!(partial("1.70") == version("1.70.0"));
+    assert!(partial("1") == version("1.0.0"));
+    assert!(partial("1.70") < version("1.70.1"));
+    assert!(partial("1.70") > version("1.70.0-beta"));
+    assert!(version("1.71.0") > partial("1.70"));
+    assert!(version("1.69.9") < partial("1.70"));
+
+    assert!(partial("1.70") < partial("1.70.0"));
+    assert!(partial("1.70.0") < partial("1.70.1"));
+    assert!(partial("2") > partial("1.99.9"));
+    assert_eq!(partial("1.70.2"), PartialVersion::from(&version("1.70.2")));
+}
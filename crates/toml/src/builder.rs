@@ -0,0 +1,432 @@
+//! Programmatically assemble new toml documents.
+//!
+//! ```
+//! use crates_toml::DocumentBuilder;
+//!
+//! let doc = DocumentBuilder::new()
+//!     .table("package")
+//!     .key("name", "foo")
+//!     .key("version", "0.1.0")
+//!     .table("dependencies")
+//!     .comment("async runtime")
+//!     .key("tokio", "1")
+//!     .render();
+//!
+//! assert_eq!(
+//!     doc,
+//!     "\
+//! [package]
+//! name = \"foo\"
+//! version = \"0.1.0\"
+//!
+//! [dependencies]
+//! ## async runtime
+//! tokio = \"1\"
+//! "
+//! );
+//! ```
+
+use std::fmt::Write as _;
+
+use crate::datetime::{DateTime, Offset};
+use crate::map::MapInner;
+use crate::util::SimpleVal;
+
+#[cfg(test)]
+mod test;
+
+/// Arrays that would exceed this line width are split up into multiple lines.
+const MAX_WIDTH: usize = 80;
+const INDENT: &str = "    ";
+
+#[derive(Debug, Default, PartialEq)]
+pub struct DocumentBuilder {
+    items: Vec<Item>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Item {
+    Comment(String),
+    BlankLine,
+    Table(Vec<String>),
+    ArrayTable(Vec<String>),
+    Assignment(Vec<String>, SimpleVal),
+}
+
+/// A single key, or a dotted key made up of multiple segments.
+pub trait IntoKeyPath {
+    fn into_key_path(self) -> Vec<String>;
+}
+
+impl IntoKeyPath for &str {
+    fn into_key_path(self) -> Vec<String> {
+        vec![self.to_string()]
+    }
+}
+
+impl IntoKeyPath for String {
+    fn into_key_path(self) -> Vec<String> {
+        vec![self]
+    }
+}
+
+impl<const SIZE: usize> IntoKeyPath for [&str; SIZE] {
+    fn into_key_path(self) -> Vec<String> {
+        self.iter().map(|s| s.to_string()).collect()
+    }
+}
+
+impl IntoKeyPath for &[&str] {
+    fn into_key_path(self) -> Vec<String> {
+        self.iter().map(|s| s.to_string()).collect()
+    }
+}
+
+impl From<&str> for SimpleVal {
+    fn from(value: &str) -> Self {
+        SimpleVal::String(value.to_string())
+    }
+}
+
+impl From<String> for SimpleVal {
+    fn from(value: String) -> Self {
+        SimpleVal::String(value)
+    }
+}
+
+impl From<i64> for SimpleVal {
+    fn from(value: i64) -> Self {
+        SimpleVal::Int(value)
+    }
+}
+
+impl From<f64> for SimpleVal {
+    fn from(value: f64) -> Self {
+        SimpleVal::Float(value)
+    }
+}
+
+impl From<bool> for SimpleVal {
+    fn from(value: bool) -> Self {
+        SimpleVal::Bool(value)
+    }
+}
+
+impl From<DateTime> for SimpleVal {
+    fn from(value: DateTime) -> Self {
+        SimpleVal::DateTime(value)
+    }
+}
+
+impl<T: Into<SimpleVal>> From<Vec<T>> for SimpleVal {
+    fn from(value: Vec<T>) -> Self {
+        SimpleVal::Array(value.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<SimpleVal>, const SIZE: usize> From<[T; SIZE]> for SimpleVal {
+    fn from(value: [T; SIZE]) -> Self {
+        SimpleVal::Array(value.into_iter().map(Into::into).collect())
+    }
+}
+
+impl DocumentBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a new table `[<path>]`, following keys are inserted into it.
+    pub fn table(mut self, path: impl IntoKeyPath) -> Self {
+        self.items.push(Item::Table(path.into_key_path()));
+        self
+    }
+
+    /// Start a new entry of an array of tables `[[<path>]]`, following keys are inserted into it.
+    pub fn array_table(mut self, path: impl IntoKeyPath) -> Self {
+        self.items.push(Item::ArrayTable(path.into_key_path()));
+        self
+    }
+
+    /// Insert an assignment `<key> = <val>` into the current table. Tables are written as
+    /// inline tables.
+    pub fn key(mut self, key: impl IntoKeyPath, val: impl Into<SimpleVal>) -> Self {
+        self.items
+            .push(Item::Assignment(key.into_key_path(), val.into()));
+        self
+    }
+
+    /// Insert a comment, each line is written as a separate comment.
+    pub fn comment(mut self, text: impl Into<String>) -> Self {
+        self.items.push(Item::Comment(text.into()));
+        self
+    }
+
+    pub fn blank_line(mut self) -> Self {
+        self.items.push(Item::BlankLine);
+        self
+    }
+
+    /// Render the formatted toml document. Table headers that directly follow other items are
+    /// separated by a blank line.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let mut prev: Option<&Item> = None;
+        for item in self.items.iter() {
+            match item {
+                Item::Comment(text) if text.is_empty() => out.push_str("#\n"),
+                Item::Comment(text) => {
+                    for line in text.lines() {
+                        if line.is_empty() {
+                            out.push_str("#\n");
+                        } else {
+                            out.push_str("# ");
+                            out.push_str(line);
+                            out.push('\n');
+                        }
+                    }
+                }
+                Item::BlankLine => out.push('\n'),
+                Item::Table(path) | Item::ArrayTable(path) => {
+                    if let Some(Item::Assignment(..) | Item::Table(_) | Item::ArrayTable(_)) = prev
+                    {
+                        out.push('\n');
+                    }
+                    let (open, close) = match item {
+                        Item::Table(_) => ("[", "]"),
+                        _ => ("[[", "]]"),
+                    };
+                    out.push_str(open);
+                    write_key_path(&mut out, path);
+                    out.push_str(close);
+                    out.push('\n');
+                }
+                Item::Assignment(key, val) => {
+                    let line_start = out.len();
+                    write_key_path(&mut out, key);
+                    out.push_str(" = ");
+                    write_val(&mut out, line_start, val);
+                    out.push('\n');
+                }
+            }
+            prev = Some(item);
+        }
+        out
+    }
+
+    /// The values this document will contain once it's parsed. This is equal to the result of
+    /// [`crate::util::map_simple`].
+    ///
+    /// # Panics
+    ///
+    /// If a table is redefined as another datatype.
+    pub fn to_simple(&self) -> MapInner<String, SimpleVal> {
+        let mut root = MapInner::new();
+        let mut current = Vec::new();
+        for item in self.items.iter() {
+            match item {
+                Item::Comment(_) | Item::BlankLine => (),
+                Item::Table(path) => {
+                    get_table(&mut root, path);
+                    current.clone_from(path);
+                }
+                Item::ArrayTable(path) => {
+                    let (last, parents) = path.split_last().expect("empty table path");
+                    let parent = get_table(&mut root, parents);
+                    let array = parent
+                        .entry(last.clone())
+                        .or_insert_with(|| SimpleVal::Array(Vec::new()));
+                    match array {
+                        SimpleVal::Array(a) => a.push(SimpleVal::Table(MapInner::new())),
+                        v => panic!("`{last}` is already defined as {}", v.datatype()),
+                    }
+                    current.clone_from(path);
+                }
+                Item::Assignment(key, val) => {
+                    let (last, parents) = key.split_last().expect("empty key");
+                    let table = get_table(&mut root, &current);
+                    let table = get_table(table, parents);
+                    table.insert(last.clone(), clone_val(val));
+                }
+            }
+        }
+        root
+    }
+}
+
+impl std::fmt::Display for DocumentBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render())
+    }
+}
+
+fn get_table<'a>(
+    mut table: &'a mut MapInner<String, SimpleVal>,
+    path: &[String],
+) -> &'a mut MapInner<String, SimpleVal> {
+    for key in path {
+        let val = (table.entry(key.clone())).or_insert_with(|| SimpleVal::Table(MapInner::new()));
+        table = match val {
+            SimpleVal::Table(t) => t,
+            SimpleVal::Array(a) => match a.last_mut() {
+                Some(SimpleVal::Table(t)) => t,
+                _ => panic!("`{key}` is already defined as an array"),
+            },
+            v => panic!("`{key}` is already defined as {}", v.datatype()),
+        };
+    }
+    table
+}
+
+fn clone_val(val: &SimpleVal) -> SimpleVal {
+    match val {
+        SimpleVal::Table(t) => {
+            SimpleVal::Table(t.iter().map(|(k, v)| (k.clone(), clone_val(v))).collect())
+        }
+        SimpleVal::Array(a) => SimpleVal::Array(a.iter().map(clone_val).collect()),
+        SimpleVal::String(s) => SimpleVal::String(s.clone()),
+        SimpleVal::Int(i) => SimpleVal::Int(*i),
+        SimpleVal::Float(f) => SimpleVal::Float(*f),
+        SimpleVal::Bool(b) => SimpleVal::Bool(*b),
+        SimpleVal::DateTime(d) => SimpleVal::DateTime(*d),
+        SimpleVal::Invalid(s) => SimpleVal::Invalid(s.clone()),
+    }
+}
+
+fn write_key_path(out: &mut String, path: &[String]) {
+    for (i, key) in path.iter().enumerate() {
+        if i > 0 {
+            out.push('.');
+        }
+        write_key(out, key);
+    }
+}
+
+fn write_key(out: &mut String, key: &str) {
+    let is_bare = !key.is_empty()
+        && (key.bytes())
+            .all(|b| matches!(b, b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'_' | b'-'));
+    if is_bare {
+        out.push_str(key);
+    } else {
+        write_string(out, key);
+    }
+}
+
+fn write_string(out: &mut String, str: &str) {
+    out.push('"');
+    for c in str.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{8}' => out.push_str("\\b"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\u{c}' => out.push_str("\\f"),
+            '\r' => out.push_str("\\r"),
+            '\0'..='\u{1f}' | '\u{7f}' => _ = write!(out, "\\u{:04X}", c as u32),
+            _ => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Write a value, `line_start` is used to decide whether arrays should be split up into
+/// multiple lines.
+fn write_val(out: &mut String, line_start: usize, val: &SimpleVal) {
+    match val {
+        SimpleVal::Table(table) => {
+            if table.is_empty() {
+                out.push_str("{}");
+                return;
+            }
+            out.push_str("{ ");
+            for (i, (k, v)) in table.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_key(out, k);
+                out.push_str(" = ");
+                write_val(out, line_start, v);
+            }
+            out.push_str(" }");
+        }
+        SimpleVal::Array(array) => {
+            let array_start = out.len();
+            out.push('[');
+            for (i, v) in array.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_val(out, line_start, v);
+            }
+            out.push(']');
+
+            let multiline = out[line_start..].contains('\n');
+            if array.is_empty() || (!multiline && out.len() - line_start <= MAX_WIDTH) {
+                return;
+            }
+
+            // split up into multiple lines
+            out.truncate(array_start);
+            let line = &out[line_start..];
+            let indent = line[..line.len() - line.trim_start().len()].to_string();
+            out.push_str("[\n");
+            for v in array.iter() {
+                let line_start = out.len();
+                out.push_str(&indent);
+                out.push_str(INDENT);
+                write_val(out, line_start, v);
+                out.push_str(",\n");
+            }
+            out.push_str(&indent);
+            out.push(']');
+        }
+        SimpleVal::String(s) => write_string(out, s),
+        SimpleVal::Int(i) => _ = write!(out, "{i}"),
+        SimpleVal::Float(f) => {
+            if f.is_nan() {
+                out.push_str("nan");
+            } else if f.is_infinite() {
+                out.push_str(if *f < 0.0 { "-inf" } else { "inf" });
+            } else {
+                // debug formatting always includes a decimal point or exponent
+                _ = write!(out, "{f:?}");
+            }
+        }
+        SimpleVal::Bool(b) => _ = write!(out, "{b}"),
+        SimpleVal::DateTime(d) => write_datetime(out, d),
+        SimpleVal::Invalid(s) => out.push_str(s),
+    }
+}
+
+fn write_datetime(out: &mut String, datetime: &DateTime) {
+    let (date, time, offset) = match datetime {
+        DateTime::OffsetDateTime(d, t, o) => (Some(d), Some(t), Some(o)),
+        DateTime::LocalDateTime(d, t) => (Some(d), Some(t), None),
+        DateTime::LocalDate(d) => (Some(d), None, None),
+        DateTime::LocalTime(t) => (None, Some(t), None),
+    };
+    if let Some(d) = date {
+        _ = write!(out, "{:04}-{:02}-{:02}", d.year, d.month, d.day);
+    }
+    if date.is_some() && time.is_some() {
+        out.push('T');
+    }
+    if let Some(t) = time {
+        _ = write!(out, "{:02}:{:02}:{:02}", t.hour, t.minute, t.second);
+        if t.nanos > 0 {
+            let nanos = format!("{:09}", t.nanos);
+            out.push('.');
+            out.push_str(nanos.trim_end_matches('0'));
+        }
+    }
+    match offset {
+        Some(Offset::Utc) => out.push('Z'),
+        Some(Offset::Custom(minutes)) => {
+            let sign = if *minutes < 0 { '-' } else { '+' };
+            let minutes = minutes.unsigned_abs();
+            _ = write!(out, "{sign}{:02}:{:02}", minutes / 60, minutes % 60);
+        }
+        None => (),
+    }
+}
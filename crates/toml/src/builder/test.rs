@@ -0,0 +1,158 @@
+use bumpalo::Bump;
+use pretty_assertions::assert_eq;
+
+use super::*;
+use crate::datetime::{Date, Time};
+use crate::{Error, TomlCtx, TomlDiagnostics, Warning};
+
+#[track_caller]
+fn check(builder: DocumentBuilder, expected: &str) {
+    let text = builder.render();
+    assert_eq!(expected, text);
+
+    let mut ctx = TomlDiagnostics::default();
+    let bump = Bump::new();
+    let tokens = ctx.lex(&bump, &text);
+    let asts = ctx.parse(&bump, &tokens);
+    let map = ctx.map(&asts);
+    assert_eq!(Vec::<Error>::new(), ctx.errors);
+    assert_eq!(Vec::<Warning>::new(), ctx.warnings);
+    assert_eq!(builder.to_simple(), crate::util::map_simple(map));
+}
+
+#[test]
+fn tables() {
+    let builder = DocumentBuilder::new()
+        .comment("top level")
+        .key("a", 1)
+        .table("package")
+        .key("name", "foo")
+        .key("edition", "2021")
+        .table(["target", "cfg(unix)", "dependencies"])
+        .key("libc", "0.2")
+        .key(["serde", "version"], "1");
+    check(
+        builder,
+        r#"# top level
+a = 1
+
+[package]
+name = "foo"
+edition = "2021"
+
+[target."cfg(unix)".dependencies]
+libc = "0.2"
+serde.version = "1"
+"#,
+    );
+}
+
+#[test]
+fn array_tables() {
+    let builder = DocumentBuilder::new()
+        .array_table("bin")
+        .key("name", "a")
+        .array_table("bin")
+        .key("name", "b")
+        .table(["bin", "metadata"])
+        .key("x", true);
+    check(
+        builder,
+        r#"[[bin]]
+name = "a"
+
+[[bin]]
+name = "b"
+
+[bin.metadata]
+x = true
+"#,
+    );
+}
+
+#[test]
+fn comments_and_blank_lines() {
+    let builder = DocumentBuilder::new()
+        .comment("first\n\nsecond")
+        .blank_line()
+        .comment("")
+        .comment("header")
+        .table("a")
+        .blank_line()
+        .key("b", 1.5);
+    check(
+        builder,
+        r#"# first
+#
+# second
+
+#
+# header
+[a]
+
+b = 1.5
+"#,
+    );
+}
+
+#[test]
+fn values() {
+    let datetime = DateTime::OffsetDateTime(
+        Date::new(2023, 12, 5),
+        Time::new(10, 11, 12, 500_000_000),
+        Offset::Custom(-90),
+    );
+    let table = SimpleVal::Table(MapInner::from_iter([
+        ("workspace".to_string(), SimpleVal::Bool(true)),
+        ("features".to_string(), SimpleVal::from(["derive"])),
+    ]));
+    let builder = DocumentBuilder::new()
+        .key("str", "quote \" backslash \\ newline \n bell \u{7}")
+        .key("key with spaces", -3)
+        .key("float", 3.0)
+        .key("inf", f64::NEG_INFINITY)
+        .key("datetime", datetime)
+        .key("date", DateTime::LocalDate(Date::new(2000, 1, 1)))
+        .key("empty", SimpleVal::Table(MapInner::new()))
+        .key("serde", table)
+        .key("nested", [vec![1, 2], vec![]]);
+    check(
+        builder,
+        r#"str = "quote \" backslash \\ newline \n bell \u0007"
+"key with spaces" = -3
+float = 3.0
+inf = -inf
+datetime = 2023-12-05T10:11:12.5-01:30
+date = 2000-01-01
+empty = {}
+serde = { workspace = true, features = ["derive"] }
+nested = [[1, 2], []]
+"#,
+    );
+}
+
+#[test]
+fn long_array() {
+    let features = [
+        "alpha-feature",
+        "beta-feature",
+        "gamma-feature",
+        "delta-feature",
+        "epsilon-feature",
+    ];
+    let builder = DocumentBuilder::new()
+        .table("features")
+        .key("default", features);
+    check(
+        builder,
+        r#"[features]
+default = [
+    "alpha-feature",
+    "beta-feature",
+    "gamma-feature",
+    "delta-feature",
+    "epsilon-feature",
+]
+"#,
+    );
+}
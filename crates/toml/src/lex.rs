@@ -1,4 +1,4 @@
-use std::ops::ControlFlow;
+use std::ops::{ControlFlow, Range};
 use std::str::Chars;
 
 use bumpalo::collections::String as BString;
@@ -224,15 +224,19 @@ struct Lexer<'a> {
 }
 
 impl<'a> Lexer<'a> {
-    fn new(bump: &'a Bump, input: &'a str) -> Self {
+    /// Create a lexer that starts at byte `start` of `input`.
+    fn new(bump: &'a Bump, input: &'a str, start: usize) -> Self {
+        let preceding = &input[..start];
+        let line_idx = preceding.bytes().filter(|b| *b == b'\n').count() as u32;
+        let line_byte_start = preceding.rfind('\n').map_or(0, |i| i + 1);
         Self {
             bump,
             input,
-            chars: input.chars(),
+            chars: input[start..].chars(),
 
-            line_idx: 0,
-            line_byte_start: 0,
-            byte_pos: 0,
+            line_idx,
+            line_byte_start,
+            byte_pos: start,
 
             in_lit: false,
             lit_start: Pos::default(),
@@ -328,7 +332,25 @@ impl<'a> StrState<'a> {
 }
 
 pub fn lex<'a>(ctx: &mut impl TomlCtx, bump: &'a Bump, input: &'a str) -> Tokens<'a> {
-    let mut lexer = Lexer::new(bump, input);
+    let lexer = Lexer::new(bump, input, 0);
+    lex_with(ctx, bump, lexer)
+}
+
+/// Lex a toml document embedded in a larger `host` file, for example the manifest inside the
+/// frontmatter of a cargo script. Only the bytes inside `range` are lexed, but all positions
+/// are relative to the start of `host`.
+pub fn lex_embedded<'a>(
+    ctx: &mut impl TomlCtx,
+    bump: &'a Bump,
+    host: &'a str,
+    range: Range<usize>,
+) -> Tokens<'a> {
+    let lexer = Lexer::new(bump, &host[..range.end], range.start);
+    lex_with(ctx, bump, lexer)
+}
+
+fn lex_with<'a>(ctx: &mut impl TomlCtx, bump: &'a Bump, mut lexer: Lexer<'a>) -> Tokens<'a> {
+    let input = lexer.input;
     while let Some(c) = lexer.next() {
         match c {
             '\r' if lexer.peek() == Some('\n') => {
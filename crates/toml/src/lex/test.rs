@@ -889,3 +889,50 @@ fn crlf() {
         },
     );
 }
+
+#[test]
+fn embedded() {
+    let host = "fn main() {}\n---\nab = 'c'\n---\n";
+    let start = host.find("ab").unwrap();
+    let end = host.rfind("---").unwrap();
+
+    let mut ctx = TomlDiagnostics::default();
+    let bump = Bump::new();
+    let tokens = ctx.lex_embedded(&bump, host, start..end);
+    assert_eq!(std::vec::Vec::<Error>::new(), ctx.errors);
+    assert_eq!(
+        Tokens {
+            tokens: &[
+                Token {
+                    ty: TokenType::LiteralOrIdent(LiteralId(0)),
+                    start: Pos { line: 2, char: 0 },
+                },
+                Token {
+                    ty: TokenType::Equal,
+                    start: Pos { line: 2, char: 3 },
+                },
+                Token {
+                    ty: TokenType::String(StringId(0)),
+                    start: Pos { line: 2, char: 5 },
+                },
+                Token {
+                    ty: TokenType::Newline,
+                    start: Pos { line: 2, char: 8 },
+                },
+            ],
+            strings: &[StringToken {
+                quote: Quote::Literal,
+                lit: "'c'",
+                lit_end: Pos { line: 2, char: 8 },
+                text: "c",
+                text_offset: TextOffset::chars(1, 1),
+            }],
+            literals: &["ab"],
+            eof: Token {
+                ty: TokenType::EOF,
+                start: Pos { line: 3, char: 0 },
+            },
+        },
+        tokens
+    );
+}
@@ -1,11 +1,15 @@
+use std::ops::Range;
+
 use bumpalo::Bump;
 use common::{Ctx, Diagnostics};
 
+pub use builder::DocumentBuilder;
 pub use error::{Error, Hint, Info, Warning};
-pub use lex::{lex, Quote, Token, TokenType, Tokens};
+pub use lex::{lex, lex_embedded, Quote, Token, TokenType, Tokens};
 pub use map::{map, MapTable};
 pub use parse::{parse, Ast, Asts};
 
+pub mod builder;
 pub mod datetime;
 pub mod error;
 mod lex;
@@ -29,6 +33,15 @@ pub trait TomlCtx:
         lex(self, bump, input)
     }
 
+    fn lex_embedded<'a>(
+        &mut self,
+        bump: &'a Bump,
+        host: &'a str,
+        range: Range<usize>,
+    ) -> Tokens<'a> {
+        lex_embedded(self, bump, host, range)
+    }
+
     fn parse<'a>(&mut self, bump: &'a Bump, tokens: &Tokens<'a>) -> Asts<'a> {
         parse(self, bump, tokens)
     }
@@ -7,6 +7,7 @@ use common::{FmtChar, FmtStr, Pos, Span};
 use crate::datetime::{Date, DateTime};
 use crate::lex::{LiteralId, StringId, StringToken, TextOffset, Token, TokenType, Tokens};
 use crate::parse::lit::PartialValue;
+use crate::parse::text::Bias;
 use crate::{Error, Quote, TomlCtx};
 
 pub use lit::LitPart;
@@ -17,6 +18,7 @@ mod lit;
 mod num;
 #[cfg(test)]
 mod test;
+mod text;
 
 pub const RECURSION_LIMIT: u16 = 100;
 
@@ -451,6 +453,26 @@ impl<'a> Ident<'a> {
         let len = self.lit.len() as u32 - (self.text_start_offset + self.text_end_offset) as u32;
         Span::from_pos_len(start, len)
     }
+
+    /// Maps a byte offset inside [`Self::text`] to the position of the corresponding character
+    /// in the source, taking escape sequences into account.
+    pub fn text_pos(&self, offset: u32) -> Pos {
+        text::text_pos(self.quote(), self.lit, self.lit_start, offset, Bias::Start)
+    }
+
+    /// Maps a range of byte offsets inside [`Self::text`] to the corresponding span in the
+    /// source, taking escape sequences into account.
+    pub fn text_range_span(&self, range: std::ops::Range<u32>) -> Span {
+        text_range_span(self.quote(), self.lit, self.lit_start, range)
+    }
+
+    fn quote(&self) -> Option<Quote> {
+        match self.kind {
+            IdentKind::Plain => None,
+            IdentKind::BasicString => Some(Quote::Basic),
+            IdentKind::LiteralString => Some(Quote::Literal),
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -529,6 +551,34 @@ impl<'a> StringVal<'a> {
     pub fn text_span(&self) -> Span {
         self.text_offset.apply_to(self.lit_span)
     }
+
+    /// Maps a byte offset inside [`Self::text`] to the position of the corresponding character
+    /// in the source, taking escape sequences, multi-line strings and line ending backslashes
+    /// into account.
+    pub fn text_pos(&self, offset: u32) -> Pos {
+        let lit_start = self.lit_span.start;
+        text::text_pos(Some(self.quote), self.lit, lit_start, offset, Bias::Start)
+    }
+
+    /// Maps a range of byte offsets inside [`Self::text`] to the corresponding span in the
+    /// source, see [`Self::text_pos`].
+    pub fn text_range_span(&self, range: std::ops::Range<u32>) -> Span {
+        text_range_span(Some(self.quote), self.lit, self.lit_span.start, range)
+    }
+}
+
+fn text_range_span(
+    quote: Option<Quote>,
+    lit: &str,
+    lit_start: Pos,
+    range: std::ops::Range<u32>,
+) -> Span {
+    let start = text::text_pos(quote, lit, lit_start, range.start, Bias::Start);
+    if range.is_empty() {
+        return Span::pos(start);
+    }
+    let end = text::text_pos(quote, lit, lit_start, range.end, Bias::End);
+    Span::new(start, end)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -1281,3 +1281,47 @@ fn local_time_hour_out_of_range() {
         ),
     );
 }
+
+#[track_caller]
+fn check_string_text_span(input: &str, range: std::ops::Range<u32>, expected: Span) {
+    let mut ctx = TomlDiagnostics::default();
+    let bump = Bump::new();
+    let tokens = ctx.lex(&bump, input);
+    let asts = ctx.parse(&bump, &tokens);
+    assert_eq!(Vec::<Error>::new(), ctx.errors);
+
+    let Some(Ast::Assignment(assignment)) = asts.asts.first() else {
+        panic!("expected assignment");
+    };
+    let Value::String(str) = &assignment.assignment.val else {
+        panic!("expected string");
+    };
+    assert_eq!(expected, str.text_range_span(range));
+}
+
+fn span(start: (u32, u32), end: (u32, u32)) -> Span {
+    Span::new(Pos::new(start.0, start.1), Pos::new(end.0, end.1))
+}
+
+#[test]
+fn string_text_span_plain() {
+    check_string_text_span(r#"a = "abcdef""#, 1..3, span((0, 6), (0, 8)));
+    check_string_text_span(r#"a = 'abcdef'"#, 0..6, span((0, 5), (0, 11)));
+    check_string_text_span(r#"a = "abcdef""#, 3..3, span((0, 8), (0, 8)));
+}
+
+#[test]
+fn string_text_span_escapes() {
+    // `\u0031` evaluates to `1`
+    check_string_text_span(r#"a = "\u0031.2.3""#, 0..1, span((0, 5), (0, 11)));
+    check_string_text_span(r#"a = "\u0031.2.3""#, 1..3, span((0, 11), (0, 13)));
+    check_string_text_span(r#"a = "x\ty""#, 1..3, span((0, 6), (0, 9)));
+    check_string_text_span(r#"a = "\U0001F600 b""#, 4..6, span((0, 15), (0, 17)));
+}
+
+#[test]
+fn string_text_span_multiline() {
+    check_string_text_span("a = \"\"\"\nabc\ndef\"\"\"", 4..7, span((2, 0), (2, 3)));
+    check_string_text_span("a = '''\r\nabc\r\ndef'''", 3..5, span((1, 3), (2, 1)));
+    check_string_text_span("a = \"\"\"abc \\\n   def\"\"\"", 2..5, span((0, 9), (1, 4)));
+}
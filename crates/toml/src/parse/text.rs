@@ -0,0 +1,134 @@
+//! Mapping from byte offsets inside the evaluated text of a string back to positions inside
+//! the source file.
+
+use common::Pos;
+
+use crate::Quote;
+
+/// Which position to choose when an offset doesn't map to exactly one position, for example
+/// because it is inside the evaluated text of an escape sequence, or because a line ending
+/// backslash doesn't produce any text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Bias {
+    /// The position of the first source character producing text at the offset, skipping
+    /// line ending backslashes.
+    Start,
+    /// The position after the last source character producing text before the offset.
+    End,
+}
+
+/// Maps a byte `offset` into the evaluated text of the string literal `lit`, which starts at
+/// `lit_start`, to a position in the source. `quote` is `None` for plain identifiers.
+pub(crate) fn text_pos(
+    quote: Option<Quote>,
+    lit: &str,
+    lit_start: Pos,
+    offset: u32,
+    bias: Bias,
+) -> Pos {
+    let Some(quote) = quote else {
+        let offset = offset.min(lit.len() as u32);
+        return lit_start.plus(offset);
+    };
+
+    let bytes = lit.as_bytes();
+    let mut pos = lit_start.plus(quote.len());
+    let mut idx = quote.len() as usize;
+    let mut text_idx = 0;
+
+    // > A newline immediately following the opening delimiter will be trimmed
+    if quote.is_multiline() {
+        match bytes.get(idx..idx + 2) {
+            Some([b'\r', b'\n']) => idx += 2,
+            _ if bytes.get(idx) == Some(&b'\n') => idx += 1,
+            _ => (),
+        }
+        if idx > quote.len() as usize {
+            pos = Pos::new(pos.line + 1, 0);
+        }
+    }
+
+    if bias == Bias::End && offset == 0 {
+        return pos;
+    }
+
+    while idx < bytes.len() {
+        let (len, new_pos, text_len) = next_segment(quote, lit, idx, pos);
+
+        match bias {
+            Bias::Start => {
+                if text_len > 0 && text_idx + text_len > offset {
+                    return pos;
+                }
+            }
+            Bias::End => {
+                if text_len > 0 && text_idx + text_len >= offset {
+                    return new_pos;
+                }
+            }
+        }
+
+        idx += len;
+        pos = new_pos;
+        text_idx += text_len;
+    }
+
+    pos
+}
+
+/// Returns the number of bytes of the segment starting at `idx`, the position after the
+/// segment and the number of bytes of the evaluated text it produces.
+fn next_segment(quote: Quote, lit: &str, idx: usize, pos: Pos) -> (usize, Pos, u32) {
+    let bytes = lit.as_bytes();
+    match bytes[idx] {
+        b'\\' if quote.is_basic() => {
+            let Some(&esc) = bytes.get(idx + 1) else {
+                return (1, pos.plus(1), 0);
+            };
+            match esc {
+                b'u' | b'U' => {
+                    let num_chars = if esc == b'u' { 4 } else { 8 };
+                    let digits = (lit[idx + 2..].bytes())
+                        .take(num_chars)
+                        .take_while(u8::is_ascii_hexdigit)
+                        .count();
+                    let cp = u32::from_str_radix(&lit[idx + 2..idx + 2 + digits], 16).ok();
+                    let text_len = match cp.and_then(char::from_u32) {
+                        Some(c) if digits == num_chars => c.len_utf8() as u32,
+                        _ => 0,
+                    };
+                    let len = 2 + digits;
+                    (len, pos.plus(len as u32), text_len)
+                }
+                b'b' | b't' | b'n' | b'f' | b'r' | b'"' | b'\\' => (2, pos.plus(2), 1),
+                b' ' | b'\t' | b'\r' | b'\n' => {
+                    // line ending backslash, all whitespace including newlines is trimmed
+                    let mut len = 1;
+                    let mut pos = pos.plus(1);
+                    while let Some(&b) = bytes.get(idx + len) {
+                        match b {
+                            b' ' | b'\t' | b'\r' => pos = pos.plus(1),
+                            b'\n' => pos = Pos::new(pos.line + 1, 0),
+                            _ => break,
+                        }
+                        len += 1;
+                    }
+                    (len, pos, 0)
+                }
+                _ => {
+                    // invalid escape characters don't produce any text
+                    let len = 1 + lit[idx + 1..].chars().next().map_or(0, char::len_utf8);
+                    (len, pos.plus(len as u32), 0)
+                }
+            }
+        }
+        b'\r' if quote.is_multiline() && bytes.get(idx + 1) == Some(&b'\n') => {
+            (2, Pos::new(pos.line + 1, 0), 1)
+        }
+        b'\n' => (1, Pos::new(pos.line + 1, 0), 1),
+        _ => {
+            let len = lit[idx..].chars().next().map_or(1, char::len_utf8);
+            (len, pos.plus(len as u32), len as u32)
+        }
+    }
+}
@@ -1,11 +1,13 @@
 local M = {}
 
 local ns = vim.api.nvim_create_namespace("crates_nvim.diagnostics")
+local inactive_ns = vim.api.nvim_create_namespace("crates_nvim.inactive")
 
 ---@class VimDiagnostics
 ---@field errors VimDiagnostic[]
 ---@field warnings VimDiagnostic[]
 ---@field infos VimDiagnostic[]
+---@field inactive VimRange[]
 
 ---@class VimDiagnostic
 ---@field lnum integer
@@ -14,6 +16,25 @@ local ns = vim.api.nvim_create_namespace("crates_nvim.diagnostics")
 ---@field end_col integer
 ---@field message string
 
+---@class VimRange
+---@field lnum integer
+---@field end_lnum integer
+---@field col integer
+---@field end_col integer
+
+---@class PlatformOptions
+---@field target string|nil a target triple, without `cfg_file` one of the built-in targets
+---@field cfg_file string|nil a file with the output of `rustc --print cfg`
+
+---@type PlatformOptions
+M.platform = {}
+
+---Select the platform used to dim inactive dependencies, by default it's the host platform.
+---@param opts PlatformOptions|nil
+function M.setup(opts)
+    M.platform = opts or {}
+end
+
 ---@param d VimDiagnostic
 ---@param severity integer
 local function to_vim_diagnostic(d, severity)
@@ -24,8 +45,12 @@ end
 
 function M.check_toml()
     local crates_nvim = require("crates_nvim_lib")
+    local platform = nil
+    if M.platform.target or M.platform.cfg_file then
+        platform = { target = M.platform.target, cfg_file = M.platform.cfg_file }
+    end
     ---@type VimDiagnostics
-    local diagnostics = crates_nvim.check_toml()
+    local diagnostics = crates_nvim.check_toml(platform)
     local bufnr = vim.api.nvim_get_current_buf()
     local vim_diagnostics = {}
     for _, e in ipairs(diagnostics.errors) do
@@ -42,6 +67,16 @@ function M.check_toml()
     end
 
     vim.diagnostic.set(ns, bufnr, vim_diagnostics, {})
+
+    -- dim dependencies that aren't used on the selected platform
+    vim.api.nvim_buf_clear_namespace(bufnr, inactive_ns, 0, -1)
+    for _, r in ipairs(diagnostics.inactive) do
+        vim.api.nvim_buf_set_extmark(bufnr, inactive_ns, r.lnum, r.col, {
+            end_row = r.end_lnum,
+            end_col = r.end_col,
+            hl_group = "Comment",
+        })
+    end
 end
 
 return M